@@ -19,7 +19,10 @@ fn run() -> Result<(), error::CliError> {
 
     match &release_matches.step {
         Some(Step::Changes(config)) => config.run(),
+        Some(Step::Status(config)) => config.run(),
+        Some(Step::Announcement(config)) => config.run(),
         Some(Step::Version(config)) => config.run(),
+        Some(Step::PromoteStable(config)) => config.run(),
         Some(Step::Replace(config)) => config.run(),
         Some(Step::Hook(config)) => config.run(),
         Some(Step::Commit(config)) => config.run(),
@@ -28,6 +31,10 @@ fn run() -> Result<(), error::CliError> {
         Some(Step::Tag(config)) => config.run(),
         Some(Step::Push(config)) => config.run(),
         Some(Step::Config(config)) => config.run(),
+        Some(Step::FixConfig(config)) => config.run(),
+        Some(Step::VerifyTags(config)) => config.run(),
+        Some(Step::DeleteTag(config)) => config.run(),
+        Some(Step::NewFragment(config)) => config.run(),
         None => release_matches.release.run(),
     }
 }
@@ -77,7 +84,10 @@ pub struct ReleaseOpt {
 #[derive(Clone, Debug, clap::Subcommand)]
 pub enum Step {
     Changes(steps::changes::ChangesStep),
+    Status(steps::status::StatusStep),
+    Announcement(steps::announcement::AnnouncementStep),
     Version(steps::version::VersionStep),
+    PromoteStable(steps::promote::PromoteStableStep),
     Replace(steps::replace::ReplaceStep),
     Hook(steps::hook::HookStep),
     Commit(steps::commit::CommitStep),
@@ -86,6 +96,10 @@ pub enum Step {
     Tag(steps::tag::TagStep),
     Push(steps::push::PushStep),
     Config(steps::config::ConfigStep),
+    FixConfig(steps::fix_config::FixConfigStep),
+    VerifyTags(steps::verify_tags::VerifyTagsStep),
+    DeleteTag(steps::delete_tag::DeleteTagStep),
+    NewFragment(steps::new_fragment::NewFragmentStep),
 }
 
 #[derive(clap::Args, Debug, Clone)]
@@ -12,31 +12,115 @@ use crate::ops::cargo;
 pub struct Config {
     #[serde(skip)]
     pub is_workspace: bool,
+    /// Base config this file builds on; resolved while loading, never merged
+    pub extends: Option<String>,
+    pub extends_checksum: Option<String>,
+    /// Settings for individual packages, from the workspace config; applied per-package, never merged
+    pub package: Option<std::collections::BTreeMap<String, Config>>,
+    /// Settings for packages whose directory matches a glob, from the workspace config; applied
+    /// per-package, never merged
+    pub paths: Option<std::collections::BTreeMap<String, Config>>,
     pub allow_branch: Option<Vec<String>>,
     pub sign_commit: Option<bool>,
+    pub commit_verify: Option<bool>,
+    pub check_submodules: Option<bool>,
     pub sign_tag: Option<bool>,
-    pub push_remote: Option<String>,
+    pub push_remote: Option<PushRemote>,
     pub registry: Option<String>,
-    pub release: Option<bool>,
+    pub registries: Option<std::collections::BTreeMap<String, RegistryConfig>>,
+    pub publish_interval: Option<Interval>,
+    pub manifest_lints: Option<ManifestLints>,
+    pub verify_readme: Option<bool>,
+    pub verify_doc_links: Option<bool>,
+    pub approval: Option<Approval>,
+    pub notify: Option<Notify>,
+    pub announcement_template: Option<String>,
+    pub announcement_path: Option<String>,
+    pub announcements: Option<Vec<Announcement>>,
+    pub index_timeout: Option<Interval>,
+    pub index_connect_timeout: Option<Interval>,
+    pub index_user_agent_suffix: Option<String>,
+    pub index_http_version: Option<IndexHttpVersion>,
+    pub index_hosts: Option<std::collections::BTreeMap<String, std::net::IpAddr>>,
+    pub index_proxy: Option<String>,
+    pub index_api_fallback: Option<bool>,
+    pub index_cache_max_age: Option<Interval>,
+    pub rehearsal_registry: Option<String>,
+    pub rehearsal_remote: Option<String>,
+    pub allowed_push_remotes: Option<Vec<String>>,
+    pub git_path: Option<String>,
+    pub git_env: Option<std::collections::BTreeMap<String, String>>,
+    pub check_branch_protection: Option<bool>,
+    pub unreleased_dependencies: Option<LintLevel>,
+    pub registry_health: Option<LintLevel>,
+    pub release: Option<ReleaseField>,
     pub publish: Option<bool>,
     pub verify: Option<bool>,
     pub owners: Option<Vec<String>>,
     pub push: Option<bool>,
     pub push_options: Option<Vec<String>>,
+    pub push_strategy: Option<PushStrategy>,
+    pub remotes: Option<std::collections::BTreeMap<String, RemoteConfig>>,
     pub shared_version: Option<SharedVersion>,
+    pub skip_unchanged: Option<bool>,
+    pub change_detection: Option<ChangeDetection>,
+    pub watch_paths: Option<Vec<String>>,
     pub consolidate_commits: Option<bool>,
     pub pre_release_commit_message: Option<String>,
+    pub first_release_commit_message: Option<String>,
+    pub pre_release_commit_body: Option<String>,
+    pub commit_strategy: Option<CommitStrategy>,
+    pub version_commit_message: Option<String>,
     pub pre_release_replacements: Option<Vec<Replace>>,
-    pub pre_release_hook: Option<Command>,
+    pub pre_release_hook: Option<Hooks>,
+    pub hook_env: Option<std::collections::BTreeMap<String, String>>,
+    pub hook_clean_env: Option<bool>,
+    pub hook_timeout: Option<Interval>,
+    pub hook_on_failure: Option<HookOnFailure>,
+    pub capture_hook_output: Option<bool>,
+    pub hook_log_dir: Option<String>,
+    pub release_manifest: Option<String>,
+    pub release_manifest_signature: Option<ManifestSignature>,
+    pub release_record: Option<String>,
+    pub changelog_path: Option<String>,
+    pub changelog_fragments: Option<String>,
+    pub commit_notes_template: Option<String>,
+    pub require_changelog_update: Option<bool>,
+    pub keep_a_changelog: Option<bool>,
+    pub changelog_links: Option<bool>,
+    pub contributor_handles: Option<bool>,
+    pub msrv_policy: Option<String>,
+    pub update_msrv: Option<bool>,
     pub tag_message: Option<String>,
     pub tag_prefix: Option<String>,
     pub tag_name: Option<String>,
     pub tag: Option<bool>,
+    pub umbrella_tag_name: Option<String>,
+    pub umbrella_tag_message: Option<String>,
+    pub dev_version_ext: Option<String>,
     pub enable_features: Option<Vec<String>>,
     pub enable_all_features: Option<bool>,
+    pub verify_features: Option<Vec<String>>,
     pub dependent_version: Option<DependentVersion>,
+    pub dependent_version_overrides: Option<std::collections::BTreeMap<String, DependentVersion>>,
+    pub dependent_prerelease: Option<DependentPrerelease>,
     pub metadata: Option<MetadataPolicy>,
     pub target: Option<String>,
+    pub verify_targets: Option<Vec<String>>,
+    pub verify_profile: Option<String>,
+    pub verify_command: Option<Vec<Command>>,
+    pub verify_args: Option<Vec<String>>,
+    pub publish_locked: Option<bool>,
+    pub release_target_dir: Option<String>,
+    pub verify_toolchain: Option<String>,
+    pub publish_order: Option<Vec<Vec<String>>>,
+    pub update_lockfile: Option<UpdateLockfile>,
+    pub confirm: Option<Vec<ConfirmAction>>,
+    pub max_version: Option<String>,
+    pub zero_ver_breaking: Option<ZeroVerBreaking>,
+    pub prerelease_tags: Option<PrereleaseTags>,
+    #[serde(rename = "await")]
+    pub await_targets: Option<Vec<AwaitTarget>>,
 }
 
 impl Config {
@@ -48,6 +132,10 @@ impl Config {
         let empty = Config::new();
         Config {
             is_workspace: true,
+            extends: None,
+            extends_checksum: None,
+            package: None,
+            paths: None,
             allow_branch: Some(
                 empty
                     .allow_branch()
@@ -55,12 +143,15 @@ impl Config {
                     .collect::<Vec<String>>(),
             ),
             sign_commit: Some(empty.sign_commit()),
+            commit_verify: Some(empty.commit_verify()),
+            check_submodules: Some(empty.check_submodules()),
             sign_tag: Some(empty.sign_tag()),
-            push_remote: Some(empty.push_remote().to_owned()),
+            push_remote: Some(PushRemote::One(empty.push_remote().to_owned())),
             registry: empty.registry().map(|s| s.to_owned()),
-            release: Some(empty.release()),
+            release: Some(ReleaseField::Enabled(empty.release())),
             publish: Some(empty.publish()),
-            verify: Some(empty.verify()),
+            // Left unset so `[registries.<name>] verify` can supply the default
+            verify: None,
             owners: Some(empty.owners().to_vec()),
             push: Some(empty.push()),
             push_options: Some(
@@ -72,19 +163,94 @@ impl Config {
             shared_version: empty
                 .shared_version()
                 .map(|s| SharedVersion::Name(s.to_owned())),
+            skip_unchanged: Some(empty.skip_unchanged()),
+            change_detection: Some(empty.change_detection()),
+            watch_paths: Some(empty.watch_paths().to_vec()),
             consolidate_commits: Some(empty.consolidate_commits()),
             pre_release_commit_message: Some(empty.pre_release_commit_message().to_owned()),
+            pre_release_commit_body: Some(empty.pre_release_commit_body().to_owned()),
+            commit_strategy: Some(empty.commit_strategy()),
+            version_commit_message: Some(empty.version_commit_message().to_owned()),
+            first_release_commit_message: empty
+                .first_release_commit_message()
+                .map(|s| s.to_owned()),
             pre_release_replacements: Some(empty.pre_release_replacements().to_vec()),
             pre_release_hook: empty.pre_release_hook().cloned(),
+            hook_env: Some(Default::default()),
+            hook_clean_env: Some(empty.hook_clean_env()),
+            hook_timeout: empty.hook_timeout().map(Interval),
+            hook_on_failure: Some(empty.hook_on_failure()),
+            capture_hook_output: Some(empty.capture_hook_output()),
+            hook_log_dir: empty.hook_log_dir().map(|s| s.to_owned()),
+            release_manifest: empty.release_manifest().map(|s| s.to_owned()),
+            release_manifest_signature: empty.release_manifest_signature(),
+            release_record: empty.release_record().map(|s| s.to_owned()),
+            changelog_path: Some(empty.changelog_path().to_owned()),
+            changelog_fragments: empty.changelog_fragments().map(|s| s.to_owned()),
+            commit_notes_template: Some(empty.commit_notes_template().to_owned()),
+            require_changelog_update: Some(empty.require_changelog_update()),
+            keep_a_changelog: Some(empty.keep_a_changelog()),
+            changelog_links: Some(empty.changelog_links()),
+            msrv_policy: empty.msrv_policy().map(|s| s.to_owned()),
+            update_msrv: Some(empty.update_msrv()),
+            contributor_handles: Some(empty.contributor_handles()),
             tag_message: Some(empty.tag_message().to_owned()),
             tag_prefix: None, // Skipping, its location dependent
             tag_name: Some(empty.tag_name().to_owned()),
             tag: Some(empty.tag()),
+            umbrella_tag_name: empty.umbrella_tag_name().map(|s| s.to_owned()),
+            dev_version_ext: Some(empty.dev_version_ext().to_owned()),
+            umbrella_tag_message: Some(empty.umbrella_tag_message().to_owned()),
             enable_features: Some(empty.enable_features().to_vec()),
             enable_all_features: Some(empty.enable_all_features()),
+            verify_features: None,
             dependent_version: Some(empty.dependent_version()),
+            dependent_version_overrides: Some(Default::default()),
+            dependent_prerelease: empty.dependent_prerelease(),
             metadata: Some(empty.metadata()),
             target: None,
+            verify_targets: Some(empty.verify_targets().to_vec()),
+            verify_profile: empty.verify_profile().map(|s| s.to_owned()),
+            verify_command: Some(empty.verify_command().to_vec()),
+            verify_args: Some(empty.verify_args().to_vec()),
+            publish_locked: Some(empty.publish_locked()),
+            release_target_dir: None,
+            verify_toolchain: empty.verify_toolchain().map(|s| s.to_owned()),
+            publish_order: Some(empty.publish_order().to_vec()),
+            update_lockfile: Some(empty.update_lockfile()),
+            confirm: Some(empty.confirm().to_vec()),
+            max_version: empty.max_version().map(|s| s.to_owned()),
+            zero_ver_breaking: Some(empty.zero_ver_breaking()),
+            prerelease_tags: Some(empty.prerelease_tags()),
+            registries: Some(Default::default()),
+            push_strategy: Some(empty.push_strategy()),
+            remotes: Some(Default::default()),
+            publish_interval: empty.publish_interval().map(Interval),
+            manifest_lints: Some(empty.manifest_lints().clone()),
+            verify_readme: Some(empty.verify_readme()),
+            verify_doc_links: Some(empty.verify_doc_links()),
+            approval: empty.approval().cloned(),
+            notify: empty.notify().cloned(),
+            announcement_template: Some(empty.announcement_template().to_owned()),
+            announcement_path: Some(empty.announcement_path().to_owned()),
+            announcements: Some(empty.announcements().to_vec()),
+            index_timeout: empty.index_timeout().map(Interval),
+            index_connect_timeout: empty.index_connect_timeout().map(Interval),
+            index_user_agent_suffix: empty.index_user_agent_suffix().map(|s| s.to_owned()),
+            index_http_version: Some(empty.index_http_version()),
+            index_hosts: None,
+            index_proxy: None,
+            index_api_fallback: Some(empty.index_api_fallback()),
+            index_cache_max_age: Some(Interval(empty.index_cache_max_age())),
+            rehearsal_registry: empty.rehearsal_registry().map(|s| s.to_owned()),
+            rehearsal_remote: empty.rehearsal_remote().map(|s| s.to_owned()),
+            allowed_push_remotes: Some(empty.allowed_push_remotes().to_owned()),
+            git_path: empty.git_path().map(|s| s.to_owned()),
+            git_env: Some(Default::default()),
+            check_branch_protection: Some(empty.check_branch_protection()),
+            unreleased_dependencies: Some(empty.unreleased_dependencies()),
+            registry_health: Some(empty.registry_health()),
+            await_targets: Some(empty.await_targets().to_vec()),
         }
     }
 
@@ -95,11 +261,17 @@ impl Config {
         if let Some(sign_commit) = source.sign_commit {
             self.sign_commit = Some(sign_commit);
         }
+        if let Some(commit_verify) = source.commit_verify {
+            self.commit_verify = Some(commit_verify);
+        }
+        if let Some(check_submodules) = source.check_submodules {
+            self.check_submodules = Some(check_submodules);
+        }
         if let Some(sign_tag) = source.sign_tag {
             self.sign_tag = Some(sign_tag);
         }
-        if let Some(push_remote) = source.push_remote.as_deref() {
-            self.push_remote = Some(push_remote.to_owned());
+        if let Some(push_remote) = source.push_remote.as_ref() {
+            self.push_remote = Some(push_remote.clone());
         }
         if let Some(registry) = source.registry.as_deref() {
             self.registry = Some(registry.to_owned());
@@ -122,48 +294,283 @@ impl Config {
         if let Some(push_options) = source.push_options.as_deref() {
             self.push_options = Some(push_options.to_owned());
         }
+        if let Some(push_strategy) = source.push_strategy {
+            self.push_strategy = Some(push_strategy);
+        }
+        if let Some(remotes) = source.remotes.as_ref() {
+            self.remotes
+                .get_or_insert_with(Default::default)
+                .extend(remotes.clone());
+        }
         if let Some(shared_version) = source.shared_version.clone() {
             self.shared_version = Some(shared_version);
         }
+        if let Some(skip_unchanged) = source.skip_unchanged {
+            self.skip_unchanged = Some(skip_unchanged);
+        }
+        if let Some(change_detection) = source.change_detection {
+            self.change_detection = Some(change_detection);
+        }
+        if let Some(watch_paths) = source.watch_paths.as_deref() {
+            self.watch_paths = Some(watch_paths.to_owned());
+        }
         if let Some(consolidate_commits) = source.consolidate_commits {
             self.consolidate_commits = Some(consolidate_commits);
         }
         if let Some(pre_release_commit_message) = source.pre_release_commit_message.as_deref() {
             self.pre_release_commit_message = Some(pre_release_commit_message.to_owned());
         }
+        if let Some(pre_release_commit_body) = source.pre_release_commit_body.as_deref() {
+            self.pre_release_commit_body = Some(pre_release_commit_body.to_owned());
+        }
+        if let Some(commit_strategy) = source.commit_strategy {
+            self.commit_strategy = Some(commit_strategy);
+        }
+        if let Some(version_commit_message) = source.version_commit_message.as_deref() {
+            self.version_commit_message = Some(version_commit_message.to_owned());
+        }
+        if let Some(first_release_commit_message) = source.first_release_commit_message.as_deref() {
+            self.first_release_commit_message = Some(first_release_commit_message.to_owned());
+        }
         if let Some(pre_release_replacements) = source.pre_release_replacements.as_deref() {
             self.pre_release_replacements = Some(pre_release_replacements.to_owned());
         }
         if let Some(pre_release_hook) = source.pre_release_hook.as_ref() {
             self.pre_release_hook = Some(pre_release_hook.to_owned());
         }
+        if let Some(hook_env) = source.hook_env.as_ref() {
+            let mut env = self.hook_env.take().unwrap_or_default();
+            env.extend(hook_env.iter().map(|(k, v)| (k.clone(), v.clone())));
+            self.hook_env = Some(env);
+        }
+        if let Some(hook_clean_env) = source.hook_clean_env {
+            self.hook_clean_env = Some(hook_clean_env);
+        }
+        if let Some(hook_timeout) = source.hook_timeout {
+            self.hook_timeout = Some(hook_timeout);
+        }
+        if let Some(hook_on_failure) = source.hook_on_failure {
+            self.hook_on_failure = Some(hook_on_failure);
+        }
+        if let Some(capture_hook_output) = source.capture_hook_output {
+            self.capture_hook_output = Some(capture_hook_output);
+        }
+        if let Some(hook_log_dir) = source.hook_log_dir.as_deref() {
+            self.hook_log_dir = Some(hook_log_dir.to_owned());
+        }
+        if let Some(release_manifest) = source.release_manifest.as_deref() {
+            self.release_manifest = Some(release_manifest.to_owned());
+        }
+        if let Some(release_manifest_signature) = source.release_manifest_signature {
+            self.release_manifest_signature = Some(release_manifest_signature);
+        }
+        if let Some(release_record) = source.release_record.as_deref() {
+            self.release_record = Some(release_record.to_owned());
+        }
+        if let Some(changelog_path) = source.changelog_path.as_deref() {
+            self.changelog_path = Some(changelog_path.to_owned());
+        }
+        if let Some(changelog_fragments) = source.changelog_fragments.as_deref() {
+            self.changelog_fragments = Some(changelog_fragments.to_owned());
+        }
+        if let Some(commit_notes_template) = source.commit_notes_template.as_deref() {
+            self.commit_notes_template = Some(commit_notes_template.to_owned());
+        }
+        if let Some(require_changelog_update) = source.require_changelog_update {
+            self.require_changelog_update = Some(require_changelog_update);
+        }
+        if let Some(keep_a_changelog) = source.keep_a_changelog {
+            self.keep_a_changelog = Some(keep_a_changelog);
+        }
+        if let Some(changelog_links) = source.changelog_links {
+            self.changelog_links = Some(changelog_links);
+        }
+        if let Some(contributor_handles) = source.contributor_handles {
+            self.contributor_handles = Some(contributor_handles);
+        }
+        if let Some(msrv_policy) = source.msrv_policy.as_deref() {
+            self.msrv_policy = Some(msrv_policy.to_owned());
+        }
+        if let Some(update_msrv) = source.update_msrv {
+            self.update_msrv = Some(update_msrv);
+        }
         if let Some(tag_message) = source.tag_message.as_deref() {
             self.tag_message = Some(tag_message.to_owned());
         }
         if let Some(tag_prefix) = source.tag_prefix.as_deref() {
             self.tag_prefix = Some(tag_prefix.to_owned());
         }
+        if let Some(dev_version_ext) = source.dev_version_ext.as_deref() {
+            self.dev_version_ext = Some(dev_version_ext.to_owned());
+        }
         if let Some(tag_name) = source.tag_name.as_deref() {
             self.tag_name = Some(tag_name.to_owned());
         }
         if let Some(tag) = source.tag {
             self.tag = Some(tag);
         }
+        if let Some(umbrella_tag_name) = source.umbrella_tag_name.as_deref() {
+            self.umbrella_tag_name = Some(umbrella_tag_name.to_owned());
+        }
+        if let Some(umbrella_tag_message) = source.umbrella_tag_message.as_deref() {
+            self.umbrella_tag_message = Some(umbrella_tag_message.to_owned());
+        }
         if let Some(enable_features) = source.enable_features.as_deref() {
             self.enable_features = Some(enable_features.to_owned());
         }
         if let Some(enable_all_features) = source.enable_all_features {
             self.enable_all_features = Some(enable_all_features);
         }
+        if let Some(verify_features) = source.verify_features.as_deref() {
+            self.verify_features = Some(verify_features.to_owned());
+        }
         if let Some(dependent_version) = source.dependent_version {
             self.dependent_version = Some(dependent_version);
         }
+        if let Some(dependent_version_overrides) = source.dependent_version_overrides.as_ref() {
+            let mut overrides = self.dependent_version_overrides.take().unwrap_or_default();
+            overrides.extend(
+                dependent_version_overrides
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.clone())),
+            );
+            self.dependent_version_overrides = Some(overrides);
+        }
+        if let Some(dependent_prerelease) = source.dependent_prerelease {
+            self.dependent_prerelease = Some(dependent_prerelease);
+        }
         if let Some(metadata) = source.metadata {
             self.metadata = Some(metadata);
         }
         if let Some(target) = source.target.as_deref() {
             self.target = Some(target.to_owned());
         }
+        if let Some(verify_targets) = source.verify_targets.as_deref() {
+            self.verify_targets = Some(verify_targets.to_owned());
+        }
+        if let Some(verify_profile) = source.verify_profile.as_deref() {
+            self.verify_profile = Some(verify_profile.to_owned());
+        }
+        if let Some(verify_command) = source.verify_command.as_deref() {
+            self.verify_command = Some(verify_command.to_owned());
+        }
+        if let Some(release_target_dir) = source.release_target_dir.as_deref() {
+            self.release_target_dir = Some(release_target_dir.to_owned());
+        }
+        if let Some(publish_locked) = source.publish_locked {
+            self.publish_locked = Some(publish_locked);
+        }
+        if let Some(verify_args) = source.verify_args.as_deref() {
+            self.verify_args = Some(verify_args.to_owned());
+        }
+        if let Some(verify_toolchain) = source.verify_toolchain.as_deref() {
+            self.verify_toolchain = Some(verify_toolchain.to_owned());
+        }
+        if let Some(publish_order) = source.publish_order.as_deref() {
+            self.publish_order = Some(publish_order.to_owned());
+        }
+        if let Some(update_lockfile) = source.update_lockfile {
+            self.update_lockfile = Some(update_lockfile);
+        }
+        if let Some(confirm) = source.confirm.as_deref() {
+            self.confirm = Some(confirm.to_owned());
+        }
+        if let Some(max_version) = source.max_version.as_deref() {
+            self.max_version = Some(max_version.to_owned());
+        }
+        if let Some(zero_ver_breaking) = source.zero_ver_breaking {
+            self.zero_ver_breaking = Some(zero_ver_breaking);
+        }
+        if let Some(prerelease_tags) = source.prerelease_tags {
+            self.prerelease_tags = Some(prerelease_tags);
+        }
+        if let Some(publish_interval) = source.publish_interval {
+            self.publish_interval = Some(publish_interval);
+        }
+        if let Some(manifest_lints) = source.manifest_lints.as_ref() {
+            self.manifest_lints
+                .get_or_insert_with(Default::default)
+                .update(manifest_lints);
+        }
+        if let Some(verify_readme) = source.verify_readme {
+            self.verify_readme = Some(verify_readme);
+        }
+        if let Some(verify_doc_links) = source.verify_doc_links {
+            self.verify_doc_links = Some(verify_doc_links);
+        }
+        if let Some(approval) = source.approval.as_ref() {
+            self.approval = Some(approval.clone());
+        }
+        if let Some(notify) = source.notify.as_ref() {
+            self.notify = Some(notify.clone());
+        }
+        if let Some(announcement_template) = source.announcement_template.as_deref() {
+            self.announcement_template = Some(announcement_template.to_owned());
+        }
+        if let Some(announcement_path) = source.announcement_path.as_deref() {
+            self.announcement_path = Some(announcement_path.to_owned());
+        }
+        if let Some(announcements) = source.announcements.as_deref() {
+            self.announcements = Some(announcements.to_owned());
+        }
+        if let Some(rehearsal_registry) = source.rehearsal_registry.as_deref() {
+            self.rehearsal_registry = Some(rehearsal_registry.to_owned());
+        }
+        if let Some(rehearsal_remote) = source.rehearsal_remote.as_deref() {
+            self.rehearsal_remote = Some(rehearsal_remote.to_owned());
+        }
+        if let Some(allowed_push_remotes) = source.allowed_push_remotes.as_deref() {
+            self.allowed_push_remotes = Some(allowed_push_remotes.to_owned());
+        }
+        if let Some(git_path) = source.git_path.as_deref() {
+            self.git_path = Some(git_path.to_owned());
+        }
+        if let Some(git_env) = source.git_env.as_ref() {
+            let mut env = self.git_env.take().unwrap_or_default();
+            env.extend(git_env.iter().map(|(k, v)| (k.clone(), v.clone())));
+            self.git_env = Some(env);
+        }
+        if let Some(check_branch_protection) = source.check_branch_protection {
+            self.check_branch_protection = Some(check_branch_protection);
+        }
+        if let Some(unreleased_dependencies) = source.unreleased_dependencies {
+            self.unreleased_dependencies = Some(unreleased_dependencies);
+        }
+        if let Some(registry_health) = source.registry_health {
+            self.registry_health = Some(registry_health);
+        }
+        if let Some(index_timeout) = source.index_timeout {
+            self.index_timeout = Some(index_timeout);
+        }
+        if let Some(index_connect_timeout) = source.index_connect_timeout {
+            self.index_connect_timeout = Some(index_connect_timeout);
+        }
+        if let Some(index_user_agent_suffix) = source.index_user_agent_suffix.as_deref() {
+            self.index_user_agent_suffix = Some(index_user_agent_suffix.to_owned());
+        }
+        if let Some(index_http_version) = source.index_http_version {
+            self.index_http_version = Some(index_http_version);
+        }
+        if let Some(index_hosts) = source.index_hosts.as_ref() {
+            self.index_hosts = Some(index_hosts.clone());
+        }
+        if let Some(index_proxy) = source.index_proxy.as_deref() {
+            self.index_proxy = Some(index_proxy.to_owned());
+        }
+        if let Some(index_api_fallback) = source.index_api_fallback {
+            self.index_api_fallback = Some(index_api_fallback);
+        }
+        if let Some(index_cache_max_age) = source.index_cache_max_age {
+            self.index_cache_max_age = Some(index_cache_max_age);
+        }
+        if let Some(registries) = source.registries.as_ref() {
+            self.registries
+                .get_or_insert_with(Default::default)
+                .extend(registries.clone());
+        }
+        if let Some(await_targets) = source.await_targets.as_deref() {
+            self.await_targets = Some(await_targets.to_owned());
+        }
     }
 
     pub fn allow_branch(&self) -> impl Iterator<Item = &str> {
@@ -177,12 +584,31 @@ impl Config {
         self.sign_commit.unwrap_or(false)
     }
 
+    /// Run git's `pre-commit` and `commit-msg` hooks on release commits
+    pub fn commit_verify(&self) -> bool {
+        self.commit_verify.unwrap_or(true)
+    }
+
+    /// Require submodules to be clean and at their recorded commits before releasing
+    pub fn check_submodules(&self) -> bool {
+        self.check_submodules.unwrap_or(true)
+    }
+
     pub fn sign_tag(&self) -> bool {
         self.sign_tag.unwrap_or(false)
     }
 
+    /// The primary remote, the first of `push-remote`
     pub fn push_remote(&self) -> &str {
-        self.push_remote.as_deref().unwrap_or("origin")
+        self.push_remotes().next().unwrap_or("origin")
+    }
+
+    /// Every remote to push to, starting with the primary
+    pub fn push_remotes(&self) -> impl Iterator<Item = &str> {
+        self.push_remote
+            .as_ref()
+            .map(|r| itertools::Either::Left(r.names()))
+            .unwrap_or_else(|| itertools::Either::Right(IntoIterator::into_iter(["origin"])))
     }
 
     pub fn registry(&self) -> Option<&str> {
@@ -190,15 +616,227 @@ impl Config {
     }
 
     pub fn release(&self) -> bool {
-        self.release.unwrap_or(true)
+        self.release != Some(ReleaseField::Enabled(false))
+    }
+
+    /// Version, commit, and tag the package but never publish it
+    pub fn tag_only(&self) -> bool {
+        self.release == Some(ReleaseField::Mode(ReleaseMode::TagOnly))
     }
 
     pub fn publish(&self) -> bool {
-        self.publish.unwrap_or(true)
+        self.publish.unwrap_or(true) && !self.tag_only()
+    }
+
+    /// Settings for `registry`, keyed by `crates-io` for the default registry
+    pub fn registry_config(&self) -> Option<&RegistryConfig> {
+        self.registries
+            .as_ref()?
+            .get(self.registry().unwrap_or(RegistryConfig::CRATES_IO))
+    }
+
+    /// Status endpoint for `registry`, if known
+    pub fn registry_status_url(&self) -> Option<&str> {
+        self.registry_config()
+            .and_then(|r| r.status_url.as_deref())
+            .or_else(|| {
+                self.registry()
+                    .is_none()
+                    .then_some(crate::ops::registry_status::CRATES_IO_STATUS_URL)
+            })
+    }
+
+    /// Publish rate limit of `registry`, if known
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        self.registry_config()
+            .and_then(|r| r.rate_limit)
+            .or_else(|| self.registry().is_none().then_some(RateLimit::CRATES_IO))
+    }
+
+    /// Environment for `cargo publish` to authenticate and connect to `registry`
+    pub fn registry_env(&self) -> Vec<(String, std::ffi::OsString)> {
+        let mut env = Vec::new();
+        let registry_config = if let Some(registry_config) = self.registry_config() {
+            registry_config
+        } else {
+            return env;
+        };
+        if let Some(token_env) = registry_config.token_env.as_deref() {
+            let var = if let Some(registry) = self.registry() {
+                format!(
+                    "CARGO_REGISTRIES_{}_TOKEN",
+                    registry.to_uppercase().replace('-', "_")
+                )
+            } else {
+                "CARGO_REGISTRY_TOKEN".to_owned()
+            };
+            if let Some(token) = std::env::var_os(token_env) {
+                env.push((var, token));
+            } else {
+                log::debug!("`{token_env}` is unset, leaving `{var}` to cargo");
+            }
+        }
+        if let Some(ca_info) = registry_config.ca_info.as_deref() {
+            env.push(("CARGO_HTTP_CAINFO".to_owned(), ca_info.into()));
+        }
+        env
+    }
+
+    /// Delay between consecutive `cargo publish` calls
+    pub fn publish_interval(&self) -> Option<std::time::Duration> {
+        self.publish_interval.map(|i| i.0)
+    }
+
+    /// How strictly to require optional `Cargo.toml` fields before publishing
+    pub fn manifest_lints(&self) -> &ManifestLints {
+        static DEFAULT: ManifestLints = ManifestLints {
+            readme: None,
+            repository: None,
+            homepage: None,
+            documentation: None,
+            keywords: None,
+            categories: None,
+        };
+        self.manifest_lints.as_ref().unwrap_or(&DEFAULT)
+    }
+
+    pub fn verify_readme(&self) -> bool {
+        self.verify_readme.unwrap_or(false)
+    }
+
+    pub fn verify_doc_links(&self) -> bool {
+        self.verify_doc_links.unwrap_or(false)
+    }
+
+    /// Conditions to meet before publishing
+    pub fn approval(&self) -> Option<&Approval> {
+        self.approval.as_ref()
+    }
+
+    /// Where to announce releases
+    pub fn notify(&self) -> Option<&Notify> {
+        self.notify.as_ref()
+    }
+
+    pub fn announcement_template(&self) -> &str {
+        self.announcement_template.as_deref().unwrap_or(
+            "# Released {{releases}}
+
+{{changelog}}
+
+## Install
+
+{{install}}
+
+## Links
+
+{{links}}
+
+Thanks to everyone who contributed: {{contributors}}
+",
+        )
+    }
+
+    /// Relative to the workspace root
+    pub fn announcement_path(&self) -> &str {
+        self.announcement_path
+            .as_deref()
+            .unwrap_or("ANNOUNCEMENT.md")
+    }
+
+    /// Announcements rendered alongside `announcement-template`, like translations
+    pub fn announcements(&self) -> &[Announcement] {
+        self.announcements.as_deref().unwrap_or(&[])
+    }
+
+    /// Staging registry for `--rehearse`
+    pub fn rehearsal_registry(&self) -> Option<&str> {
+        self.rehearsal_registry.as_deref()
+    }
+
+    /// Staging git remote URL for `--rehearse`
+    pub fn rehearsal_remote(&self) -> Option<&str> {
+        self.rehearsal_remote.as_deref()
+    }
+
+    /// The `git` executable, when not the one on `PATH`
+    pub fn git_path(&self) -> Option<&str> {
+        self.git_path.as_deref()
+    }
+
+    /// Environment variables added to every `git` invocation
+    pub fn git_env(&self) -> &std::collections::BTreeMap<String, String> {
+        static EMPTY: std::collections::BTreeMap<String, String> =
+            std::collections::BTreeMap::new();
+        self.git_env.as_ref().unwrap_or(&EMPTY)
+    }
+
+    /// Globs of remote URLs releases may push to, empty to allow any
+    pub fn allowed_push_remotes(&self) -> &[String] {
+        self.allowed_push_remotes.as_deref().unwrap_or(&[])
+    }
+
+    /// Ask the forge whether the release branch accepts direct pushes before releasing
+    pub fn check_branch_protection(&self) -> bool {
+        self.check_branch_protection.unwrap_or(false)
+    }
+
+    /// Severity for published crates depending on members with changes that aren't being released
+    pub fn unreleased_dependencies(&self) -> LintLevel {
+        self.unreleased_dependencies.unwrap_or(LintLevel::Warn)
+    }
+
+    /// Severity for registries reporting an incident before anything is changed
+    pub fn registry_health(&self) -> LintLevel {
+        self.registry_health.unwrap_or(LintLevel::Allow)
+    }
+
+    pub fn index_timeout(&self) -> Option<std::time::Duration> {
+        self.index_timeout.map(|i| i.0)
+    }
+
+    pub fn index_connect_timeout(&self) -> Option<std::time::Duration> {
+        self.index_connect_timeout.map(|i| i.0)
+    }
+
+    /// Appended to the user-agent of index requests
+    pub fn index_user_agent_suffix(&self) -> Option<&str> {
+        self.index_user_agent_suffix.as_deref()
+    }
+
+    pub fn index_http_version(&self) -> IndexHttpVersion {
+        self.index_http_version.unwrap_or_default()
+    }
+
+    /// Addresses for hosts that can't be looked up in DNS
+    pub fn index_hosts(&self) -> impl Iterator<Item = (&str, std::net::IpAddr)> {
+        self.index_hosts
+            .iter()
+            .flatten()
+            .map(|(host, ip)| (host.as_str(), *ip))
+    }
+
+    /// Proxy for HTTP requests, overriding `HTTPS_PROXY` and friends
+    pub fn index_proxy(&self) -> Option<&str> {
+        self.index_proxy.as_deref()
+    }
+
+    /// Ask the registry's web API when its index can't be reached
+    pub fn index_api_fallback(&self) -> bool {
+        self.index_api_fallback.unwrap_or(false)
+    }
+
+    /// How recently cargo must have updated its cache of a sparse index to skip the network
+    pub fn index_cache_max_age(&self) -> std::time::Duration {
+        self.index_cache_max_age
+            .map(|i| i.0)
+            .unwrap_or(std::time::Duration::from_secs(5 * 60))
     }
 
     pub fn verify(&self) -> bool {
-        self.verify.unwrap_or(true)
+        self.verify
+            .or_else(|| self.registry_config().and_then(|r| r.verify))
+            .unwrap_or(true)
     }
 
     pub fn owners(&self) -> &[String] {
@@ -216,10 +854,48 @@ impl Config {
             .flat_map(|v| v.iter().map(|s| s.as_str()))
     }
 
+    pub fn push_strategy(&self) -> PushStrategy {
+        self.push_strategy.unwrap_or_default()
+    }
+
+    /// Settings for the git remote `remote`, under `[remotes.<name>]`
+    pub fn remote_config(&self, remote: &str) -> Option<&RemoteConfig> {
+        self.remotes.as_ref()?.get(remote)
+    }
+
+    /// What to do when pushing to `remote` fails
+    pub fn remote_on_failure(&self, remote: &str) -> PushFailure {
+        self.remote_config(remote)
+            .and_then(|r| r.on_failure)
+            .unwrap_or_default()
+    }
+
+    /// `push-options` for `remote`, preferring those under `[remotes.<name>]`
+    pub fn remote_push_options(&self, remote: &str) -> impl Iterator<Item = &str> {
+        self.remote_config(remote)
+            .and_then(|r| r.push_options.as_ref())
+            .or(self.push_options.as_ref())
+            .into_iter()
+            .flat_map(|v| v.iter().map(|s| s.as_str()))
+    }
+
     pub fn shared_version(&self) -> Option<&str> {
         self.shared_version.as_ref().and_then(|s| s.as_name())
     }
 
+    pub fn skip_unchanged(&self) -> bool {
+        self.skip_unchanged.unwrap_or(false)
+    }
+
+    pub fn change_detection(&self) -> ChangeDetection {
+        self.change_detection.unwrap_or_default()
+    }
+
+    /// Globs, relative to the workspace root, of files outside the package that count as changes to it
+    pub fn watch_paths(&self) -> &[String] {
+        self.watch_paths.as_deref().unwrap_or(&[])
+    }
+
     pub fn consolidate_commits(&self) -> bool {
         self.consolidate_commits.unwrap_or(self.is_workspace)
     }
@@ -236,6 +912,33 @@ impl Config {
             })
     }
 
+    pub fn commit_strategy(&self) -> CommitStrategy {
+        self.commit_strategy.unwrap_or_default()
+    }
+
+    /// The message for the version bump with `commit-strategy = "split"`
+    pub fn version_commit_message(&self) -> &str {
+        self.version_commit_message.as_deref().unwrap_or_else(|| {
+            if self.consolidate_commits() {
+                "chore: Bump versions"
+            } else {
+                "chore: Bump {{crate_name}} to {{version}}"
+            }
+        })
+    }
+
+    /// A line of the consolidated commit's body, rendered for each released package
+    pub fn pre_release_commit_body(&self) -> &str {
+        self.pre_release_commit_body
+            .as_deref()
+            .unwrap_or("- {{crate_name}} {{prev_version}} -> {{version}}")
+    }
+
+    /// Replaces `pre-release-commit-message` for packages that were never released
+    pub fn first_release_commit_message(&self) -> Option<&str> {
+        self.first_release_commit_message.as_deref()
+    }
+
     pub fn pre_release_replacements(&self) -> &[Replace] {
         self.pre_release_replacements
             .as_ref()
@@ -243,10 +946,101 @@ impl Config {
             .unwrap_or(&[])
     }
 
-    pub fn pre_release_hook(&self) -> Option<&Command> {
+    pub fn pre_release_hook(&self) -> Option<&Hooks> {
         self.pre_release_hook.as_ref()
     }
 
+    /// Environment variables for `pre-release-hook`, where `env:NAME` passes through `NAME`
+    pub fn hook_env(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.hook_env
+            .iter()
+            .flat_map(|env| env.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+    }
+
+    /// Run `pre-release-hook` without inheriting the environment
+    pub fn hook_clean_env(&self) -> bool {
+        self.hook_clean_env.unwrap_or(false)
+    }
+
+    /// How long before `pre-release-hook` is killed
+    pub fn hook_timeout(&self) -> Option<std::time::Duration> {
+        self.hook_timeout.map(|i| i.0)
+    }
+
+    pub fn hook_on_failure(&self) -> HookOnFailure {
+        self.hook_on_failure.unwrap_or_default()
+    }
+
+    /// Show hook output as a section after the hook finishes rather than interleaved
+    pub fn capture_hook_output(&self) -> bool {
+        self.capture_hook_output.unwrap_or(false)
+    }
+
+    /// Directory, relative to the workspace root, to write each hook's output to
+    pub fn hook_log_dir(&self) -> Option<&str> {
+        self.hook_log_dir.as_deref()
+    }
+
+    /// Where to write the release manifest, relative to the workspace root
+    pub fn release_manifest(&self) -> Option<&str> {
+        self.release_manifest.as_deref()
+    }
+
+    pub fn release_manifest_signature(&self) -> Option<ManifestSignature> {
+        self.release_manifest_signature
+    }
+
+    /// Where to record each release, relative to the workspace root
+    pub fn release_record(&self) -> Option<&str> {
+        self.release_record.as_deref()
+    }
+
+    /// Relative to the package root
+    pub fn changelog_path(&self) -> &str {
+        self.changelog_path.as_deref().unwrap_or("CHANGELOG.md")
+    }
+
+    /// Directory of changelog fragments, relative to the package root
+    pub fn changelog_fragments(&self) -> Option<&str> {
+        self.changelog_fragments.as_deref()
+    }
+
+    /// Release notes for when `changelog-path` has none for the version, built from the commits
+    /// since the prior tag
+    pub fn commit_notes_template(&self) -> &str {
+        self.commit_notes_template
+            .as_deref()
+            .unwrap_or("{{commits}}")
+    }
+
+    pub fn require_changelog_update(&self) -> bool {
+        self.require_changelog_update.unwrap_or(false)
+    }
+
+    /// Manage `changelog-path` as a [Keep a Changelog](https://keepachangelog.com/) file
+    pub fn keep_a_changelog(&self) -> bool {
+        self.keep_a_changelog.unwrap_or(false)
+    }
+
+    /// Add missing compare links to the `keep-a-changelog` file, derived from `push-remote`
+    pub fn changelog_links(&self) -> bool {
+        self.changelog_links.unwrap_or(false)
+    }
+
+    /// Show GitHub handles in `{{contributors}}`
+    pub fn contributor_handles(&self) -> bool {
+        self.contributor_handles.unwrap_or(false)
+    }
+
+    /// How far `rust-version` may trail the latest stable Rust, e.g. `"N-2"`
+    pub fn msrv_policy(&self) -> Option<&str> {
+        self.msrv_policy.as_deref()
+    }
+
+    pub fn update_msrv(&self) -> bool {
+        self.update_msrv.unwrap_or(false)
+    }
+
     pub fn tag_message(&self) -> &str {
         self.tag_message
             .as_deref()
@@ -268,6 +1062,22 @@ impl Config {
         self.tag.unwrap_or(true)
     }
 
+    /// The pre-release identifiers marking `{{next_version}}` as a development version
+    pub fn dev_version_ext(&self) -> &str {
+        self.dev_version_ext.as_deref().unwrap_or("alpha.0")
+    }
+
+    /// Workspace-wide tag, independent of any package
+    pub fn umbrella_tag_name(&self) -> Option<&str> {
+        self.umbrella_tag_name.as_deref()
+    }
+
+    pub fn umbrella_tag_message(&self) -> &str {
+        self.umbrella_tag_message
+            .as_deref()
+            .unwrap_or("chore: Release {{tag_name}}")
+    }
+
     pub fn enable_features(&self) -> &[String] {
         self.enable_features
             .as_ref()
@@ -292,70 +1102,853 @@ impl Config {
         }
     }
 
-    pub fn dependent_version(&self) -> DependentVersion {
-        self.dependent_version.unwrap_or_default()
+    /// Features to verify the package with, when they differ from [`Config::features`]
+    pub fn verify_features(&self) -> Option<cargo::Features> {
+        let features = self.verify_features.as_deref()?;
+        if features.is_empty() {
+            Some(cargo::Features::None)
+        } else {
+            Some(cargo::Features::Selective(features.to_owned()))
+        }
+    }
+
+    pub fn dependent_version(&self) -> DependentVersion {
+        self.dependent_version.unwrap_or_default()
+    }
+
+    /// Policy for updating requirements on the dependency `name`
+    pub fn dependent_version_for(&self, name: &str) -> DependentVersion {
+        self.dependent_version_overrides
+            .as_ref()
+            .and_then(|o| o.get(name).copied())
+            .unwrap_or_else(|| self.dependent_version())
+    }
+
+    /// Policy for requirements on a pre-release, `None` to follow `dependent-version`
+    pub fn dependent_prerelease(&self) -> Option<DependentPrerelease> {
+        self.dependent_prerelease
+    }
+
+    pub fn metadata(&self) -> MetadataPolicy {
+        self.metadata.unwrap_or_default()
+    }
+
+    /// Target triples to verify the packaged crate for, in place of the publish build
+    pub fn verify_targets(&self) -> &[String] {
+        self.verify_targets
+            .as_ref()
+            .map(|v| v.as_ref())
+            .unwrap_or(&[])
+    }
+
+    pub fn verify_profile(&self) -> Option<&str> {
+        self.verify_profile.as_deref()
+    }
+
+    /// Commands that must succeed before publishing, run from the package root whether or not
+    /// `verify` is set
+    pub fn verify_command(&self) -> &[Command] {
+        self.verify_command.as_deref().unwrap_or(&[])
+    }
+
+    pub fn verify_args(&self) -> &[String] {
+        self.verify_args.as_ref().map(|v| v.as_ref()).unwrap_or(&[])
+    }
+
+    pub fn verify_toolchain(&self) -> Option<&str> {
+        self.verify_toolchain.as_deref()
+    }
+
+    /// Publish and verify with `--locked`, so the build uses exactly what `Cargo.lock` pins
+    pub fn publish_locked(&self) -> bool {
+        self.publish_locked.unwrap_or(false)
+    }
+
+    pub fn verify_options(&self) -> cargo::VerifyOptions<'_> {
+        cargo::VerifyOptions {
+            toolchain: self.verify_toolchain(),
+            args: self.verify_args(),
+            target_dir: None,
+            locked: self.publish_locked(),
+        }
+    }
+
+    /// Where verification and packaging build, so they leave the workspace's `target/` alone
+    ///
+    /// Defaults to a directory per workspace in the user's cache dir, unless the user already
+    /// picked one with `CARGO_TARGET_DIR` or `build.target-dir`, which cargo then uses as-is.
+    pub fn release_target_dir(&self, workspace_root: &Path) -> Option<PathBuf> {
+        use sha2::Digest as _;
+
+        if let Some(dir) = self.release_target_dir.as_deref() {
+            return Some(workspace_root.join(dir));
+        }
+        let cargo_target_dir = std::env::var_os("CARGO_TARGET_DIR").is_some()
+            || crate::ops::credentials::cargo_config(&["build", "target-dir"])
+                .ok()
+                .flatten()
+                .is_some();
+        if cargo_target_dir {
+            return None;
+        }
+        let hash = sha2::Sha256::digest(workspace_root.display().to_string().as_bytes())
+            .iter()
+            .take(8)
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        dirs_next::cache_dir().map(|d| d.join("cargo-release").join("target").join(hash))
+    }
+
+    pub fn publish_order(&self) -> &[Vec<String>] {
+        self.publish_order
+            .as_ref()
+            .map(|v| v.as_ref())
+            .unwrap_or(&[])
+    }
+
+    /// Exclusive upper bound for released versions, e.g. `"2.0.0"` on a `1.x` maintenance branch
+    pub fn max_version(&self) -> Option<&str> {
+        self.max_version.as_deref()
+    }
+
+    pub fn zero_ver_breaking(&self) -> ZeroVerBreaking {
+        self.zero_ver_breaking.unwrap_or_default()
+    }
+
+    pub fn prerelease_tags(&self) -> PrereleaseTags {
+        self.prerelease_tags.unwrap_or_default()
+    }
+
+    pub fn update_lockfile(&self) -> UpdateLockfile {
+        self.update_lockfile.unwrap_or_default()
+    }
+
+    /// Actions to interactively confirm before performing
+    pub fn confirm(&self) -> &[ConfirmAction] {
+        self.confirm
+            .as_ref()
+            .map(|v| v.as_ref())
+            .unwrap_or(&[ConfirmAction::Release])
+    }
+
+    pub fn await_targets(&self) -> &[AwaitTarget] {
+        self.await_targets
+            .as_ref()
+            .map(|v| v.as_ref())
+            .unwrap_or(&[])
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Replace {
+    pub file: PathBuf,
+    pub search: String,
+    pub replace: String,
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+    pub exactly: Option<usize>,
+    #[serde(default)]
+    pub prerelease: bool,
+    /// Replace even in files that look binary
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// One command or a list of commands to run in order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Hooks {
+    Command(Command),
+    Commands(Vec<Hook>),
+}
+
+impl Hooks {
+    pub fn hooks(&self) -> Vec<Hook> {
+        match self {
+            Hooks::Command(command) => vec![Hook {
+                command: command.clone(),
+                cwd: None,
+                shell: None,
+                on_failure: None,
+                // Single commands have always run, checking `DRY_RUN` themselves
+                run_in_dry_run: Some(true),
+            }],
+            Hooks::Commands(hooks) => hooks.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+pub struct Hook {
+    pub command: Command,
+    pub cwd: Option<HookCwd>,
+    pub shell: Option<HookShell>,
+    /// Overrides `hook-on-failure`
+    pub on_failure: Option<HookOnFailure>,
+    pub run_in_dry_run: Option<bool>,
+}
+
+impl Hook {
+    /// Whether the hook is safe to run when nothing else is being changed
+    pub fn run_in_dry_run(&self) -> bool {
+        self.run_in_dry_run.unwrap_or(false)
+    }
+
+    pub fn cwd(&self, ws_root: &std::path::Path, pkg_root: &std::path::Path) -> std::path::PathBuf {
+        match self.cwd.as_ref() {
+            Some(HookCwd::WorkspaceRoot) => ws_root.to_owned(),
+            Some(HookCwd::PackageRoot) | None => pkg_root.to_owned(),
+            Some(HookCwd::Path(path)) => pkg_root.join(path),
+        }
+    }
+
+    /// The arguments to run, wrapped in `shell` if set
+    pub fn argv(&self) -> Vec<&str> {
+        let args = self.command.args();
+        match self.shell.unwrap_or_default() {
+            HookShell::None => args,
+            HookShell::Bash => {
+                let mut argv = vec!["bash", "-c"];
+                argv.extend(args);
+                argv
+            }
+            HookShell::Pwsh => {
+                let mut argv = vec!["pwsh", "-NoProfile", "-NonInteractive", "-Command"];
+                argv.extend(args);
+                argv
+            }
+        }
+    }
+}
+
+/// Where a hook runs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum HookCwd {
+    WorkspaceRoot,
+    PackageRoot,
+    /// Relative to the package root
+    Path(std::path::PathBuf),
+}
+
+impl TryFrom<String> for HookCwd {
+    type Error = std::convert::Infallible;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.as_str() {
+            "workspace-root" => Ok(HookCwd::WorkspaceRoot),
+            "package-root" => Ok(HookCwd::PackageRoot),
+            _ => Ok(HookCwd::Path(s.into())),
+        }
+    }
+}
+
+impl From<HookCwd> for String {
+    fn from(cwd: HookCwd) -> Self {
+        match cwd {
+            HookCwd::WorkspaceRoot => "workspace-root".to_owned(),
+            HookCwd::PackageRoot => "package-root".to_owned(),
+            HookCwd::Path(path) => path.display().to_string(),
+        }
+    }
+}
+
+/// The shell to run a hook's command in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum HookShell {
+    /// Run the command directly
+    #[default]
+    None,
+    /// `bash -c <command> [args...]`
+    Bash,
+    /// `pwsh -Command <command> [args...]`
+    Pwsh,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Command {
+    Line(String),
+    Args(Vec<String>),
+}
+
+impl Command {
+    pub fn args(&self) -> Vec<&str> {
+        match self {
+            Command::Line(ref s) => vec![s.as_str()],
+            Command::Args(ref a) => a.iter().map(|s| s.as_str()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+#[value(rename_all = "kebab-case")]
+#[derive(Default)]
+pub enum DependentVersion {
+    /// Always upgrade dependents
+    #[default] // This is the safest option as its hard to test `Fix`
+    Upgrade,
+    /// Upgrade when the old version requirement no longer applies
+    Fix,
+    /// Always pin dependents to the exact version
+    Pin,
+}
+
+/// How dependents' requirements are updated when releasing a pre-release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DependentPrerelease {
+    /// Require exactly the pre-release, `=1.2.0-rc.1`
+    Pin,
+    /// Require the pre-release or anything compatible after it, `1.2.0-rc.1`
+    Caret,
+    /// Leave requirements as they are until the final release
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+#[value(rename_all = "kebab-case")]
+#[derive(Default)]
+pub enum MetadataPolicy {
+    /// Apply when set, clear when not
+    #[default]
+    Optional,
+    /// Error if not set
+    Required,
+    /// Never apply the set metadata
+    Ignore,
+    /// Keep the prior metadata if not set
+    Persistent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdateLockfile {
+    /// Re-resolve `Cargo.lock` for the workspace
+    #[default]
+    Workspace,
+    /// Only update the entries of released packages
+    Package,
+    /// Leave `Cargo.lock` alone
+    None,
+}
+
+/// What to do when `pre-release-hook` fails or times out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(try_from = "String", into = "String")]
+pub enum HookOnFailure {
+    /// Stop the release
+    #[default]
+    Abort,
+    /// Report the failure and continue
+    Warn,
+    /// Run the hook up to N more times before stopping the release
+    Retry(u32),
+}
+
+impl std::str::FromStr for HookOnFailure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "abort" => Ok(HookOnFailure::Abort),
+            "warn" => Ok(HookOnFailure::Warn),
+            _ => {
+                let retries = s
+                    .strip_prefix("retry:")
+                    .and_then(|n| n.parse().ok())
+                    .ok_or_else(|| {
+                        format!("invalid `hook-on-failure` {s:?}, expected `abort`, `warn`, or `retry:N`")
+                    })?;
+                Ok(HookOnFailure::Retry(retries))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for HookOnFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HookOnFailure::Abort => "abort".fmt(f),
+            HookOnFailure::Warn => "warn".fmt(f),
+            HookOnFailure::Retry(retries) => write!(f, "retry:{retries}"),
+        }
+    }
+}
+
+impl TryFrom<String> for HookOnFailure {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<HookOnFailure> for String {
+    fn from(policy: HookOnFailure) -> Self {
+        policy.to_string()
+    }
+}
+
+/// Conditions that must all be met before publishing
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Approval {
+    /// Workspace file listing `<crate> <version>` for each approved release
+    pub file: Option<String>,
+    /// Environment variable an approval system sets
+    pub env: Option<String>,
+    /// Have a different OS user authenticate
+    pub other_user: Option<bool>,
+    /// Endpoint the release plan is POSTed to for an allow/deny decision
+    pub webhook: Option<String>,
+    /// Environment variable holding a bearer token for `webhook`
+    pub webhook_token_env: Option<String>,
+    /// Give up waiting on `webhook` after this long, which counts as a denial
+    pub webhook_timeout: Option<Interval>,
+}
+
+/// Where to announce releases, under `[notify]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Notify {
+    pub email: Option<EmailNotify>,
+    pub matrix: Option<MatrixNotify>,
+}
+
+/// Announce releases by email, under `[notify.email]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+#[serde(rename_all = "kebab-case")]
+pub struct EmailNotify {
+    /// SMTP server host
+    pub server: Option<String>,
+    pub port: Option<u16>,
+    pub tls: Option<SmtpTls>,
+    /// Environment variable holding the SMTP username
+    pub username_env: Option<String>,
+    /// Environment variable holding the SMTP password
+    pub password_env: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<Vec<String>>,
+    pub subject: Option<String>,
+    pub body: Option<String>,
+}
+
+impl EmailNotify {
+    pub fn to(&self) -> &[String] {
+        self.to.as_deref().unwrap_or(&[])
+    }
+
+    pub fn subject(&self) -> &str {
+        self.subject.as_deref().unwrap_or("Released {{releases}}")
+    }
+
+    pub fn body(&self) -> &str {
+        self.body.as_deref().unwrap_or("{{changelog}}")
+    }
+}
+
+/// An additional announcement, under `[[announcements]]`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+pub struct Announcement {
+    /// Template file, relative to the workspace root
+    pub template_path: String,
+    /// Where `cargo release announcement --execute` writes it, relative to the workspace root
+    pub path: String,
+}
+
+/// Announce releases in a Matrix room, under `[notify.matrix]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+#[serde(rename_all = "kebab-case")]
+pub struct MatrixNotify {
+    /// Base URL, like `https://matrix.example.org`
+    pub homeserver: Option<String>,
+    /// Like `!abc:example.org`
+    pub room_id: Option<String>,
+    /// Environment variable holding the access token
+    pub token_env: Option<String>,
+    pub body: Option<String>,
+}
+
+impl MatrixNotify {
+    pub fn token_env(&self) -> &str {
+        self.token_env.as_deref().unwrap_or("MATRIX_ACCESS_TOKEN")
+    }
+
+    pub fn body(&self) -> &str {
+        self.body
+            .as_deref()
+            .unwrap_or("**Released {{releases}}**\n\n{{changelog}}")
+    }
+}
+
+/// How to secure the connection to the SMTP server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SmtpTls {
+    /// Upgrade a plain connection, usually on port 587
+    #[default]
+    Starttls,
+    /// Connect over TLS, usually on port 465
+    Tls,
+    /// Only for local relays
+    None,
+}
+
+/// Severity for each optional `Cargo.toml` field that is missing
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ManifestLints {
+    pub readme: Option<LintLevel>,
+    pub repository: Option<LintLevel>,
+    pub homepage: Option<LintLevel>,
+    pub documentation: Option<LintLevel>,
+    pub keywords: Option<LintLevel>,
+    pub categories: Option<LintLevel>,
+}
+
+impl ManifestLints {
+    pub fn update(&mut self, source: &ManifestLints) {
+        if let Some(readme) = source.readme {
+            self.readme = Some(readme);
+        }
+        if let Some(repository) = source.repository {
+            self.repository = Some(repository);
+        }
+        if let Some(homepage) = source.homepage {
+            self.homepage = Some(homepage);
+        }
+        if let Some(documentation) = source.documentation {
+            self.documentation = Some(documentation);
+        }
+        if let Some(keywords) = source.keywords {
+            self.keywords = Some(keywords);
+        }
+        if let Some(categories) = source.categories {
+            self.categories = Some(categories);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintLevel {
+    #[default]
+    Allow,
+    Warn,
+    Error,
+}
+
+/// How to talk to an index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndexHttpVersion {
+    /// HTTP/2 for crates.io, falling back to HTTP/1.1 if that fails
+    #[default]
+    Auto,
+    Http1,
+    /// HTTP/2 without negotiating it first
+    Http2,
+}
+
+/// A length of time, written like `30s`, `500ms`, `5m`, or `1h`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Interval(pub std::time::Duration);
+
+impl std::str::FromStr for Interval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (value, unit) = s.split_at(split);
+        let value: u64 = value.parse().map_err(|_| {
+            format!("invalid interval {s:?}, expected a number and a unit like `30s`")
+        })?;
+        let duration = match unit.trim() {
+            "ms" => std::time::Duration::from_millis(value),
+            "" | "s" => std::time::Duration::from_secs(value),
+            "m" => std::time::Duration::from_secs(value * 60),
+            "h" => std::time::Duration::from_secs(value * 60 * 60),
+            _ => {
+                return Err(format!(
+                    "invalid interval {s:?}, expected a unit of `ms`, `s`, `m`, or `h`"
+                ))
+            }
+        };
+        Ok(Interval(duration))
+    }
+}
+
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let millis = self.0.as_millis();
+        if millis % 1000 != 0 {
+            write!(f, "{millis}ms")
+        } else {
+            write!(f, "{}s", millis / 1000)
+        }
+    }
+}
+
+impl TryFrom<String> for Interval {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Interval> for String {
+    fn from(interval: Interval) -> Self {
+        interval.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommitStrategy {
+    /// One release commit
+    #[default]
+    Single,
+    /// Commit the version bump separately from replacements, changelogs, and hooks
+    Split,
+}
+
+/// Settings for a single registry, under `[registries.<name>]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+#[serde(rename_all = "kebab-case")]
+pub struct RegistryConfig {
+    /// Index URL, for looking up what is already published; `sparse+` for sparse indexes
+    pub index: Option<String>,
+    /// PEM file with extra root certificates for the registry
+    pub ca_info: Option<String>,
+    pub rate_limit: Option<RateLimit>,
+    /// Default for `verify` when publishing to this registry
+    pub verify: Option<bool>,
+    /// Environment variable holding the registry's token
+    pub token_env: Option<String>,
+    /// Endpoint to check the registry is healthy before releasing
+    pub status_url: Option<String>,
+    /// Root of the registry's web API, like `https://crates.io`
+    pub api: Option<String>,
+    /// Cargo credential provider for an index that requires authentication, with its arguments
+    pub credential_provider: Option<Vec<String>>,
+}
+
+impl RegistryConfig {
+    /// Name for the default registry
+    pub const CRATES_IO: &'static str = "crates-io";
+}
+
+/// Settings for a single git remote, under `[remotes.<name>]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+#[serde(rename_all = "kebab-case")]
+pub struct RemoteConfig {
+    /// Overrides `push-options` when pushing to this remote
+    pub push_options: Option<Vec<String>>,
+    pub on_failure: Option<PushFailure>,
+}
+
+/// How to sign the release manifest
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ManifestSignature {
+    /// ASCII-armored detached signature at `<path>.asc`
+    Gpg,
+    /// `cosign sign-blob` bundle at `<path>.sigstore.json`
+    Sigstore,
+}
+
+/// What gets pushed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PushStrategy {
+    /// The branch and each tag, by name, in one atomic push
+    #[default]
+    Refspec,
+    /// The branch, letting git bring along the annotated tags it reaches
+    FollowTags,
+    /// Only the tags, for when the release commit lands through a pull request
+    TagsOnly,
+}
+
+/// How to tell whether a package changed since its last release
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeDetection {
+    /// Files in the package touched since the prior tag
+    #[default]
+    Git,
+    /// The `.crate` that would be published compared to the registry's checksum
+    Content,
+}
+
+/// What to do when pushing to a remote fails
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PushFailure {
+    /// Stop the release
+    #[default]
+    Abort,
+    /// Report the failure and keep going, like for a mirror
+    Warn,
+}
+
+/// One or more git remotes, from `push-remote`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PushRemote {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl PushRemote {
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        match self {
+            PushRemote::One(name) => std::slice::from_ref(name).iter(),
+            PushRemote::Many(names) => names.iter(),
+        }
+        .map(|name| name.as_str())
+    }
+}
+
+/// How many crates can be published at once, and how quickly that allowance comes back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "kebab-case")]
+pub struct RateLimit {
+    /// Burst of crates that were never published before
+    pub new: usize,
+    /// Burst of new versions of existing crates
+    pub existing: usize,
+    /// Seconds for one more new crate to be allowed
+    #[serde(default = "RateLimit::default_new_interval")]
+    pub new_interval: u64,
+    /// Seconds for one more new version to be allowed
+    #[serde(default = "RateLimit::default_existing_interval")]
+    pub existing_interval: u64,
+}
+
+impl RateLimit {
+    // "The rate limit for creating new crates is 1 crate every 10 minutes, with a burst of 5 crates."
+    // "The rate limit for new versions of existing crates is 1 per minute, with a burst of 30 crates"
+    pub const CRATES_IO: Self = Self {
+        new: 5,
+        existing: 30,
+        new_interval: 10 * 60,
+        existing_interval: 60,
+    };
+
+    fn default_new_interval() -> u64 {
+        Self::CRATES_IO.new_interval
+    }
+
+    fn default_existing_interval() -> u64 {
+        Self::CRATES_IO.existing_interval
     }
 
-    pub fn metadata(&self) -> MetadataPolicy {
-        self.metadata.unwrap_or_default()
+    /// The burst and refill interval for `new` or existing crates
+    pub fn bucket(&self, new: bool) -> (usize, u64) {
+        if new {
+            (self.new, self.new_interval)
+        } else {
+            (self.existing, self.existing_interval)
+        }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct Replace {
-    pub file: PathBuf,
-    pub search: String,
-    pub replace: String,
-    pub min: Option<usize>,
-    pub max: Option<usize>,
-    pub exactly: Option<usize>,
-    #[serde(default)]
-    pub prerelease: bool,
+/// What `promote-stable` does with the tags of the promoted pre-releases
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PrereleaseTags {
+    #[default]
+    Keep,
+    /// Delete the tags, locally and on `push-remote`
+    Delete,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum Command {
-    Line(String),
-    Args(Vec<String>),
+/// What a `breaking` bump means below 1.0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ZeroVerBreaking {
+    /// `0.x.y` to `0.(x+1).0`, like cargo's semver compatibility rules
+    #[default]
+    Minor,
+    /// `0.x.y` to `1.0.0`
+    Major,
 }
 
-impl Command {
-    pub fn args(&self) -> Vec<&str> {
-        match self {
-            Command::Line(ref s) => vec![s.as_str()],
-            Command::Args(ref a) => a.iter().map(|s| s.as_str()).collect(),
-        }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+#[value(rename_all = "kebab-case")]
+pub enum AwaitTarget {
+    /// The crates.io API serves the new version
+    Index,
+    /// docs.rs finished building the new version
+    Docs,
+}
+
+impl std::fmt::Display for AwaitTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use clap::ValueEnum;
+
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
 #[serde(rename_all = "kebab-case")]
 #[value(rename_all = "kebab-case")]
-#[derive(Default)]
-pub enum DependentVersion {
-    /// Always upgrade dependents
-    #[default] // This is the safest option as its hard to test `Fix`
-    Upgrade,
-    /// Upgrade when the old version requirement no longer applies
-    Fix,
+pub enum ConfirmAction {
+    /// Before making any changes
+    Release,
+    /// Before running `cargo publish`
+    Publish,
+    /// Before running `git push`
+    Push,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 #[value(rename_all = "kebab-case")]
-#[derive(Default)]
-pub enum MetadataPolicy {
-    /// Apply when set, clear when not
-    #[default]
-    Optional,
-    /// Error if not set
-    Required,
-    /// Never apply the set metadata
-    Ignore,
-    /// Keep the prior metadata if not set
-    Persistent,
+pub enum SkipStep {
+    /// Same as `publish = false`
+    Publish,
+    /// Same as `verify = false`
+    Verify,
+    /// Same as `tag = false`
+    Tag,
+    /// Same as `push = false`
+    Push,
+}
+
+impl std::fmt::Display for SkipStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use clap::ValueEnum;
+
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -378,6 +1971,26 @@ impl SharedVersion {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ReleaseField {
+    Enabled(bool),
+    Mode(ReleaseMode),
+}
+
+impl From<bool> for ReleaseField {
+    fn from(enabled: bool) -> Self {
+        ReleaseField::Enabled(enabled)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReleaseMode {
+    /// Participate in the version bump, commit, and tag without publishing
+    TagOnly,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 struct CargoManifest {
@@ -460,11 +2073,26 @@ pub fn load_workspace_config(
         is_workspace: 1 < ws_meta.workspace_members.len(),
         ..Default::default()
     };
+    for layer in workspace_config_layers(args, ws_meta)? {
+        release_config.update(&layer.config);
+    }
+    // `git` is run from too many places to pass this around
+    crate::ops::git::set_options(release_config.git_path(), release_config.git_env());
+    crate::ops::git::git_version()?;
+    Ok(release_config)
+}
+
+/// The sources merged by [`load_workspace_config`], in increasing precedence
+pub fn workspace_config_layers(
+    args: &ConfigArgs,
+    ws_meta: &cargo_metadata::Metadata,
+) -> CargoResult<Vec<ConfigLayer>> {
+    let mut layers = Vec::new();
 
     if !args.isolated {
         let is_workspace = 1 < ws_meta.workspace_members.len();
-        let cfg = if is_workspace {
-            resolve_workspace_config(ws_meta.workspace_root.as_std_path())?
+        if is_workspace {
+            layers.extend(workspace_file_layers(ws_meta.workspace_root.as_std_path())?);
         } else {
             // Outside of workspaces, go ahead and treat package config as workspace config so
             // users don't have to specially configure workspace-specific fields
@@ -473,22 +2101,24 @@ pub fn load_workspace_config(
                 .iter()
                 .find(|p| ws_meta.workspace_members.iter().any(|m| *m == p.id))
                 .unwrap();
-            resolve_config(
+            layers.extend(package_file_layers(
                 ws_meta.workspace_root.as_std_path(),
                 pkg.manifest_path.as_std_path(),
-            )?
-        };
-        release_config.update(&cfg);
+            )?);
+        }
     }
 
     if let Some(custom_config_path) = args.custom_config.as_ref() {
         // when calling with -c option
         let cfg = resolve_custom_config(custom_config_path.as_ref())?.unwrap_or_default();
-        release_config.update(&cfg);
+        layers.push(ConfigLayer::new(
+            format!("--config {}", custom_config_path.display()),
+            cfg,
+        ));
     }
 
-    release_config.update(&args.to_config());
-    Ok(release_config)
+    layers.push(ConfigLayer::new("command-line", args.to_config()));
+    Ok(layers)
 }
 
 pub fn load_package_config(
@@ -496,31 +2126,68 @@ pub fn load_package_config(
     ws_meta: &cargo_metadata::Metadata,
     pkg: &cargo_metadata::Package,
 ) -> CargoResult<Config> {
-    let manifest_path = pkg.manifest_path.as_std_path();
-
     let is_workspace = 1 < ws_meta.workspace_members.len();
     let mut release_config = Config {
         is_workspace,
         ..Default::default()
     };
+    for layer in package_config_layers(args, ws_meta, pkg)? {
+        release_config.update(&layer.config);
+    }
+    Ok(release_config)
+}
+
+/// The sources merged by [`load_package_config`], in increasing precedence
+pub fn package_config_layers(
+    args: &ConfigArgs,
+    ws_meta: &cargo_metadata::Metadata,
+    pkg: &cargo_metadata::Package,
+) -> CargoResult<Vec<ConfigLayer>> {
+    let manifest_path = pkg.manifest_path.as_std_path();
+    let mut layers = Vec::new();
 
     if !args.isolated {
-        let cfg = resolve_config(ws_meta.workspace_root.as_std_path(), manifest_path)?;
-        release_config.update(&cfg);
+        layers.extend(package_file_layers(
+            ws_meta.workspace_root.as_std_path(),
+            manifest_path,
+        )?);
     }
 
     if let Some(custom_config_path) = args.custom_config.as_ref() {
         // when calling with -c option
         let cfg = resolve_custom_config(Path::new(custom_config_path))?.unwrap_or_default();
-        release_config.update(&cfg);
+        layers.push(ConfigLayer::new(
+            format!("--config {}", custom_config_path.display()),
+            cfg,
+        ));
     }
 
-    release_config.update(&args.to_config());
+    layers.push(ConfigLayer::new("command-line", args.to_config()));
 
     let overrides = resolve_overrides(ws_meta.workspace_root.as_std_path(), manifest_path)?;
-    release_config.update(&overrides);
+    layers.push(ConfigLayer::new(
+        format!("override (inferred from {})", manifest_path.display()),
+        overrides,
+    ));
 
-    Ok(release_config)
+    Ok(layers)
+}
+
+/// A single source of configuration
+#[derive(Debug, Clone)]
+pub struct ConfigLayer {
+    /// Where `config` came from, for reporting to the user
+    pub source: String,
+    pub config: Config,
+}
+
+impl ConfigLayer {
+    pub fn new(source: impl Into<String>, config: Config) -> Self {
+        Self {
+            source: source.into(),
+            config,
+        }
+    }
 }
 
 #[derive(Clone, Default, Debug, clap::Args)]
@@ -547,6 +2214,10 @@ pub struct ConfigArgs {
     #[arg(long, value_delimiter = ',', value_name = "GLOB[,...]")]
     pub allow_branch: Option<Vec<String>>,
 
+    /// Steps to skip
+    #[arg(long, value_delimiter = ',', value_name = "STEP[,...]", value_enum)]
+    pub skip: Vec<SkipStep>,
+
     #[command(flatten)]
     pub commit: CommitArgs,
 
@@ -561,6 +2232,20 @@ pub struct ConfigArgs {
 }
 
 impl ConfigArgs {
+    /// Retarget publishing and pushing for a rehearsal, skipping either without a staging target
+    pub fn rehearsal(&self, registry: Option<&str>, remote: Option<&str>) -> Self {
+        let mut args = self.clone();
+        match registry {
+            Some(registry) => args.publish.registry = Some(registry.to_owned()),
+            None => args.skip.push(SkipStep::Publish),
+        }
+        match remote {
+            Some(remote) => args.push.push_remote = Some(remote.to_owned()),
+            None => args.skip.push(SkipStep::Push),
+        }
+        args
+    }
+
     pub fn to_config(&self) -> Config {
         let mut config = Config {
             allow_branch: self.allow_branch.clone(),
@@ -573,6 +2258,15 @@ impl ConfigArgs {
         config.update(&self.publish.to_config());
         config.update(&self.tag.to_config());
         config.update(&self.push.to_config());
+        // Applied last so skipping wins over any other flag
+        for skip in &self.skip {
+            match skip {
+                SkipStep::Publish => config.publish = Some(false),
+                SkipStep::Verify => config.verify = Some(false),
+                SkipStep::Tag => config.tag = Some(false),
+                SkipStep::Push => config.push = Some(false),
+            }
+        }
         config
     }
 
@@ -630,6 +2324,15 @@ pub struct PublishArgs {
     /// Build for the target triple
     #[arg(long, value_name = "TRIPLE")]
     target: Option<String>,
+
+    /// Poll after publishing until ready
+    #[arg(
+        long = "await",
+        value_delimiter = ',',
+        value_name = "TARGET[,...]",
+        value_enum
+    )]
+    await_targets: Vec<AwaitTarget>,
 }
 
 impl PublishArgs {
@@ -641,6 +2344,7 @@ impl PublishArgs {
             enable_features: (!self.features.is_empty()).then(|| self.features.clone()),
             enable_all_features: self.all_features.then_some(true),
             target: self.target.clone(),
+            await_targets: (!self.await_targets.is_empty()).then(|| self.await_targets.clone()),
             ..Default::default()
         }
     }
@@ -700,17 +2404,128 @@ impl PushArgs {
     pub fn to_config(&self) -> Config {
         Config {
             push: resolve_bool_arg(self.push, self.no_push),
-            push_remote: self.push_remote.clone(),
+            push_remote: self.push_remote.clone().map(PushRemote::One),
             ..Default::default()
         }
     }
 }
 
+/// Where release config lives within a `release.toml`
+pub const FILE_RELEASE_TABLES: &[&[&str]] = &[&[]];
+/// Where release config lives within a `Cargo.toml`
+pub const MANIFEST_RELEASE_TABLES: &[&[&str]] = &[
+    &["package", "metadata", "release"],
+    &["workspace", "metadata", "release"],
+];
+
+/// A config field that was renamed
+struct DeprecatedField {
+    old: &'static str,
+    new: &'static str,
+    /// The new field is a `bool` with the opposite meaning
+    invert: bool,
+}
+
+const DEPRECATED_FIELDS: &[DeprecatedField] = &[
+    DeprecatedField {
+        old: "disable-push",
+        new: "push",
+        invert: true,
+    },
+    DeprecatedField {
+        old: "disable-publish",
+        new: "publish",
+        invert: true,
+    },
+    DeprecatedField {
+        old: "disable-tag",
+        new: "tag",
+        invert: true,
+    },
+];
+
+/// Parse `content`, accepting deprecated field names in the config `tables`
+///
+/// Returns the parsed value along with a description of each deprecated field.
+fn parse_config<T: serde::de::DeserializeOwned>(
+    content: &str,
+    tables: &[&[&str]],
+) -> CargoResult<(T, Vec<String>)> {
+    let mut doc: toml_edit::DocumentMut = content.parse()?;
+    let deprecations = migrate_config(&mut doc, tables);
+    let value = toml_edit::de::from_document(doc)?;
+    Ok((value, deprecations))
+}
+
+/// Rewrite deprecated fields in the config `tables` of `doc` to their current names
+///
+/// Returns a description of each field that was rewritten.  When both the deprecated and current
+/// name are present, the current one wins.
+pub fn migrate_config(doc: &mut toml_edit::DocumentMut, tables: &[&[&str]]) -> Vec<String> {
+    let mut deprecations = Vec::new();
+    for keys in tables {
+        let mut item = Some(doc.as_item_mut());
+        for key in keys.iter() {
+            item = item.and_then(|i| i.get_mut(key));
+        }
+        let table = if let Some(table) = item.and_then(|i| i.as_table_like_mut()) {
+            table
+        } else {
+            continue;
+        };
+
+        for field in DEPRECATED_FIELDS {
+            let mut value = if let Some(value) = table.remove(field.old) {
+                value
+            } else {
+                continue;
+            };
+            if field.invert {
+                if let Some(b) = value.as_bool() {
+                    value = toml_edit::value(!b);
+                }
+            }
+            if table.contains_key(field.new) {
+                deprecations.push(format!(
+                    "`{}` is deprecated and ignored in favor of `{}`",
+                    field.old, field.new
+                ));
+            } else {
+                deprecations.push(format!(
+                    "`{}` is deprecated, use `{} = {}` instead",
+                    field.old,
+                    field.new,
+                    value.to_string().trim()
+                ));
+                table.insert(field.new, value);
+            }
+        }
+    }
+    deprecations
+}
+
+fn warn_deprecations(source: &str, deprecations: &[String]) {
+    static WARNED: once_cell::sync::Lazy<std::sync::Mutex<std::collections::HashSet<String>>> =
+        once_cell::sync::Lazy::new(Default::default);
+
+    // Config files are re-read for every package, only warn once
+    let mut warned = WARNED.lock().unwrap();
+    for deprecation in deprecations {
+        let message = format!("{source}: {deprecation}");
+        if warned.insert(message.clone()) {
+            let _ = crate::ops::shell::warn(format!(
+                "{message} (run `cargo release fix-config` to update)"
+            ));
+        }
+    }
+}
+
 fn get_pkg_config_from_manifest(manifest_path: &Path) -> CargoResult<Option<Config>> {
     if manifest_path.exists() {
         let m = std::fs::read_to_string(manifest_path)?;
-        let c: CargoManifest = toml::from_str(&m)
+        let (c, deprecations): (CargoManifest, _) = parse_config(&m, MANIFEST_RELEASE_TABLES)
             .with_context(|| format!("Failed to parse `{}`", manifest_path.display()))?;
+        warn_deprecations(&manifest_path.display().to_string(), &deprecations);
 
         Ok(c.package.and_then(|p| p.into_config()))
     } else {
@@ -721,8 +2536,9 @@ fn get_pkg_config_from_manifest(manifest_path: &Path) -> CargoResult<Option<Conf
 fn get_ws_config_from_manifest(manifest_path: &Path) -> CargoResult<Option<Config>> {
     if manifest_path.exists() {
         let m = std::fs::read_to_string(manifest_path)?;
-        let c: CargoManifest = toml::from_str(&m)
+        let (c, deprecations): (CargoManifest, _) = parse_config(&m, MANIFEST_RELEASE_TABLES)
             .with_context(|| format!("Failed to parse `{}`", manifest_path.display()))?;
+        warn_deprecations(&manifest_path.display().to_string(), &deprecations);
 
         Ok(c.workspace.and_then(|p| p.into_config()))
     } else {
@@ -733,14 +2549,209 @@ fn get_ws_config_from_manifest(manifest_path: &Path) -> CargoResult<Option<Confi
 fn get_config_from_file(file_path: &Path) -> CargoResult<Option<Config>> {
     if file_path.exists() {
         let c = std::fs::read_to_string(file_path)?;
-        let config = toml::from_str(&c)
+        let (config, deprecations) = parse_config(&c, FILE_RELEASE_TABLES)
             .with_context(|| format!("Failed to parse `{}`", file_path.display()))?;
+        warn_deprecations(&file_path.display().to_string(), &deprecations);
+        let source = ExtendsSource::Local(file_path.to_owned());
+        let config = resolve_extends(config, &source, 0)
+            .with_context(|| format!("Failed to load base config of `{}`", file_path.display()))?;
         Ok(Some(config))
     } else {
         Ok(None)
     }
 }
 
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// Where a config was loaded from, for resolving a relative `extends` inside it
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ExtendsSource {
+    /// A file on disk
+    Local(std::path::PathBuf),
+    /// A file fetched over `https://`
+    Url(reqwest::Url),
+    /// A `/`-separated `path` within `repo`
+    Git { repo: String, path: String },
+}
+
+impl ExtendsSource {
+    /// Locate `extends`, as written in the config loaded from `self`
+    fn join(&self, extends: &str) -> CargoResult<Self> {
+        if extends.starts_with("https://") {
+            return Ok(Self::Url(reqwest::Url::parse(extends)?));
+        }
+        if let Some(repo) = extends.strip_prefix("git+") {
+            let (repo, path) = repo
+                .split_once('#')
+                .ok_or_else(|| anyhow::format_err!("`{extends}` is missing `#<path>`"))?;
+            return Ok(Self::git(repo, path));
+        }
+        let source = match self {
+            Self::Local(path) => {
+                let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+                Self::Local(base_dir.join(extends))
+            }
+            Self::Url(url) => Self::Url(url.join(extends)?),
+            Self::Git { repo, path } => {
+                let base_dir = path.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+                Self::git(repo, &format!("{base_dir}/{extends}"))
+            }
+        };
+        Ok(source)
+    }
+
+    /// `path` within `repo`, with `.` and `..` resolved so it can't escape the checkout
+    fn git(repo: &str, path: &str) -> Self {
+        let mut components = Vec::new();
+        for component in path.split('/') {
+            match component {
+                "" | "." => {}
+                ".." => {
+                    components.pop();
+                }
+                component => components.push(component),
+            }
+        }
+        Self::Git {
+            repo: repo.to_owned(),
+            path: components.join("/"),
+        }
+    }
+}
+
+/// Layer `config` on top of the config it `extends`, recursively
+///
+/// `extends` may be
+/// - `https://...`, fetched as-is
+/// - `git+<repo>#<path>`, `<path>` read from a shallow clone of the default branch
+/// - a path, relative to `source`, whether that is a local file, a URL, or a file in a repo
+fn resolve_extends(
+    mut config: Config,
+    source: &ExtendsSource,
+    depth: usize,
+) -> CargoResult<Config> {
+    let extends = if let Some(extends) = config.extends.take() {
+        extends
+    } else {
+        return Ok(config);
+    };
+    let checksum = config.extends_checksum.take();
+    if MAX_EXTENDS_DEPTH <= depth {
+        anyhow::bail!(
+            "`extends` nested more than {MAX_EXTENDS_DEPTH} levels deep, is there a cycle?"
+        );
+    }
+
+    let next_source = source.join(&extends)?;
+    let content = match &next_source {
+        ExtendsSource::Local(path) => {
+            std::fs::read(path).with_context(|| format!("Failed to read `{}`", path.display()))?
+        }
+        ExtendsSource::Url(url) => {
+            if checksum.is_none() {
+                anyhow::bail!("`extends = \"{extends}\"` requires `extends-checksum`");
+            }
+            fetch_url(url.as_str())?
+        }
+        ExtendsSource::Git { repo, path } => {
+            if checksum.is_none() {
+                anyhow::bail!("`extends = \"{extends}\"` requires `extends-checksum`");
+            }
+            // The workspace's `git-path` and `git-env` aren't known yet, only this file's
+            let options = crate::ops::git::GitOptions::new(config.git_path(), config.git_env());
+            fetch_git(&options, repo, path)?
+        }
+    };
+
+    if let Some(checksum) = checksum.as_deref() {
+        verify_checksum(&extends, &content, checksum)?;
+    }
+
+    let content = String::from_utf8(content)
+        .map_err(|_| anyhow::format_err!("`{extends}` is not valid UTF-8"))?;
+    let (base, deprecations) = parse_config(&content, FILE_RELEASE_TABLES)
+        .with_context(|| format!("Failed to parse `{extends}`"))?;
+    warn_deprecations(&extends, &deprecations);
+    let mut base = resolve_extends(base, &next_source, depth + 1)?;
+    base.update(&config);
+    // Not covered by `update` as they are applied as layers of their own
+    base.package = merge_sections(base.package.take(), config.package);
+    base.paths = merge_sections(base.paths.take(), config.paths);
+    Ok(base)
+}
+
+/// Layer the `[package.<name>]` or `[paths."<glob>"]` sections of `source` over those of `base`
+fn merge_sections(
+    base: Option<std::collections::BTreeMap<String, Config>>,
+    source: Option<std::collections::BTreeMap<String, Config>>,
+) -> Option<std::collections::BTreeMap<String, Config>> {
+    let source = if let Some(source) = source {
+        source
+    } else {
+        return base;
+    };
+    let mut merged = base.unwrap_or_default();
+    for (key, config) in source {
+        merged.entry(key).or_default().update(&config);
+    }
+    Some(merged)
+}
+
+fn fetch_url(url: &str) -> CargoResult<Vec<u8>> {
+    let client = crate::ops::http::default_client()?;
+    let res = client.get(url).send()?;
+    let status = res.status();
+    if !status.is_success() {
+        anyhow::bail!("failed to fetch `{url}`: {status}");
+    }
+    Ok(res.bytes()?.to_vec())
+}
+
+fn fetch_git(
+    options: &crate::ops::git::GitOptions,
+    repo: &str,
+    path: &str,
+) -> CargoResult<Vec<u8>> {
+    let checkout =
+        std::env::temp_dir().join(format!("cargo-release-extends-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&checkout);
+    let cloned = crate::ops::git::call_git_outside(
+        options,
+        [
+            std::ffi::OsStr::new("clone"),
+            std::ffi::OsStr::new("--quiet"),
+            std::ffi::OsStr::new("--depth"),
+            std::ffi::OsStr::new("1"),
+            std::ffi::OsStr::new(repo),
+            checkout.as_os_str(),
+        ],
+    )?;
+    let content = if cloned {
+        std::fs::read(checkout.join(path))
+            .with_context(|| format!("Failed to read `{path}` from `{repo}`"))
+    } else {
+        Err(anyhow::format_err!("failed to clone `{repo}`"))
+    };
+    let _ = std::fs::remove_dir_all(&checkout);
+    content
+}
+
+fn verify_checksum(source: &str, content: &[u8], expected: &str) -> CargoResult<()> {
+    use sha2::Digest as _;
+
+    let expected = expected.strip_prefix("sha256:").unwrap_or(expected);
+    let actual = sha2::Sha256::digest(content)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    if !actual.eq_ignore_ascii_case(expected) {
+        anyhow::bail!(
+            "checksum mismatch for `{source}`: expected sha256:{expected}, got sha256:{actual}"
+        );
+    }
+    Ok(())
+}
+
 pub fn resolve_custom_config(file_path: &Path) -> CargoResult<Option<Config>> {
     get_config_from_file(file_path)
 }
@@ -754,13 +2765,21 @@ pub fn resolve_custom_config(file_path: &Path) -> CargoResult<Option<Config>> {
 /// 3. $(workspace)/Cargo.toml
 pub fn resolve_workspace_config(workspace_root: &Path) -> CargoResult<Config> {
     let mut config = Config::default();
+    for layer in workspace_file_layers(workspace_root)? {
+        config.update(&layer.config);
+    }
+    Ok(config)
+}
+
+fn workspace_file_layers(workspace_root: &Path) -> CargoResult<Vec<ConfigLayer>> {
+    let mut layers = Vec::new();
 
     // User-local configuration from home directory.
     let home_dir = dirs_next::home_dir();
     if let Some(mut home) = home_dir {
         home.push(".release.toml");
         if let Some(cfg) = get_config_from_file(&home)? {
-            config.update(&cfg);
+            layers.push(ConfigLayer::new(format!("home ({})", home.display()), cfg));
         }
     };
 
@@ -768,7 +2787,10 @@ pub fn resolve_workspace_config(workspace_root: &Path) -> CargoResult<Config> {
     if let Some(mut config_path) = config_dir {
         config_path.push("cargo-release/release.toml");
         if let Some(cfg) = get_config_from_file(&config_path)? {
-            config.update(&cfg);
+            layers.push(ConfigLayer::new(
+                format!("XDG ({})", config_path.display()),
+                cfg,
+            ));
         }
     };
 
@@ -776,16 +2798,22 @@ pub fn resolve_workspace_config(workspace_root: &Path) -> CargoResult<Config> {
     let default_config = workspace_root.join("release.toml");
     let current_dir_config = get_config_from_file(&default_config)?;
     if let Some(cfg) = current_dir_config {
-        config.update(&cfg);
+        layers.push(ConfigLayer::new(
+            format!("workspace ({})", default_config.display()),
+            cfg,
+        ));
     };
 
     let manifest_path = workspace_root.join("Cargo.toml");
     let current_dir_config = get_ws_config_from_manifest(&manifest_path)?;
     if let Some(cfg) = current_dir_config {
-        config.update(&cfg);
+        layers.push(ConfigLayer::new(
+            format!("workspace ({})", manifest_path.display()),
+            cfg,
+        ));
     };
 
-    Ok(config)
+    Ok(layers)
 }
 
 /// Try to resolve configuration source.
@@ -795,28 +2823,90 @@ pub fn resolve_workspace_config(workspace_root: &Path) -> CargoResult<Config> {
 /// 2. $HOME/.config/cargo-release/release.toml
 /// 3. $(workspace)/release.toml
 /// 3. $(workspace)/Cargo.toml `workspace.metadata.release`
+/// 4. `[package.<name>]` within any of the above
 /// 4. $(crate)/release.toml
 /// 5. $(crate)/Cargo.toml `package.metadata.release`
 ///
 /// `$(crate)/Cargo.toml` is a way to differentiate configuration for the root crate and the
 /// workspace.
 pub fn resolve_config(workspace_root: &Path, manifest_path: &Path) -> CargoResult<Config> {
-    let mut config = resolve_workspace_config(workspace_root)?;
+    let mut config = Config::default();
+    for layer in package_file_layers(workspace_root, manifest_path)? {
+        config.update(&layer.config);
+    }
+    Ok(config)
+}
 
-    // Crate config
+fn package_file_layers(
+    workspace_root: &Path,
+    manifest_path: &Path,
+) -> CargoResult<Vec<ConfigLayer>> {
+    let mut layers = workspace_file_layers(workspace_root)?;
     let crate_root = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+
+    // `[paths."<glob>"]` sections of the workspace config
+    if layers.iter().any(|layer| layer.config.paths.is_some()) {
+        let package_path = crate_root
+            .strip_prefix(workspace_root)
+            .unwrap_or(crate_root)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let mut path_layers = Vec::new();
+        for layer in &layers {
+            for (pattern, config) in layer.config.paths.iter().flatten() {
+                let glob = globset::Glob::new(pattern.trim_end_matches('/'))?.compile_matcher();
+                if glob.is_match(&package_path) {
+                    path_layers.push(ConfigLayer::new(
+                        format!("{} [paths.\"{}\"]", layer.source, pattern),
+                        config.clone(),
+                    ));
+                }
+            }
+        }
+        layers.extend(path_layers);
+    }
+
+    // `[package.<name>]` sections of the workspace config
+    if layers.iter().any(|layer| layer.config.package.is_some()) {
+        let manifest: toml_edit::DocumentMut = std::fs::read_to_string(manifest_path)?.parse()?;
+        if let Some(name) = manifest
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+        {
+            let package_layers = layers
+                .iter()
+                .filter_map(|layer| {
+                    let config = layer.config.package.as_ref()?.get(name)?;
+                    Some(ConfigLayer::new(
+                        format!("{} [package.{}]", layer.source, name),
+                        config.clone(),
+                    ))
+                })
+                .collect::<Vec<_>>();
+            layers.extend(package_layers);
+        }
+    }
+
+    // Crate config
     let default_config = crate_root.join("release.toml");
     let current_dir_config = get_config_from_file(&default_config)?;
     if let Some(cfg) = current_dir_config {
-        config.update(&cfg);
+        layers.push(ConfigLayer::new(
+            format!("package ({})", default_config.display()),
+            cfg,
+        ));
     };
 
     let current_dir_config = get_pkg_config_from_manifest(manifest_path)?;
     if let Some(cfg) = current_dir_config {
-        config.update(&cfg);
+        layers.push(ConfigLayer::new(
+            format!("package ({})", manifest_path.display()),
+            cfg,
+        ));
     };
 
-    Ok(config)
+    Ok(layers)
 }
 
 pub fn resolve_overrides(workspace_root: &Path, manifest_path: &Path) -> CargoResult<Config> {
@@ -827,7 +2917,7 @@ pub fn resolve_overrides(workspace_root: &Path, manifest_path: &Path) -> CargoRe
         if workspace_cache.is_none() {
             let workspace_path = workspace_root.join("Cargo.toml");
             let toml = std::fs::read_to_string(&workspace_path)?;
-            let manifest: CargoManifest = toml::from_str(&toml)
+            let (manifest, _) = parse_config(&toml, MANIFEST_RELEASE_TABLES)
                 .with_context(|| format!("Failed to parse `{}`", workspace_path.display()))?;
 
             *workspace_cache = Some(manifest);
@@ -840,7 +2930,7 @@ pub fn resolve_overrides(workspace_root: &Path, manifest_path: &Path) -> CargoRe
     let mut workspace_cache = None;
     // the publish flag in cargo file
     let manifest = std::fs::read_to_string(manifest_path)?;
-    let manifest: CargoManifest = toml::from_str(&manifest)
+    let (manifest, _): (CargoManifest, _) = parse_config(&manifest, MANIFEST_RELEASE_TABLES)
         .with_context(|| format!("Failed to parse `{}`", manifest_path.display()))?;
     if let Some(package) = manifest.package.as_ref() {
         let publish = match package.publish.as_ref() {
@@ -867,7 +2957,7 @@ pub fn resolve_overrides(workspace_root: &Path, manifest_path: &Path) -> CargoRe
 
         if package.version.is_none() {
             // No point releasing if it can't be published and doesn't have a version to update
-            release_config.release = Some(false);
+            release_config.release = Some(false.into());
         }
         if package
             .version
@@ -910,4 +3000,200 @@ mod test {
             assert!(!release_config.sign_commit());
         }
     }
+
+    mod push_remotes {
+        use super::*;
+
+        #[test]
+        fn string_or_list() {
+            let config: Config = toml::from_str(r#"push-remote = "upstream""#).unwrap();
+            assert_eq!(config.push_remotes().collect::<Vec<_>>(), ["upstream"]);
+
+            let config: Config = toml::from_str(r#"push-remote = ["origin", "mirror"]"#).unwrap();
+            assert_eq!(config.push_remote(), "origin");
+            assert_eq!(
+                config.push_remotes().collect::<Vec<_>>(),
+                ["origin", "mirror"]
+            );
+
+            let config = Config::default();
+            assert_eq!(config.push_remotes().collect::<Vec<_>>(), ["origin"]);
+        }
+    }
+
+    mod release_field {
+        use super::*;
+
+        #[test]
+        fn bool_or_tag_only() {
+            let config: Config = toml::from_str("release = false").unwrap();
+            assert!(!config.release());
+            assert!(config.publish());
+
+            let config: Config = toml::from_str(r#"release = "tag-only""#).unwrap();
+            assert!(config.release());
+            assert!(config.tag_only());
+            assert!(!config.publish());
+
+            let config = Config::default();
+            assert!(config.release());
+            assert!(!config.tag_only());
+        }
+    }
+
+    mod update {
+        use super::*;
+
+        #[test]
+        fn tables_merge_by_key() {
+            let mut config: Config = toml::from_str(
+                r#"
+git-env = { GIT_SSH_COMMAND = "ssh", GIT_TRACE = "0" }
+hook-env = { FOO = "foo", BAR = "bar" }
+dependent-version-overrides = { alpha = "fix", beta = "fix" }
+"#,
+            )
+            .unwrap();
+            let source: Config = toml::from_str(
+                r#"
+git-env = { GIT_TRACE = "1" }
+hook-env = { BAR = "baz" }
+dependent-version-overrides = { beta = "pin" }
+"#,
+            )
+            .unwrap();
+            config.update(&source);
+
+            assert_eq!(config.git_env().get("GIT_SSH_COMMAND").unwrap(), "ssh");
+            assert_eq!(config.git_env().get("GIT_TRACE").unwrap(), "1");
+            assert_eq!(
+                config.hook_env().collect::<Vec<_>>(),
+                [("BAR", "baz"), ("FOO", "foo")]
+            );
+            assert_eq!(config.dependent_version_for("alpha"), DependentVersion::Fix);
+            assert_eq!(config.dependent_version_for("beta"), DependentVersion::Pin);
+        }
+    }
+
+    mod hooks {
+        use super::*;
+
+        #[test]
+        fn commands() {
+            let config: Config = toml::from_str(
+                r#"pre-release-hook = [{ command = ["cargo", "xtask"], run-in-dry-run = true }]"#,
+            )
+            .unwrap();
+            let hooks = config.pre_release_hook().unwrap().hooks();
+            assert_eq!(hooks.len(), 1);
+            assert!(hooks[0].run_in_dry_run());
+        }
+
+        #[test]
+        fn timeout() {
+            let config: Config = toml::from_str(r#"hook-timeout = "2m""#).unwrap();
+            assert_eq!(
+                config.hook_timeout(),
+                Some(std::time::Duration::from_secs(120))
+            );
+        }
+
+        #[test]
+        fn unknown_field() {
+            let err = toml::from_str::<Config>(
+                r#"pre-release-hook = [{ command = ["cargo", "xtask"], run-in-dryrun = true }]"#,
+            );
+            assert!(err.is_err());
+        }
+    }
+
+    mod migrate_config {
+        use super::*;
+
+        #[test]
+        fn inverts_renamed_bool() {
+            let mut doc: toml_edit::DocumentMut =
+                "disable-push = true\ntag = false\ndisable-tag = false\n"
+                    .parse()
+                    .unwrap();
+            let deprecations = super::migrate_config(&mut doc, FILE_RELEASE_TABLES);
+            assert_eq!(deprecations.len(), 2);
+            assert_eq!(doc.to_string(), "tag = false\npush = false\n");
+        }
+    }
+
+    mod resolve_extends {
+        use super::*;
+
+        #[test]
+        fn local_base_is_overridden() {
+            let temp = assert_fs::TempDir::new().unwrap();
+            std::fs::write(
+                temp.path().join("base.toml"),
+                "sign-commit = true\npush = false\n",
+            )
+            .unwrap();
+            let config = Config {
+                extends: Some("base.toml".to_owned()),
+                push: Some(true),
+                ..Default::default()
+            };
+
+            let source = ExtendsSource::Local(temp.path().join("release.toml"));
+            let config = super::resolve_extends(config, &source, 0).unwrap();
+            assert!(config.sign_commit());
+            assert!(config.push());
+            assert_eq!(config.extends, None);
+        }
+
+        #[test]
+        fn keeps_package_sections() {
+            let temp = assert_fs::TempDir::new().unwrap();
+            std::fs::write(
+                temp.path().join("base.toml"),
+                "[package.foo]\nsign-tag = true\n[package.bar]\npush = false\n",
+            )
+            .unwrap();
+            let config: Config = toml::from_str(
+                "extends = \"base.toml\"\n[package.foo]\ntag = false\n[paths.\"crates/*\"]\npublish = false\n",
+            )
+            .unwrap();
+
+            let source = ExtendsSource::Local(temp.path().join("release.toml"));
+            let config = super::resolve_extends(config, &source, 0).unwrap();
+            let package = config.package.unwrap();
+            assert!(package["foo"].sign_tag());
+            assert!(!package["foo"].tag());
+            assert!(!package["bar"].push());
+            assert!(!config.paths.unwrap()["crates/*"].publish());
+        }
+
+        #[test]
+        fn relative_to_remote_source() {
+            let repo = "https://example.com/org/config.git";
+            let source = ExtendsSource::Local("release.toml".into())
+                .join(&format!("git+{repo}#rust/release.toml"))
+                .unwrap();
+            assert_eq!(
+                source.join("../base.toml").unwrap(),
+                ExtendsSource::git(repo, "base.toml")
+            );
+
+            let source = ExtendsSource::Url(
+                reqwest::Url::parse("https://example.com/config/rust/release.toml").unwrap(),
+            );
+            assert_eq!(
+                source.join("base.toml").unwrap(),
+                ExtendsSource::Url(
+                    reqwest::Url::parse("https://example.com/config/rust/base.toml").unwrap()
+                )
+            );
+        }
+
+        #[test]
+        fn checksum_mismatch() {
+            let err = verify_checksum("base.toml", b"push = false\n", "sha256:00").unwrap_err();
+            assert!(err.to_string().contains("checksum mismatch"));
+        }
+    }
 }
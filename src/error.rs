@@ -31,11 +31,13 @@ process_error_from!(anyhow::Error);
 process_error_from!(std::io::Error);
 process_error_from!(semver::Error);
 process_error_from!(ignore::Error);
+process_error_from!(globset::Error);
 process_error_from!(tame_index::Error);
 process_error_from!(tame_index::external::reqwest::Error);
 process_error_from!(cargo_metadata::Error);
 process_error_from!(toml::ser::Error);
 process_error_from!(toml_edit::ser::Error);
+process_error_from!(serde_json::Error);
 
 impl From<i32> for CliError {
     fn from(code: i32) -> Self {
@@ -6,6 +6,7 @@ use bstr::ByteSlice;
 use crate::config;
 use crate::error::CargoResult;
 use crate::ops::cmd::call;
+use crate::ops::cmd::call_with_env;
 
 /// Expresses what features flags should be used
 #[derive(Clone, Debug)]
@@ -22,6 +23,29 @@ fn cargo() -> String {
     env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned())
 }
 
+/// Customization of the verification build
+#[derive(Copy, Clone, Debug, Default)]
+pub struct VerifyOptions<'a> {
+    /// Build with `rustup run <toolchain> cargo`
+    pub toolchain: Option<&'a str>,
+    /// Extra arguments for each verifying `cargo` call
+    pub args: &'a [String],
+    /// Build here rather than in the workspace's `target/`
+    pub target_dir: Option<&'a Path>,
+    /// Resolve dependencies only from `Cargo.lock`
+    pub locked: bool,
+}
+
+impl<'a> VerifyOptions<'a> {
+    fn command<'s>(&'s self, cargo: &'s str) -> Vec<&'s str> {
+        if let Some(toolchain) = self.toolchain {
+            vec!["rustup", "run", toolchain, "cargo"]
+        } else {
+            vec![cargo]
+        }
+    }
+}
+
 pub fn package_content(manifest_path: &Path) -> CargoResult<Vec<std::path::PathBuf>> {
     let mut cmd = std::process::Command::new(cargo());
     cmd.arg("package");
@@ -49,24 +73,129 @@ pub fn package_content(manifest_path: &Path) -> CargoResult<Vec<std::path::PathB
     }
 }
 
+/// Package the crate at `manifest_path` as it would be published, without verifying it
+pub fn package(manifest_path: &Path, target_dir: &Path) -> CargoResult<()> {
+    let output = std::process::Command::new(cargo())
+        .arg("package")
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .arg("--target-dir")
+        .arg(target_dir)
+        .arg("--no-verify")
+        .arg("--allow-dirty")
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let error = String::from_utf8_lossy(&output.stderr);
+        Err(anyhow::format_err!(
+            "failed to package {}: {}",
+            manifest_path.display(),
+            error
+        ))
+    }
+}
+
+/// Extract the `.crate` at `crate_file` into `dest`
+pub fn unpack_crate(crate_file: &Path, dest: &Path) -> CargoResult<()> {
+    std::fs::create_dir_all(dest)?;
+    let output = std::process::Command::new("tar")
+        .arg("-xzf")
+        .arg(crate_file)
+        .arg("-C")
+        .arg(dest)
+        .output()
+        .map_err(|e| anyhow::format_err!("failed to launch `tar`: {e}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(anyhow::format_err!(
+            "failed to unpack {}: {}",
+            crate_file.display(),
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
+
+/// Files that differ between two unpacked crates, relative to their roots
+///
+/// `.cargo_vcs_info.json` is skipped as it records the commit packaged from, and `Cargo.toml` is
+/// compared as TOML as cargo regenerates it.
+pub fn crate_differences(a: &Path, b: &Path) -> CargoResult<Vec<std::path::PathBuf>> {
+    fn files(
+        root: &Path,
+        dir: &Path,
+        found: &mut std::collections::BTreeSet<std::path::PathBuf>,
+    ) -> CargoResult<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                files(root, &path, found)?;
+            } else {
+                found.insert(path.strip_prefix(root).unwrap_or(&path).to_owned());
+            }
+        }
+        Ok(())
+    }
+
+    let mut a_files = std::collections::BTreeSet::new();
+    files(a, a, &mut a_files)?;
+    let mut b_files = std::collections::BTreeSet::new();
+    files(b, b, &mut b_files)?;
+
+    let mut changed = Vec::new();
+    for path in a_files.union(&b_files) {
+        if path == Path::new(".cargo_vcs_info.json") {
+            continue;
+        }
+        let a_content = std::fs::read(a.join(path)).ok();
+        let b_content = std::fs::read(b.join(path)).ok();
+        let same = if path == Path::new("Cargo.toml") {
+            let parse = |content: &Option<Vec<u8>>| {
+                content
+                    .as_deref()
+                    .and_then(|c| std::str::from_utf8(c).ok())
+                    .and_then(|c| c.parse::<toml::Table>().ok())
+            };
+            match (parse(&a_content), parse(&b_content)) {
+                (Some(a_manifest), Some(b_manifest)) => a_manifest == b_manifest,
+                _ => a_content == b_content,
+            }
+        } else {
+            a_content == b_content
+        };
+        if !same {
+            changed.push(path.clone());
+        }
+    }
+    Ok(changed)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn publish(
     dry_run: bool,
     verify: bool,
+    verify_options: &VerifyOptions<'_>,
     manifest_path: &Path,
     pkgid: Option<&str>,
     features: &Features,
     registry: Option<&str>,
+    registry_env: &[(String, std::ffi::OsString)],
     target: Option<&str>,
 ) -> CargoResult<bool> {
     let cargo = cargo();
 
-    let mut command: Vec<&str> = vec![
-        &cargo,
+    let mut command: Vec<&str> = if verify {
+        verify_options.command(&cargo)
+    } else {
+        vec![&cargo]
+    };
+    command.extend([
         "publish",
         "--manifest-path",
         manifest_path.to_str().unwrap(),
-    ];
+    ]);
 
     if let Some(pkgid) = pkgid {
         command.push("--package");
@@ -83,10 +212,170 @@ pub fn publish(
         command.push("--allow-dirty");
     }
 
-    if !verify {
+    if verify {
+        command.extend(verify_options.args.iter().map(|s| s.as_str()));
+    } else {
         command.push("--no-verify");
     }
 
+    if let Some(target_dir) = verify_options.target_dir {
+        command.push("--target-dir");
+        command.push(target_dir.to_str().unwrap());
+    }
+
+    if verify_options.locked {
+        command.push("--locked");
+    }
+
+    if let Some(target) = target {
+        command.push("--target");
+        command.push(target);
+    }
+
+    let feature_arg;
+    match features {
+        Features::None => (),
+        Features::Selective(vec) => {
+            feature_arg = vec.join(" ");
+            command.push("--features");
+            command.push(&feature_arg);
+        }
+        Features::All => {
+            command.push("--all-features");
+        }
+    };
+
+    if registry_env.is_empty() {
+        call(command, false)
+    } else {
+        let envs = registry_env
+            .iter()
+            .map(|(k, v)| (std::ffi::OsStr::new(k), v.as_os_str()))
+            .collect();
+        call_with_env(command, envs, false, None, &std::env::current_dir()?, false)
+    }
+}
+
+/// Build the packaged crate for `target`, like `cargo publish` does before uploading
+pub fn verify_package(
+    dry_run: bool,
+    verify_options: &VerifyOptions<'_>,
+    manifest_path: &Path,
+    pkgid: Option<&str>,
+    features: &Features,
+    target: &str,
+) -> CargoResult<bool> {
+    let cargo = cargo();
+
+    let mut command = verify_options.command(&cargo);
+    command.extend([
+        "package",
+        "--manifest-path",
+        manifest_path.to_str().unwrap(),
+        "--target",
+        target,
+    ]);
+    command.extend(verify_options.args.iter().map(|s| s.as_str()));
+
+    if let Some(target_dir) = verify_options.target_dir {
+        command.push("--target-dir");
+        command.push(target_dir.to_str().unwrap());
+    }
+
+    if verify_options.locked {
+        command.push("--locked");
+    }
+
+    if let Some(pkgid) = pkgid {
+        command.push("--package");
+        command.push(pkgid);
+    }
+
+    if dry_run {
+        command.push("--allow-dirty");
+    }
+
+    let feature_arg;
+    match features {
+        Features::None => (),
+        Features::Selective(vec) => {
+            feature_arg = vec.join(" ");
+            command.push("--features");
+            command.push(&feature_arg);
+        }
+        Features::All => {
+            command.push("--all-features");
+        }
+    };
+
+    call(command, false)
+}
+
+/// Document the package, failing on broken intra-doc links
+pub fn check_doc_links(manifest_path: &Path, pkgid: Option<&str>) -> CargoResult<bool> {
+    let cargo = cargo();
+
+    let mut command = vec![
+        cargo.as_str(),
+        "doc",
+        "--no-deps",
+        "--manifest-path",
+        manifest_path.to_str().unwrap(),
+    ];
+    if let Some(pkgid) = pkgid {
+        command.push("--package");
+        command.push(pkgid);
+    }
+
+    let mut rustdocflags = env::var_os("RUSTDOCFLAGS").unwrap_or_default();
+    if !rustdocflags.is_empty() {
+        rustdocflags.push(" ");
+    }
+    rustdocflags.push("-D rustdoc::broken_intra_doc_links");
+    let envs = [(
+        std::ffi::OsStr::new("RUSTDOCFLAGS"),
+        rustdocflags.as_os_str(),
+    )]
+    .into_iter()
+    .collect();
+    call_with_env(command, envs, false, None, &env::current_dir()?, false)
+}
+
+/// Build the package with a specific profile, which `cargo publish` can't do
+pub fn verify_profile(
+    verify_options: &VerifyOptions<'_>,
+    profile: &str,
+    manifest_path: &Path,
+    pkgid: Option<&str>,
+    features: &Features,
+    target: Option<&str>,
+) -> CargoResult<bool> {
+    let cargo = cargo();
+
+    let mut command = verify_options.command(&cargo);
+    command.extend([
+        "build",
+        "--manifest-path",
+        manifest_path.to_str().unwrap(),
+        "--profile",
+        profile,
+    ]);
+    command.extend(verify_options.args.iter().map(|s| s.as_str()));
+
+    if let Some(target_dir) = verify_options.target_dir {
+        command.push("--target-dir");
+        command.push(target_dir.to_str().unwrap());
+    }
+
+    if verify_options.locked {
+        command.push("--locked");
+    }
+
+    if let Some(pkgid) = pkgid {
+        command.push("--package");
+        command.push(pkgid);
+    }
+
     if let Some(target) = target {
         command.push("--target");
         command.push(target);
@@ -118,7 +407,8 @@ pub fn wait_for_publish(
 ) -> CargoResult<()> {
     if !dry_run {
         if registry.is_some() {
-            // HACK: `index` never reports crates as present for alternative registries
+            // HACK: `index` only knows alternative registries with a configured `index`, and
+            // polling a git index would mean re-fetching it every second
             log::debug!("Not waiting for publish as that is only supported for crates.io; ensure you are using at least cargo v1.66 which will wait for you.");
             return Ok(());
         }
@@ -166,43 +456,41 @@ pub fn is_published(
     }
 }
 
-pub fn set_workspace_version(
-    manifest_path: &Path,
-    version: &str,
+pub fn ensure_owners(
+    name: &str,
+    logins: &[String],
+    registry: Option<&str>,
     dry_run: bool,
 ) -> CargoResult<()> {
-    let original_manifest = std::fs::read_to_string(manifest_path)?;
-    let mut manifest: toml_edit::DocumentMut = original_manifest.parse()?;
-    manifest["workspace"]["package"]["version"] = toml_edit::value(version);
-    let manifest = manifest.to_string();
+    // "Look-before-you-leap" in case the user has permission to publish but not set owners.
+    let current = list_owners(name, registry)?;
 
-    if dry_run {
-        if manifest != original_manifest {
-            let diff = crate::ops::diff::unified_diff(
-                &original_manifest,
-                &manifest,
-                manifest_path,
-                "updated",
-            );
-            log::debug!("change:\n{diff}");
-        }
-    } else {
-        atomic_write(manifest_path, &manifest)?;
+    let expected = logins
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<std::collections::BTreeSet<_>>();
+    let current = current
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<std::collections::BTreeSet<_>>();
+
+    let missing = expected.difference(&current).copied().collect::<Vec<_>>();
+    add_owners(name, &missing, registry, dry_run)?;
+
+    let extra = current.difference(&expected).copied().collect::<Vec<_>>();
+    if !extra.is_empty() {
+        log::debug!("extra owners for {}: {}", name, extra.join(", "));
     }
 
     Ok(())
 }
 
-pub fn ensure_owners(
+/// The logins currently owning `name` on `registry`
+pub fn list_owners(
     name: &str,
-    logins: &[String],
     registry: Option<&str>,
-    dry_run: bool,
-) -> CargoResult<()> {
-    let cargo = cargo();
-
-    // "Look-before-you-leap" in case the user has permission to publish but not set owners.
-    let mut cmd = std::process::Command::new(&cargo);
+) -> CargoResult<std::collections::BTreeSet<String>> {
+    let mut cmd = std::process::Command::new(cargo());
     cmd.arg("owner").arg(name).arg("--color=never");
     cmd.arg("--list");
     if let Some(registry) = registry {
@@ -225,114 +513,218 @@ pub fn ensure_owners(
     for line in raw.lines() {
         if let Some((owner, _)) = line.split_once(' ') {
             if !owner.is_empty() {
-                current.insert(owner);
+                current.insert(owner.to_owned());
             }
         }
     }
+    Ok(current)
+}
 
-    let expected = logins
-        .iter()
-        .map(|s| s.as_str())
-        .collect::<std::collections::BTreeSet<_>>();
-
-    let missing = expected.difference(&current).copied().collect::<Vec<_>>();
-    if !missing.is_empty() {
-        let _ = crate::ops::shell::status(
-            "Adding",
-            format!("owners for {}: {}", name, missing.join(", ")),
-        );
-        if !dry_run {
-            let mut cmd = std::process::Command::new(&cargo);
-            cmd.arg("owner").arg(name).arg("--color=never");
-            for missing in missing {
-                cmd.arg("--add").arg(missing);
-            }
-            if let Some(registry) = registry {
-                cmd.arg("--registry");
-                cmd.arg(registry);
-            }
-            let output = cmd.output()?;
-            if !output.status.success() {
-                // HACK: Can't error as the user might not have permission to set owners and we can't
-                // tell what the error was without parsing it
-                let _ = crate::ops::shell::warn(format!(
-                    "failed to set owners for {}: {}",
-                    name,
-                    String::from_utf8_lossy(&output.stderr)
-                ));
-            }
-        }
+/// Invite `logins` to own `name`, only warning when not permitted
+pub fn add_owners(
+    name: &str,
+    logins: &[&str],
+    registry: Option<&str>,
+    dry_run: bool,
+) -> CargoResult<()> {
+    if logins.is_empty() {
+        return Ok(());
     }
 
-    let extra = current.difference(&expected).copied().collect::<Vec<_>>();
-    if !extra.is_empty() {
-        log::debug!("extra owners for {}: {}", name, extra.join(", "));
+    let _ = crate::ops::shell::status(
+        "Adding",
+        format!("owners for {}: {}", name, logins.join(", ")),
+    );
+    if !dry_run {
+        let mut cmd = std::process::Command::new(cargo());
+        cmd.arg("owner").arg(name).arg("--color=never");
+        for login in logins {
+            cmd.arg("--add").arg(login);
+        }
+        if let Some(registry) = registry {
+            cmd.arg("--registry");
+            cmd.arg(registry);
+        }
+        let output = cmd.output()?;
+        if !output.status.success() {
+            // HACK: Can't error as the user might not have permission to set owners and we can't
+            // tell what the error was without parsing it
+            let _ = crate::ops::shell::warn(format!(
+                "failed to set owners for {}: {}",
+                name,
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
     }
 
     Ok(())
 }
 
-pub fn set_package_version(manifest_path: &Path, version: &str, dry_run: bool) -> CargoResult<()> {
-    let original_manifest = std::fs::read_to_string(manifest_path)?;
-    let mut manifest: toml_edit::DocumentMut = original_manifest.parse()?;
-    manifest["package"]["version"] = toml_edit::value(version);
-    let manifest = manifest.to_string();
+/// Set `rust-version`, following it into `[workspace.package]` when inherited
+pub fn set_rust_version(
+    manifest_path: &Path,
+    workspace_manifest_path: &Path,
+    rust_version: &str,
+    dry_run: bool,
+) -> CargoResult<()> {
+    let mut edits = ManifestEdits::new();
+    edits.set_rust_version(manifest_path, workspace_manifest_path, rust_version)?;
+    edits.write(dry_run)
+}
 
-    if dry_run {
-        if manifest != original_manifest {
-            let diff = crate::ops::diff::unified_diff(
-                &original_manifest,
-                &manifest,
-                manifest_path,
-                "updated",
-            );
-            log::debug!("change:\n{diff}");
-        }
-    } else {
-        atomic_write(manifest_path, &manifest)?;
+/// The version of the installed stable Rust toolchain
+pub fn stable_rust_version() -> CargoResult<semver::Version> {
+    let output = std::process::Command::new("rustup")
+        .args(["run", "stable", "rustc", "--version"])
+        .output()
+        .or_else(|_| {
+            std::process::Command::new("rustc")
+                .arg("--version")
+                .output()
+        })
+        .map_err(|_| anyhow::format_err!("`rustc` not found"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to look up the stable Rust version: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
     }
+    // rustc 1.80.1 (3f5fd8dd4 2024-08-06)
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version = stdout
+        .split_whitespace()
+        .nth(1)
+        .ok_or_else(|| anyhow::format_err!("unrecognized `rustc --version`: {}", stdout.trim()))?;
+    Ok(semver::Version::parse(version)?)
+}
 
-    Ok(())
+pub fn set_package_version(manifest_path: &Path, version: &str, dry_run: bool) -> CargoResult<()> {
+    let mut edits = ManifestEdits::new();
+    edits.set_package_version(manifest_path, version)?;
+    edits.write(dry_run)
 }
 
-pub fn upgrade_dependency_req(
-    manifest_name: &str,
-    manifest_path: &Path,
-    root: &Path,
-    name: &str,
-    version: &semver::Version,
-    upgrade: config::DependentVersion,
-    dry_run: bool,
-) -> CargoResult<()> {
-    let manifest_root = manifest_path
-        .parent()
-        .expect("always at least a parent dir");
-    let original_manifest = std::fs::read_to_string(manifest_path)?;
-    let mut manifest: toml_edit::DocumentMut = original_manifest.parse()?;
+/// Edits to manifests, applied in memory and written out once per manifest
+///
+/// This avoids re-reading and re-writing a manifest for every version or dependency change in a
+/// release.
+#[derive(Debug, Default)]
+pub struct ManifestEdits {
+    manifests: std::collections::BTreeMap<std::path::PathBuf, EditedManifest>,
+}
 
-    for dep_item in find_dependency_tables(manifest.as_table_mut())
-        .flat_map(|t| t.iter_mut().filter_map(|(_, d)| d.as_table_like_mut()))
-        .filter(|d| is_relevant(*d, manifest_root, root))
-    {
-        upgrade_req(manifest_name, dep_item, name, version, upgrade);
+#[derive(Debug)]
+struct EditedManifest {
+    original: String,
+    manifest: toml_edit::DocumentMut,
+}
+
+impl ManifestEdits {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    let manifest = manifest.to_string();
-    if manifest != original_manifest {
-        if dry_run {
-            let diff = crate::ops::diff::unified_diff(
-                &original_manifest,
-                &manifest,
-                manifest_path,
-                "updated",
-            );
-            log::debug!("change:\n{diff}");
+    fn manifest(&mut self, manifest_path: &Path) -> CargoResult<&mut toml_edit::DocumentMut> {
+        let edited = match self.manifests.entry(manifest_path.to_owned()) {
+            std::collections::btree_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::btree_map::Entry::Vacant(entry) => {
+                let original = std::fs::read_to_string(manifest_path)?;
+                let manifest = original.parse()?;
+                entry.insert(EditedManifest { original, manifest })
+            }
+        };
+        Ok(&mut edited.manifest)
+    }
+
+    pub fn set_package_version(&mut self, manifest_path: &Path, version: &str) -> CargoResult<()> {
+        let manifest = self.manifest(manifest_path)?;
+        manifest["package"]["version"] = toml_edit::value(version);
+        Ok(())
+    }
+
+    pub fn set_workspace_version(
+        &mut self,
+        manifest_path: &Path,
+        version: &str,
+    ) -> CargoResult<()> {
+        let manifest = self.manifest(manifest_path)?;
+        manifest["workspace"]["package"]["version"] = toml_edit::value(version);
+        Ok(())
+    }
+
+    /// Set `rust-version`, following it into `[workspace.package]` when inherited
+    pub fn set_rust_version(
+        &mut self,
+        manifest_path: &Path,
+        workspace_manifest_path: &Path,
+        rust_version: &str,
+    ) -> CargoResult<()> {
+        let is_inherited = self
+            .manifest(manifest_path)?
+            .get("package")
+            .and_then(|p| p.get("rust-version"))
+            .and_then(|r| r.as_table_like())
+            .and_then(|r| r.get("workspace"))
+            .and_then(|w| w.as_bool())
+            .unwrap_or(false);
+
+        if is_inherited {
+            let manifest = self.manifest(workspace_manifest_path)?;
+            manifest["workspace"]["package"]["rust-version"] = toml_edit::value(rust_version);
         } else {
-            atomic_write(manifest_path, &manifest)?;
+            let manifest = self.manifest(manifest_path)?;
+            manifest["package"]["rust-version"] = toml_edit::value(rust_version);
         }
+        Ok(())
     }
 
-    Ok(())
+    pub fn upgrade_dependency_req(
+        &mut self,
+        manifest_name: &str,
+        manifest_path: &Path,
+        root: &Path,
+        name: &str,
+        version: &semver::Version,
+        upgrade: config::DependentVersion,
+        prerelease: Option<config::DependentPrerelease>,
+    ) -> CargoResult<()> {
+        let manifest_root = manifest_path
+            .parent()
+            .expect("always at least a parent dir");
+        let manifest = self.manifest(manifest_path)?;
+
+        for dep_item in find_dependency_tables(manifest.as_table_mut())
+            .flat_map(|t| t.iter_mut().filter_map(|(_, d)| d.as_table_like_mut()))
+            .filter(|d| is_relevant(*d, manifest_root, root))
+        {
+            upgrade_req(manifest_name, dep_item, name, version, upgrade, prerelease);
+        }
+
+        Ok(())
+    }
+
+    /// Write out each manifest that changed, or log the diff in dry-run mode
+    pub fn write(self, dry_run: bool) -> CargoResult<()> {
+        for (manifest_path, edited) in self.manifests {
+            let manifest = edited.manifest.to_string();
+            if manifest == edited.original {
+                continue;
+            }
+            if dry_run {
+                let diff = crate::ops::diff::unified_diff(
+                    &edited.original,
+                    &manifest,
+                    &manifest_path,
+                    "updated",
+                );
+                log::debug!("change:\n{diff}");
+            } else {
+                atomic_write(&manifest_path, &manifest)?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 fn find_dependency_tables(
@@ -397,6 +789,7 @@ fn upgrade_req(
     name: &str,
     version: &semver::Version,
     upgrade: config::DependentVersion,
+    prerelease: Option<config::DependentPrerelease>,
 ) -> bool {
     let version_value = if let Some(version_value) = dep_item.get_mut("version") {
         version_value
@@ -411,38 +804,16 @@ fn upgrade_req(
         log::debug!("unsupported dependency {}", name);
         return false;
     };
-    let existing_req = if let Ok(existing_req) = semver::VersionReq::parse(existing_req_str) {
-        existing_req
-    } else {
+    if semver::VersionReq::parse(existing_req_str).is_err() {
         log::debug!("unsupported dependency req {}={}", name, existing_req_str);
         return false;
-    };
-    let new_req = match upgrade {
-        config::DependentVersion::Fix => {
-            if !existing_req.matches(version) {
-                let new_req = crate::ops::version::upgrade_requirement(existing_req_str, version)
-                    .ok()
-                    .flatten();
-                if let Some(new_req) = new_req {
-                    new_req
-                } else {
-                    return false;
-                }
-            } else {
-                return false;
-            }
-        }
-        config::DependentVersion::Upgrade => {
-            let new_req = crate::ops::version::upgrade_requirement(existing_req_str, version)
-                .ok()
-                .flatten();
-            if let Some(new_req) = new_req {
-                new_req
-            } else {
-                return false;
-            }
-        }
-    };
+    }
+    let new_req =
+        if let Some(new_req) = upgraded_req(existing_req_str, version, upgrade, prerelease) {
+            new_req
+        } else {
+            return false;
+        };
 
     let _ = crate::ops::shell::status(
         "Updating",
@@ -455,6 +826,45 @@ fn upgrade_req(
     true
 }
 
+/// The requirement a dependent will have on `version` after being updated, if it changes
+pub fn upgraded_req(
+    existing_req_str: &str,
+    version: &semver::Version,
+    upgrade: config::DependentVersion,
+    prerelease: Option<config::DependentPrerelease>,
+) -> Option<String> {
+    let existing_req = semver::VersionReq::parse(existing_req_str).ok()?;
+    if let (false, Some(prerelease)) = (version.pre.is_empty(), prerelease) {
+        // Requirements only match pre-releases of the same `major.minor.patch`, so spell it out
+        let new_req = match prerelease {
+            config::DependentPrerelease::Pin => crate::ops::version::pin_requirement(version),
+            config::DependentPrerelease::Caret => crate::ops::version::caret_requirement(version),
+            config::DependentPrerelease::Ignore => return None,
+        };
+        return (new_req != existing_req_str).then_some(new_req);
+    }
+    match upgrade {
+        config::DependentVersion::Fix => {
+            if !existing_req.matches(version) {
+                crate::ops::version::upgrade_requirement(existing_req_str, version)
+                    .ok()
+                    .flatten()
+            } else {
+                None
+            }
+        }
+        config::DependentVersion::Upgrade => {
+            crate::ops::version::upgrade_requirement(existing_req_str, version)
+                .ok()
+                .flatten()
+        }
+        config::DependentVersion::Pin => {
+            let new_req = crate::ops::version::pin_requirement(version);
+            (new_req != existing_req_str).then_some(new_req)
+        }
+    }
+}
+
 pub fn update_lock(manifest_path: &Path) -> CargoResult<()> {
     cargo_metadata::MetadataCommand::new()
         .manifest_path(manifest_path)
@@ -463,9 +873,113 @@ pub fn update_lock(manifest_path: &Path) -> CargoResult<()> {
     Ok(())
 }
 
-pub fn sort_workspace(ws_meta: &cargo_metadata::Metadata) -> Vec<&cargo_metadata::PackageId> {
+/// Whether `Cargo.lock` already agrees with the manifests, so `--locked` builds can succeed
+pub fn lock_is_current(manifest_path: &Path) -> CargoResult<bool> {
+    let output = std::process::Command::new(cargo())
+        .args([
+            "metadata",
+            "--locked",
+            "--format-version",
+            "1",
+            "--manifest-path",
+        ])
+        .arg(manifest_path)
+        .stdout(std::process::Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        log::debug!(
+            "`cargo metadata --locked` failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(output.status.success())
+}
+
+pub fn update_lock_packages<'s>(
+    manifest_path: &Path,
+    names: impl IntoIterator<Item = &'s str>,
+) -> CargoResult<()> {
+    let cargo = cargo();
+
+    let mut command: Vec<&str> = vec![
+        &cargo,
+        "update",
+        "--manifest-path",
+        manifest_path.to_str().unwrap(),
+    ];
+    for name in names {
+        command.push("--package");
+        command.push(name);
+    }
+    if !call(command, false)? {
+        anyhow::bail!("failed to update `Cargo.lock`");
+    }
+
+    Ok(())
+}
+
+/// Report the `Cargo.lock` changes for packages moving from the old to the new version
+pub fn preview_lock<'s>(
+    lock_path: &Path,
+    versions: impl IntoIterator<Item = (&'s str, &'s str, &'s str)>,
+) -> CargoResult<()> {
+    let original_lock = std::fs::read_to_string(lock_path)?;
+    let mut lock: toml_edit::DocumentMut = original_lock.parse()?;
+    if let Some(packages) = lock
+        .get_mut("package")
+        .and_then(|p| p.as_array_of_tables_mut())
+    {
+        for (name, old, new) in versions {
+            for package in packages.iter_mut() {
+                // Only workspace members lack a `source`
+                if package.get("name").and_then(|n| n.as_str()) == Some(name)
+                    && package.get("version").and_then(|v| v.as_str()) == Some(old)
+                    && !package.contains_key("source")
+                {
+                    package["version"] = toml_edit::value(new);
+                }
+            }
+        }
+    }
+    let lock = lock.to_string();
+
+    if lock != original_lock {
+        let diff = crate::ops::diff::unified_diff(&original_lock, &lock, lock_path, "updated");
+        log::debug!("change:\n{diff}");
+    }
+
+    Ok(())
+}
+
+/// Order workspace members so dependencies are published first
+///
+/// `publish_order` lists batches of package names; every package in a batch is published after
+/// all packages in the preceding batches.
+pub fn sort_workspace<'m>(
+    ws_meta: &'m cargo_metadata::Metadata,
+    publish_order: &[Vec<String>],
+) -> CargoResult<Vec<&'m cargo_metadata::PackageId>> {
     let members: std::collections::HashSet<_> = ws_meta.workspace_members.iter().collect();
-    let dep_tree: std::collections::HashMap<_, _> = ws_meta
+    let mut batches: std::collections::HashMap<&cargo_metadata::PackageId, usize> =
+        Default::default();
+    let mut ordered = Vec::new();
+    for (i, batch) in publish_order.iter().enumerate() {
+        for name in batch {
+            let pkg = ws_meta
+                .packages
+                .iter()
+                .find(|p| p.name == *name && members.contains(&p.id))
+                .ok_or_else(|| {
+                    anyhow::format_err!("`publish-order` references unknown package `{name}`")
+                })?;
+            if batches.insert(&pkg.id, i).is_some() {
+                anyhow::bail!("`publish-order` lists `{name}` more than once");
+            }
+            ordered.push((&pkg.id, i));
+        }
+    }
+
+    let mut dep_tree: std::collections::HashMap<_, Vec<_>> = ws_meta
         .resolve
         .as_ref()
         .expect("cargo-metadata resolved deps")
@@ -499,6 +1013,16 @@ pub fn sort_workspace(ws_meta: &cargo_metadata::Metadata) -> Vec<&cargo_metadata
             }
         })
         .collect();
+    for (pkg_id, batch) in &ordered {
+        let earlier = ordered
+            .iter()
+            .filter(|(_, other)| other < batch)
+            .map(|(other_id, _)| *other_id);
+        dep_tree
+            .get_mut(pkg_id)
+            .expect("batches only contain members")
+            .extend(earlier);
+    }
 
     let mut sorted = Vec::new();
     let mut processed = std::collections::HashSet::new();
@@ -506,7 +1030,21 @@ pub fn sort_workspace(ws_meta: &cargo_metadata::Metadata) -> Vec<&cargo_metadata
         sort_workspace_inner(pkg_id, &dep_tree, &mut processed, &mut sorted);
     }
 
-    sorted
+    // Cycles are silently broken, so check the requested order was achievable
+    let mut latest_batch = 0;
+    for pkg_id in &sorted {
+        if let Some(batch) = batches.get(pkg_id).copied() {
+            if batch < latest_batch {
+                anyhow::bail!(
+                    "`publish-order` conflicts with the dependencies of `{}`",
+                    ws_meta[*pkg_id].name
+                );
+            }
+            latest_batch = batch;
+        }
+    }
+
+    Ok(sorted)
 }
 
 fn sort_workspace_inner<'m>(
@@ -532,6 +1070,89 @@ fn sort_workspace_inner<'m>(
     sorted.push(pkg_id);
 }
 
+/// Order workspaces so those depended on (via `path` dependencies) come first
+pub fn sort_workspaces(
+    ws_metas: Vec<cargo_metadata::Metadata>,
+) -> CargoResult<Vec<cargo_metadata::Metadata>> {
+    let member_roots: Vec<std::collections::HashSet<_>> = ws_metas
+        .iter()
+        .map(|ws_meta| {
+            ws_meta
+                .workspace_packages()
+                .into_iter()
+                .filter_map(|pkg| pkg.manifest_path.parent())
+                .collect()
+        })
+        .collect();
+    let dep_tree: Vec<Vec<usize>> = ws_metas
+        .iter()
+        .enumerate()
+        .map(|(i, ws_meta)| {
+            let dep_roots: std::collections::HashSet<_> = ws_meta
+                .workspace_packages()
+                .into_iter()
+                .flat_map(|pkg| pkg.dependencies.iter())
+                // Ignore dev dependencies, like `sort_workspace`
+                .filter(|dep| dep.kind != cargo_metadata::DependencyKind::Development)
+                .filter_map(|dep| dep.path.as_deref())
+                .collect();
+            (0..ws_metas.len())
+                .filter(|j| *j != i)
+                .filter(|j| member_roots[*j].iter().any(|root| dep_roots.contains(root)))
+                .collect()
+        })
+        .collect();
+
+    let mut sorted = Vec::new();
+    let mut processed = std::collections::HashSet::new();
+    let mut visiting = Vec::new();
+    for i in 0..ws_metas.len() {
+        sort_workspaces_inner(i, &dep_tree, &mut processed, &mut visiting, &mut sorted).map_err(
+            |cycle| {
+                let cycle = cycle
+                    .into_iter()
+                    .map(|j| ws_metas[j].workspace_root.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                anyhow::format_err!("workspaces depend on each other: {cycle}")
+            },
+        )?;
+    }
+
+    let mut ws_metas: Vec<_> = ws_metas.into_iter().map(Some).collect();
+    Ok(sorted
+        .into_iter()
+        .map(|i| ws_metas[i].take().expect("each workspace is sorted once"))
+        .collect())
+}
+
+fn sort_workspaces_inner(
+    i: usize,
+    dep_tree: &[Vec<usize>],
+    processed: &mut std::collections::HashSet<usize>,
+    visiting: &mut Vec<usize>,
+    sorted: &mut Vec<usize>,
+) -> Result<(), Vec<usize>> {
+    if let Some(start) = visiting.iter().position(|j| *j == i) {
+        let mut cycle = visiting[start..].to_vec();
+        cycle.push(i);
+        return Err(cycle);
+    }
+    if processed.contains(&i) {
+        return Ok(());
+    }
+
+    visiting.push(i);
+    for dep in &dep_tree[i] {
+        sort_workspaces_inner(*dep, dep_tree, processed, visiting, sorted)?;
+    }
+    visiting.pop();
+
+    processed.insert(i);
+    sorted.push(i);
+    Ok(())
+}
+
 fn atomic_write(path: &Path, data: &str) -> std::io::Result<()> {
     let temp_path = path
         .parent()
@@ -578,6 +1199,84 @@ mod test {
         }
     }
 
+    mod manifest_edits {
+        use super::*;
+
+        #[test]
+        fn combines_edits() {
+            let temp = assert_fs::TempDir::new().unwrap();
+            temp.copy_from("tests/fixtures/simple", &["**"]).unwrap();
+            let manifest_path = temp.child("Cargo.toml");
+
+            let mut edits = ManifestEdits::new();
+            edits
+                .set_package_version(manifest_path.path(), "2.0.0")
+                .unwrap();
+            edits
+                .set_rust_version(manifest_path.path(), manifest_path.path(), "1.70")
+                .unwrap();
+            edits.write(false).unwrap();
+
+            let meta = cargo_metadata::MetadataCommand::new()
+                .manifest_path(manifest_path.path())
+                .exec()
+                .unwrap();
+            assert_eq!(meta.packages[0].version.to_string(), "2.0.0");
+            assert_eq!(
+                meta.packages[0]
+                    .rust_version
+                    .as_ref()
+                    .map(|v| v.to_string()),
+                Some("1.70.0".to_owned())
+            );
+
+            temp.close().unwrap();
+        }
+    }
+
+    mod upgraded_req {
+        use super::*;
+
+        #[test]
+        fn prerelease_policy() {
+            let rc = semver::Version::parse("1.2.0-rc.1").unwrap();
+            let upgrade = config::DependentVersion::Upgrade;
+            assert_eq!(
+                upgraded_req("1.1", &rc, upgrade, Some(config::DependentPrerelease::Pin)),
+                Some("=1.2.0-rc.1".to_owned())
+            );
+            assert_eq!(
+                upgraded_req(
+                    "1.1",
+                    &rc,
+                    upgrade,
+                    Some(config::DependentPrerelease::Caret)
+                ),
+                Some("1.2.0-rc.1".to_owned())
+            );
+            assert_eq!(
+                upgraded_req(
+                    "1.1",
+                    &rc,
+                    upgrade,
+                    Some(config::DependentPrerelease::Ignore)
+                ),
+                None
+            );
+
+            let release = semver::Version::parse("1.2.0").unwrap();
+            assert_eq!(
+                upgraded_req(
+                    "=1.2.0-rc.1",
+                    &release,
+                    config::DependentVersion::Pin,
+                    Some(config::DependentPrerelease::Ignore)
+                ),
+                Some("=1.2.0".to_owned())
+            );
+        }
+    }
+
     mod update_lock {
         use super::*;
 
@@ -669,7 +1368,7 @@ mod test {
                 .exec()
                 .unwrap();
 
-            let sorted = sort_workspace(&meta);
+            let sorted = sort_workspace(&meta, &[]).unwrap();
             let root_package = meta.resolve.as_ref().unwrap().root.as_ref().unwrap();
             assert_ne!(
                 sorted[0], root_package,
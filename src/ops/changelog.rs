@@ -0,0 +1,494 @@
+//! Native support for [Keep a Changelog](https://keepachangelog.com/)
+
+use std::ops::Range;
+
+use crate::error::CargoResult;
+
+/// The release notes collected under the `Unreleased` heading
+pub fn unreleased_notes(changelog: &str) -> Option<&str> {
+    let section = find_unreleased(changelog)?;
+    Some(changelog[section.body].trim())
+}
+
+/// The release notes under the heading for `version`
+pub fn release_notes<'c>(changelog: &'c str, version: &str) -> Option<&'c str> {
+    let section = find_section(changelog, |line| {
+        line.starts_with("## ") && heading_label(line) == version
+    })?;
+    Some(changelog[section.body].trim())
+}
+
+/// Move the `Unreleased` notes under a heading for `version`, leaving an empty `Unreleased`
+/// section behind
+///
+/// When `tag_name` is known, compare links (`[Unreleased]: .../compare/v1.0.0...HEAD`) are
+/// updated as well.
+pub fn rotate(
+    changelog: &str,
+    version: &str,
+    date: &str,
+    tag_name: Option<&str>,
+) -> CargoResult<String> {
+    let section = find_unreleased(changelog)
+        .ok_or_else(|| anyhow::format_err!("no `## [Unreleased]` section found"))?;
+
+    let mut rotated = String::with_capacity(changelog.len());
+    rotated.push_str(&changelog[..section.heading.end]);
+    rotated.push_str("\n\n");
+    rotated.push_str(&format!("## [{version}] - {date}"));
+    rotated.push_str(&changelog[section.heading.end..]);
+
+    if let Some(tag_name) = tag_name {
+        rotated = rotate_links(&rotated, version, tag_name);
+    }
+
+    Ok(rotated)
+}
+
+/// Fold the notes of `version`'s pre-releases back into the `Unreleased` section, dropping their
+/// headings and links
+///
+/// This lets the stable release collect everything since the last stable release.
+pub fn fold_prereleases(changelog: &str, version: &str) -> CargoResult<String> {
+    let prefix = format!("{version}-");
+    let mut kept = String::with_capacity(changelog.len());
+    let mut notes = Vec::new();
+    let mut current: Option<String> = None;
+    for line in changelog.split_inclusive('\n') {
+        let content = line.trim_end();
+        let is_heading = content.starts_with("## ");
+        let is_link = is_link_definition(content);
+        if is_heading || is_link {
+            if let Some(body) = current.take() {
+                notes.push(body);
+            }
+            let label = if is_heading {
+                heading_label(content)
+            } else {
+                link_label(content)
+            };
+            if label.starts_with(&prefix) {
+                if is_heading {
+                    current = Some(String::new());
+                }
+                continue;
+            }
+        }
+        if let Some(body) = current.as_mut() {
+            body.push_str(line);
+        } else {
+            kept.push_str(line);
+        }
+    }
+    if let Some(body) = current.take() {
+        notes.push(body);
+    }
+
+    let notes = notes
+        .iter()
+        .map(|n| n.trim())
+        .filter(|n| !n.is_empty())
+        .collect::<Vec<_>>();
+    if notes.is_empty() {
+        return Ok(kept);
+    }
+
+    let section = find_unreleased(&kept)
+        .ok_or_else(|| anyhow::format_err!("no `## [Unreleased]` section found"))?;
+    let mut body = kept[section.body.clone()].trim().to_owned();
+    for note in notes {
+        if !body.is_empty() {
+            body.push_str("\n\n");
+        }
+        body.push_str(note);
+    }
+
+    Ok(replace_body(&kept, &section, &body))
+}
+
+/// Append `note` to the `Unreleased` section
+pub fn add_unreleased_note(changelog: &str, note: &str) -> CargoResult<String> {
+    let section = find_unreleased(changelog)
+        .ok_or_else(|| anyhow::format_err!("no `## [Unreleased]` section found"))?;
+    let mut body = changelog[section.body.clone()].trim().to_owned();
+    if !body.is_empty() {
+        body.push('\n');
+    }
+    body.push_str(note);
+
+    Ok(replace_body(changelog, &section, &body))
+}
+
+/// Add the `Unreleased` and `version` compare links when the changelog doesn't have them yet
+///
+/// `prev_tag` is the tag of the release before `version`, if any.
+pub fn add_missing_links(
+    changelog: &str,
+    repository_url: &str,
+    version: &str,
+    prev_tag: Option<&str>,
+    tag_name: &str,
+) -> String {
+    // GitLab nests repository pages under `/-/`
+    let pages = if repository_url.contains("gitlab") {
+        format!("{repository_url}/-")
+    } else {
+        repository_url.to_owned()
+    };
+
+    let mut unreleased_end = None;
+    let mut first_link = None;
+    let mut has_version = false;
+    let mut offset = 0;
+    for line in changelog.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let content = line.trim_end();
+        if !is_link_definition(content) {
+            continue;
+        }
+        first_link.get_or_insert(start);
+        let label = link_label(content);
+        if label.eq_ignore_ascii_case(UNRELEASED) {
+            unreleased_end = Some(offset);
+        } else if label == version {
+            has_version = true;
+        }
+    }
+
+    let mut links = String::new();
+    if unreleased_end.is_none() {
+        links.push_str(&format!(
+            "[Unreleased]: {pages}/compare/{tag_name}...HEAD\n"
+        ));
+    }
+    if !has_version {
+        let url = if let Some(prev_tag) = prev_tag {
+            format!("{pages}/compare/{prev_tag}...{tag_name}")
+        } else if pages == repository_url {
+            format!("{pages}/releases/tag/{tag_name}")
+        } else {
+            format!("{pages}/tags/{tag_name}")
+        };
+        links.push_str(&format!("[{version}]: {url}\n"));
+    }
+    if links.is_empty() {
+        return changelog.to_owned();
+    }
+
+    let mut updated = String::with_capacity(changelog.len() + links.len() + 1);
+    if let Some(at) = unreleased_end.or(first_link) {
+        let (before, after) = changelog.split_at(at);
+        updated.push_str(before);
+        if !before.ends_with('\n') {
+            updated.push('\n');
+        }
+        updated.push_str(&links);
+        updated.push_str(after);
+    } else {
+        updated.push_str(changelog.trim_end());
+        updated.push_str("\n\n");
+        updated.push_str(&links);
+    }
+    updated
+}
+
+fn replace_body(changelog: &str, section: &Section, body: &str) -> String {
+    let mut replaced = String::with_capacity(changelog.len() + body.len());
+    replaced.push_str(&changelog[..section.heading.end]);
+    replaced.push_str("\n\n");
+    if !body.is_empty() {
+        replaced.push_str(body);
+        replaced.push_str(if section.body.end < changelog.len() {
+            "\n\n"
+        } else {
+            "\n"
+        });
+    }
+    replaced.push_str(&changelog[section.body.end..]);
+    replaced
+}
+
+struct Section {
+    /// The heading line, without its line ending
+    heading: Range<usize>,
+    body: Range<usize>,
+}
+
+fn find_unreleased(changelog: &str) -> Option<Section> {
+    find_section(changelog, is_unreleased_heading)
+}
+
+/// The first section whose heading line satisfies `is_heading`
+fn find_section(changelog: &str, is_heading: impl Fn(&str) -> bool) -> Option<Section> {
+    let mut heading = None;
+    let mut offset = 0;
+    for line in changelog.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let content = line.trim_end();
+        if let Some(heading) = &heading {
+            if content.starts_with("## ") || is_link_definition(content) {
+                return Some(Section {
+                    body: heading.end..start,
+                    heading: heading.clone(),
+                });
+            }
+        } else if is_heading(content) {
+            heading = Some(start..start + content.len());
+        }
+    }
+    heading.map(|heading| Section {
+        body: heading.end..changelog.len(),
+        heading,
+    })
+}
+
+fn is_unreleased_heading(line: &str) -> bool {
+    let title = if let Some(title) = line.strip_prefix("## ") {
+        title
+    } else {
+        return false;
+    };
+    let title = title.trim_start().trim_start_matches('[');
+    title
+        .get(..UNRELEASED.len())
+        .map(|t| t.eq_ignore_ascii_case(UNRELEASED))
+        .unwrap_or(false)
+}
+
+fn is_link_definition(line: &str) -> bool {
+    line.starts_with('[') && line.contains("]: ")
+}
+
+/// The version (or `Unreleased`) a `## [x.y.z] - date` heading is for
+fn heading_label(line: &str) -> &str {
+    let title = line.trim_start_matches('#').trim_start();
+    let title = title.trim_start_matches('[');
+    let end = title
+        .find(|c: char| c == ']' || c.is_whitespace())
+        .unwrap_or(title.len());
+    &title[..end]
+}
+
+fn link_label(line: &str) -> &str {
+    line.trim_start_matches('[')
+        .split(']')
+        .next()
+        .unwrap_or_default()
+}
+
+fn rotate_links(changelog: &str, version: &str, tag_name: &str) -> String {
+    let mut rotated = String::with_capacity(changelog.len());
+    for line in changelog.split_inclusive('\n') {
+        let content = line.trim_end();
+        let (label, url) = if let Some(link) = content.split_once("]: ") {
+            link
+        } else {
+            rotated.push_str(line);
+            continue;
+        };
+        let is_unreleased = label
+            .strip_prefix('[')
+            .map(|l| l.eq_ignore_ascii_case(UNRELEASED))
+            .unwrap_or(false);
+        let compare = url
+            .strip_suffix("...HEAD")
+            .and_then(|base| base.rfind('/').map(|i| &base[..=i]));
+        match (is_unreleased, compare) {
+            (true, Some(compare)) => {
+                let ending = &line[content.len()..];
+                let ending = if ending.is_empty() { "\n" } else { ending };
+                rotated.push_str(&format!("{label}]: {compare}{tag_name}...HEAD{ending}"));
+                let released = url.replace("...HEAD", &format!("...{tag_name}"));
+                rotated.push_str(&format!("[{version}]: {released}{ending}"));
+            }
+            (true, None) => {
+                log::debug!("not updating unrecognized changelog link {}", content);
+                rotated.push_str(line);
+            }
+            (false, _) => {
+                rotated.push_str(line);
+            }
+        }
+    }
+    rotated
+}
+
+const UNRELEASED: &str = "unreleased";
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    mod unreleased_notes {
+        use super::*;
+
+        #[test]
+        fn empty() {
+            let changelog = "# Changelog\n\n## [Unreleased] - ReleaseDate\n\n## [1.0.0] - 2024-01-01\n\n- Initial release\n";
+            assert_eq!(unreleased_notes(changelog), Some(""));
+        }
+
+        #[test]
+        fn before_links() {
+            let changelog = "# Changelog\n\n## [Unreleased]\n\n- Fixed a bug\n\n[Unreleased]: https://github.com/o/r/compare/v1.0.0...HEAD\n";
+            assert_eq!(unreleased_notes(changelog), Some("- Fixed a bug"));
+        }
+    }
+
+    mod rotate {
+        use super::*;
+
+        #[test]
+        fn with_links() {
+            let changelog = "\
+# Changelog
+
+## [Unreleased]
+
+- Fixed a bug
+
+## [1.0.0] - 2024-01-01
+
+- Initial release
+
+[Unreleased]: https://github.com/o/r/compare/v1.0.0...HEAD
+[1.0.0]: https://github.com/o/r/compare/v0.1.0...v1.0.0
+";
+            let expected = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.0.1] - 2024-02-01
+
+- Fixed a bug
+
+## [1.0.0] - 2024-01-01
+
+- Initial release
+
+[Unreleased]: https://github.com/o/r/compare/v1.0.1...HEAD
+[1.0.1]: https://github.com/o/r/compare/v1.0.0...v1.0.1
+[1.0.0]: https://github.com/o/r/compare/v0.1.0...v1.0.0
+";
+            let actual = rotate(changelog, "1.0.1", "2024-02-01", Some("v1.0.1")).unwrap();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod add_missing_links {
+        use super::*;
+
+        #[test]
+        fn without_footer() {
+            let changelog = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.0.1] - 2024-02-01
+
+- Fixed a bug
+";
+            let expected = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.0.1] - 2024-02-01
+
+- Fixed a bug
+
+[Unreleased]: https://github.com/o/r/compare/v1.0.1...HEAD
+[1.0.1]: https://github.com/o/r/compare/v1.0.0...v1.0.1
+";
+            let actual = add_missing_links(
+                changelog,
+                "https://github.com/o/r",
+                "1.0.1",
+                Some("v1.0.0"),
+                "v1.0.1",
+            );
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn keeps_existing() {
+            let changelog = "\
+## [Unreleased]
+
+[Unreleased]: https://github.com/o/r/compare/v1.0.1...HEAD
+[1.0.1]: https://github.com/o/r/compare/v1.0.0...v1.0.1
+";
+            let actual = add_missing_links(
+                changelog,
+                "https://github.com/o/r",
+                "1.0.1",
+                Some("v1.0.0"),
+                "v1.0.1",
+            );
+            assert_eq!(actual, changelog);
+        }
+    }
+
+    mod release_notes {
+        use super::*;
+
+        #[test]
+        fn between_versions() {
+            let changelog = "# Changelog\n\n## [Unreleased]\n\n## [1.0.1] - 2024-02-01\n\n- Fixed a bug\n\n## [1.0.0] - 2024-01-01\n\n- Initial release\n";
+            assert_eq!(release_notes(changelog, "1.0.1"), Some("- Fixed a bug"));
+            assert_eq!(release_notes(changelog, "1.0"), None);
+        }
+    }
+
+    mod fold_prereleases {
+        use super::*;
+
+        #[test]
+        fn with_links() {
+            let changelog = "\
+# Changelog
+
+## [Unreleased]
+
+## [1.0.0-rc.2] - 2024-02-01
+
+- Fixed a regression
+
+## [1.0.0-rc.1] - 2024-01-15
+
+- Added a feature
+
+## [0.9.0] - 2024-01-01
+
+- Initial release
+
+[Unreleased]: https://github.com/o/r/compare/v1.0.0-rc.2...HEAD
+[1.0.0-rc.2]: https://github.com/o/r/compare/v1.0.0-rc.1...v1.0.0-rc.2
+[1.0.0-rc.1]: https://github.com/o/r/compare/v0.9.0...v1.0.0-rc.1
+[0.9.0]: https://github.com/o/r/compare/v0.8.0...v0.9.0
+";
+            let expected = "\
+# Changelog
+
+## [Unreleased]
+
+- Fixed a regression
+
+- Added a feature
+
+## [0.9.0] - 2024-01-01
+
+- Initial release
+
+[Unreleased]: https://github.com/o/r/compare/v1.0.0-rc.2...HEAD
+[0.9.0]: https://github.com/o/r/compare/v0.8.0...v0.9.0
+";
+            let actual = fold_prereleases(changelog, "1.0.0").unwrap();
+            assert_eq!(actual, expected);
+        }
+    }
+}
@@ -9,6 +9,8 @@ fn do_call(
     command: impl IntoIterator<Item = impl Into<String>>,
     path: Option<&Path>,
     envs: Option<BTreeMap<&OsStr, &OsStr>>,
+    clear_env: bool,
+    timeout: Option<std::time::Duration>,
     dry_run: bool,
 ) -> CargoResult<bool> {
     let command: Vec<_> = command.into_iter().map(|s| s.into()).collect();
@@ -19,6 +21,24 @@ fn do_call(
         log::trace!("{}", command.join(" "));
         return Ok(true);
     }
+    let cmd_name = &command[0];
+    let mut cmd = build_command(&command, path, envs, clear_env);
+    if timeout.is_some() {
+        own_process_group(&mut cmd);
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| anyhow::format_err!("failed to launch `{cmd_name}`: {e}"))?;
+    wait(&mut child, cmd_name, timeout)
+}
+
+fn build_command(
+    command: &[String],
+    path: Option<&Path>,
+    envs: Option<BTreeMap<&OsStr, &OsStr>>,
+    clear_env: bool,
+) -> Command {
     let mut iter = command.iter();
     let cmd_name = iter.next().unwrap();
 
@@ -28,6 +48,10 @@ fn do_call(
         cmd.current_dir(p);
     }
 
+    if clear_env {
+        cmd.env_clear();
+    }
+
     if let Some(e) = envs {
         cmd.envs(e.iter());
     }
@@ -38,21 +62,134 @@ fn do_call(
         }
     }
 
-    let mut child = cmd
-        .spawn()
-        .map_err(|e| anyhow::format_err!("failed to launch `{cmd_name}`: {e}"))?;
-    let result = child
-        .wait()
-        .map_err(|e| anyhow::format_err!("failed to launch `{cmd_name}`: {e}"))?;
+    cmd
+}
+
+fn wait(
+    child: &mut std::process::Child,
+    cmd_name: &str,
+    timeout: Option<std::time::Duration>,
+) -> CargoResult<bool> {
+    let result = if let Some(timeout) = timeout {
+        #[cfg(unix)]
+        let _interrupt = ForwardInterrupt::new(child.id());
+        let start = std::time::Instant::now();
+        loop {
+            if let Some(result) = child
+                .try_wait()
+                .map_err(|e| anyhow::format_err!("failed to launch `{cmd_name}`: {e}"))?
+            {
+                break result;
+            }
+            if timeout <= start.elapsed() {
+                kill_process_group(child);
+                let _ = child.wait();
+                let _ = crate::ops::shell::warn(format!(
+                    "`{cmd_name}` timed out after {}s",
+                    timeout.as_secs()
+                ));
+                return Ok(false);
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+    } else {
+        child
+            .wait()
+            .map_err(|e| anyhow::format_err!("failed to launch `{cmd_name}`: {e}"))?
+    };
 
     Ok(result.success())
 }
 
+/// Start the command in a process group of its own, so [`kill_process_group`] also reaches
+/// anything it spawns
+///
+/// The terminal's Ctrl-C then no longer reaches it, see [`ForwardInterrupt`]
+fn own_process_group(cmd: &mut Command) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt as _;
+        cmd.process_group(0);
+    }
+    // Windows kills by process tree instead
+    #[cfg(not(unix))]
+    let _ = cmd;
+}
+
+/// Kill `child` along with any processes it started, see [`own_process_group`]
+fn kill_process_group(child: &mut std::process::Child) {
+    let pid = child.id().to_string();
+    #[cfg(unix)]
+    let killed = signal_process_group(&pid, "KILL");
+    #[cfg(windows)]
+    let killed = Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &pid])
+        .status();
+    #[cfg(not(any(unix, windows)))]
+    let killed: std::io::Result<std::process::ExitStatus> =
+        Err(std::io::ErrorKind::Unsupported.into());
+    if !killed.map(|s| s.success()).unwrap_or(false) {
+        log::debug!("failed to kill the process group of {pid}, killing just it");
+        let _ = child.kill();
+    }
+}
+
+#[cfg(unix)]
+fn signal_process_group(pid: &str, signal: &str) -> std::io::Result<std::process::ExitStatus> {
+    Command::new("kill")
+        .args([&format!("-{signal}"), "--", &format!("-{pid}")])
+        .status()
+}
+
+/// Process groups of the commands currently waited on, see [`ForwardInterrupt`]
+#[cfg(unix)]
+static PROCESS_GROUPS: std::sync::Mutex<Vec<u32>> = std::sync::Mutex::new(Vec::new());
+
+/// While alive, forward Ctrl-C to the process group of `pid` before exiting
+///
+/// The terminal only interrupts its foreground process group, which a command started with
+/// [`own_process_group`] left.
+#[cfg(unix)]
+struct ForwardInterrupt(u32);
+
+#[cfg(unix)]
+impl ForwardInterrupt {
+    fn new(pid: u32) -> Self {
+        static HANDLER: std::sync::Once = std::sync::Once::new();
+        HANDLER.call_once(|| {
+            let handler = ctrlc::set_handler(|| {
+                if let Ok(groups) = PROCESS_GROUPS.lock() {
+                    for pid in groups.iter() {
+                        let _ = signal_process_group(&pid.to_string(), "INT");
+                    }
+                }
+                std::process::exit(130);
+            });
+            if let Err(err) = handler {
+                log::debug!("cannot forward Ctrl-C to commands: {err}");
+            }
+        });
+        if let Ok(mut groups) = PROCESS_GROUPS.lock() {
+            groups.push(pid);
+        }
+        Self(pid)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for ForwardInterrupt {
+    fn drop(&mut self) {
+        if let Ok(mut groups) = PROCESS_GROUPS.lock() {
+            groups.retain(|pid| *pid != self.0);
+        }
+    }
+}
+
 pub fn call(
     command: impl IntoIterator<Item = impl Into<String>>,
     dry_run: bool,
 ) -> CargoResult<bool> {
-    do_call(command, None, None, dry_run)
+    do_call(command, None, None, false, None, dry_run)
 }
 
 pub fn call_on_path(
@@ -60,14 +197,71 @@ pub fn call_on_path(
     path: &Path,
     dry_run: bool,
 ) -> CargoResult<bool> {
-    do_call(command, Some(path), None, dry_run)
+    do_call(command, Some(path), None, false, None, dry_run)
 }
 
+/// Run `command` with `envs` added to (or, with `clear_env`, replacing) the environment
+///
+/// Exceeding `timeout` kills the command and is reported as a failure.
 pub fn call_with_env(
     command: impl IntoIterator<Item = impl Into<String>>,
     envs: BTreeMap<&OsStr, &OsStr>,
+    clear_env: bool,
+    timeout: Option<std::time::Duration>,
     path: &Path,
     dry_run: bool,
 ) -> CargoResult<bool> {
-    do_call(command, Some(path), Some(envs), dry_run)
+    do_call(command, Some(path), Some(envs), clear_env, timeout, dry_run)
+}
+
+/// The result of a command whose output was captured
+#[derive(Clone, Debug, Default)]
+pub struct Captured {
+    pub success: bool,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// Like [`call_with_env`] but collecting stdout and stderr instead of passing them through
+pub fn call_with_env_captured(
+    command: impl IntoIterator<Item = impl Into<String>>,
+    envs: BTreeMap<&OsStr, &OsStr>,
+    clear_env: bool,
+    timeout: Option<std::time::Duration>,
+    path: &Path,
+) -> CargoResult<Captured> {
+    let command: Vec<String> = command.into_iter().map(|s| s.into()).collect();
+    let cmd_name = &command[0];
+    let mut cmd = build_command(&command, Some(path), Some(envs), clear_env);
+    cmd.stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+    if timeout.is_some() {
+        own_process_group(&mut cmd);
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| anyhow::format_err!("failed to launch `{cmd_name}`: {e}"))?;
+    // Drain both pipes while waiting so a chatty command can't block on a full pipe
+    let stdout = child.stdout.take().map(read_in_background);
+    let stderr = child.stderr.take().map(read_in_background);
+    let success = wait(&mut child, cmd_name, timeout)?;
+    let stdout = stdout.and_then(|h| h.join().ok()).unwrap_or_default();
+    let stderr = stderr.and_then(|h| h.join().ok()).unwrap_or_default();
+
+    Ok(Captured {
+        success,
+        stdout,
+        stderr,
+    })
+}
+
+fn read_in_background(
+    mut pipe: impl std::io::Read + Send + 'static,
+) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        buffer
+    })
 }
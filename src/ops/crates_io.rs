@@ -0,0 +1,28 @@
+use tame_index::external::reqwest;
+
+use crate::error::CargoResult;
+
+const API_URL: &str = "https://crates.io/api/v1";
+
+pub struct CratesIoApi {
+    client: reqwest::Client,
+}
+
+impl CratesIoApi {
+    pub fn open() -> CargoResult<Self> {
+        // crates.io requires identifying ourselves, which every shared client does
+        let client = super::http::default_async_client()?;
+        Ok(Self { client })
+    }
+
+    /// Determines if the specified crate version is served by the crates.io API
+    pub async fn has_krate_version(&self, name: &str, version: &str) -> CargoResult<bool> {
+        let url = format!("{API_URL}/crates/{name}/{version}");
+        let res = self.client.get(url).send().await?;
+        match res.status() {
+            reqwest::StatusCode::OK => Ok(true),
+            reqwest::StatusCode::NOT_FOUND => Ok(false),
+            status => anyhow::bail!("unexpected response from crates.io for {name}: {status}"),
+        }
+    }
+}
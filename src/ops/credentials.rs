@@ -0,0 +1,384 @@
+//! Credentials for registries whose sparse index requires authentication
+//!
+//! Like cargo, a token comes from the registry's environment variable or its credential providers,
+//! defaulting to `cargo:token` (cargo's `credentials.toml`).  Failing that, `.netrc` (or `$NETRC`)
+//! is checked for the index's host.
+
+use std::io::{BufRead as _, Write as _};
+
+use crate::config::RegistryConfig;
+use crate::error::CargoResult;
+
+/// How to authenticate to an index
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IndexAuth {
+    /// Sent as-is in the `Authorization` header, like cargo does
+    Token(String),
+    /// HTTP basic auth, from `.netrc`
+    Basic { login: String, password: String },
+}
+
+/// Credentials for the index at `index_url` of `registry`, if any are configured
+pub fn index_auth(
+    registry: &str,
+    config: &RegistryConfig,
+    index_url: &str,
+) -> CargoResult<Option<IndexAuth>> {
+    if let Some(token_env) = config.token_env.as_deref() {
+        if let Ok(token) = std::env::var(token_env) {
+            return Ok(Some(IndexAuth::Token(token)));
+        }
+    }
+    let cargo_env = format!(
+        "CARGO_REGISTRIES_{}_TOKEN",
+        registry.to_uppercase().replace('-', "_")
+    );
+    if let Ok(token) = std::env::var(&cargo_env) {
+        return Ok(Some(IndexAuth::Token(token)));
+    }
+    let providers = if let Some(provider) = config.credential_provider.clone() {
+        vec![provider]
+    } else {
+        cargo_providers(registry)?
+    };
+    for provider in providers {
+        if let Some(token) = provider_token(&provider, registry, index_url)? {
+            return Ok(Some(IndexAuth::Token(token)));
+        }
+    }
+    if let Some(host) = host(index_url) {
+        let netrc = std::env::var_os("NETRC")
+            .map(std::path::PathBuf::from)
+            .or_else(|| dirs_next::home_dir().map(|home| home.join(".netrc")));
+        if let Some(netrc) = netrc {
+            match std::fs::read_to_string(&netrc) {
+                Ok(content) => {
+                    if let Some((login, password)) = netrc_login(&content, host) {
+                        return Ok(Some(IndexAuth::Basic { login, password }));
+                    }
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The credential providers cargo would try for `registry`, in order
+///
+/// From `registries.<name>.credential-provider`, else `registry.global-credential-providers`
+/// (the last one first), else just `cargo:token`.  Aliases from `credential-alias` are expanded.
+fn cargo_providers(registry: &str) -> CargoResult<Vec<Vec<String>>> {
+    let env_name = registry.to_uppercase().replace('-', "_");
+    let providers = if let Ok(provider) =
+        std::env::var(format!("CARGO_REGISTRIES_{env_name}_CREDENTIAL_PROVIDER"))
+    {
+        vec![provider_args(&toml::Value::String(provider))]
+    } else if let Some(provider) = cargo_config(&["registries", registry, "credential-provider"])? {
+        vec![provider_args(&provider)]
+    } else if let Ok(providers) = std::env::var("CARGO_REGISTRY_GLOBAL_CREDENTIAL_PROVIDERS") {
+        providers
+            .split_whitespace()
+            .rev()
+            .map(|p| vec![p.to_owned()])
+            .collect()
+    } else if let Some(toml::Value::Array(providers)) =
+        cargo_config(&["registry", "global-credential-providers"])?
+    {
+        providers.iter().rev().map(provider_args).collect()
+    } else {
+        vec![vec!["cargo:token".to_owned()]]
+    };
+
+    let mut expanded = Vec::with_capacity(providers.len());
+    for provider in providers {
+        let alias = if let Some(program) = provider.first() {
+            cargo_config(&["credential-alias", program])?
+        } else {
+            continue;
+        };
+        if let Some(alias) = alias {
+            let mut alias = provider_args(&alias);
+            alias.extend(provider.into_iter().skip(1));
+            expanded.push(alias);
+        } else {
+            expanded.push(provider);
+        }
+    }
+    Ok(expanded)
+}
+
+/// A provider and its arguments, written as a string or a list like cargo accepts
+fn provider_args(value: &toml::Value) -> Vec<String> {
+    match value {
+        toml::Value::String(s) => s.split_whitespace().map(ToOwned::to_owned).collect(),
+        toml::Value::Array(a) => a
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(ToOwned::to_owned)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `keys` from cargo's config files, the one nearest the current directory winning
+pub fn cargo_config(keys: &[&str]) -> CargoResult<Option<toml::Value>> {
+    for path in cargo_config_files(&std::env::current_dir()?) {
+        let content = std::fs::read_to_string(&path)?;
+        let config: toml::Table = toml::from_str(&content)?;
+        let mut value = None;
+        for (i, key) in keys.iter().enumerate() {
+            value = if i == 0 {
+                config.get(*key)
+            } else {
+                value.and_then(|v: &toml::Value| v.get(*key))
+            };
+        }
+        if let Some(value) = value {
+            return Ok(Some(value.clone()));
+        }
+    }
+    Ok(None)
+}
+
+/// The cargo config files that apply in `dir`, nearest first
+pub fn cargo_config_files(dir: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let mut dirs = dir
+        .ancestors()
+        .map(|dir| dir.join(".cargo"))
+        .collect::<Vec<_>>();
+    dirs.extend(cargo_home());
+    dirs.into_iter()
+        .filter_map(|dir| {
+            ["config.toml", "config"]
+                .into_iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.is_file())
+        })
+        .collect()
+}
+
+/// Get a token from `provider`, either one of cargo's built-in providers or a plugin
+fn provider_token(
+    provider: &[String],
+    registry: &str,
+    index_url: &str,
+) -> CargoResult<Option<String>> {
+    let (program, args) = if let Some((program, args)) = provider.split_first() {
+        (program, args)
+    } else {
+        return Ok(None);
+    };
+    match program.as_str() {
+        "cargo:token" => credentials_file_token(registry),
+        "cargo:token-from-stdout" => command_token(args, registry, index_url),
+        builtin if builtin.starts_with("cargo:") => {
+            log::debug!("skipping unsupported credential provider `{builtin}` for {registry}");
+            Ok(None)
+        }
+        _ => plugin_token(program, args, registry, index_url),
+    }
+}
+
+/// The token printed by `cargo:token-from-stdout`'s command
+fn command_token(args: &[String], registry: &str, index_url: &str) -> CargoResult<Option<String>> {
+    let (program, args) = if let Some((program, args)) = args.split_first() {
+        (program, args)
+    } else {
+        anyhow::bail!("`cargo:token-from-stdout` needs a command for {registry}");
+    };
+    let output = std::process::Command::new(program)
+        .args(args)
+        .env("CARGO_REGISTRY_NAME_OPT", registry)
+        .env("CARGO_REGISTRY_INDEX_URL", index_url)
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .map_err(|e| anyhow::format_err!("failed to launch `{program}`: {e}"))?;
+    if !output.status.success() {
+        anyhow::bail!("`{program}` failed to print a token for {registry}");
+    }
+    let token = String::from_utf8(output.stdout)?;
+    let token = token.lines().next().unwrap_or_default().trim();
+    Ok((!token.is_empty()).then(|| token.to_owned()))
+}
+
+/// Ask a cargo credential provider for a token, speaking version 1 of its protocol
+///
+/// Like cargo, the arguments only go in the request.
+fn plugin_token(
+    program: &str,
+    args: &[String],
+    registry: &str,
+    index_url: &str,
+) -> CargoResult<Option<String>> {
+    #[derive(serde::Deserialize)]
+    enum Response {
+        Ok { token: String },
+        Err { kind: String },
+    }
+
+    log::trace!("Asking `{program}` for a token for {registry}");
+    let mut child = std::process::Command::new(program)
+        .arg("--cargo-plugin")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            anyhow::format_err!("failed to launch credential provider `{program}`: {e}")
+        })?;
+
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = std::io::BufReader::new(child.stdout.take().unwrap());
+    // The provider introduces itself first with the protocol versions it speaks
+    let mut hello = String::new();
+    stdout.read_line(&mut hello)?;
+    let request = serde_json::json!({
+        "v": 1,
+        "registry": { "index-url": index_url, "name": registry },
+        "kind": "get",
+        "operation": "read",
+        "args": args,
+    });
+    writeln!(stdin, "{request}")?;
+    stdin.flush()?;
+    let mut response = String::new();
+    stdout.read_line(&mut response)?;
+    drop(stdin);
+    child.wait()?;
+
+    match serde_json::from_str::<Response>(&response)? {
+        Response::Ok { token } => Ok(Some(token)),
+        Response::Err { kind } if kind == "not-found" || kind == "url-not-supported" => Ok(None),
+        Response::Err { kind } => {
+            anyhow::bail!("credential provider `{program}` failed for {registry}: {kind}")
+        }
+    }
+}
+
+/// The token `cargo login` saved for `registry`
+fn credentials_file_token(registry: &str) -> CargoResult<Option<String>> {
+    let cargo_home = if let Some(cargo_home) = cargo_home() {
+        cargo_home
+    } else {
+        return Ok(None);
+    };
+    for name in ["credentials.toml", "credentials"] {
+        let content = match std::fs::read_to_string(cargo_home.join(name)) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        let credentials: toml::Table = toml::from_str(&content)?;
+        let token = credentials
+            .get("registries")
+            .and_then(|r| r.get(registry))
+            .and_then(|r| r.get("token"))
+            .and_then(|t| t.as_str());
+        return Ok(token.map(ToOwned::to_owned));
+    }
+    Ok(None)
+}
+
+fn cargo_home() -> Option<std::path::PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .map(std::path::PathBuf::from)
+        .or_else(|| dirs_next::home_dir().map(|home| home.join(".cargo")))
+}
+
+/// The host of an index URL, like `sparse+https://host:port/path`
+fn host(index_url: &str) -> Option<&str> {
+    let url = index_url.strip_prefix("sparse+").unwrap_or(index_url);
+    let (_, rest) = url.split_once("://")?;
+    let authority = rest.split('/').next()?;
+    let authority = authority
+        .rsplit_once('@')
+        .map(|(_, a)| a)
+        .unwrap_or(authority);
+    authority.split(':').next().filter(|h| !h.is_empty())
+}
+
+/// The login and password `.netrc` has for `host`, falling back to its `default` entry
+fn netrc_login(content: &str, host: &str) -> Option<(String, String)> {
+    // Machine, login, and password, with no machine for the `default` entry
+    let mut entries: Vec<(Option<&str>, Option<&str>, Option<&str>)> = Vec::new();
+    let mut tokens = content.split_whitespace();
+    while let Some(token) = tokens.next() {
+        match token {
+            "machine" => entries.push((Some(tokens.next().unwrap_or_default()), None, None)),
+            "default" => entries.push((None, None, None)),
+            "login" => {
+                if let Some(entry) = entries.last_mut() {
+                    entry.1 = tokens.next();
+                }
+            }
+            "password" => {
+                if let Some(entry) = entries.last_mut() {
+                    entry.2 = tokens.next();
+                }
+            }
+            "account" => {
+                tokens.next();
+            }
+            // Macro bodies aren't tokens, so stop rather than misread them
+            "macdef" => break,
+            _ => {}
+        }
+    }
+    entries
+        .iter()
+        .find(|(machine, ..)| *machine == Some(host))
+        .or_else(|| entries.iter().find(|(machine, ..)| machine.is_none()))
+        .and_then(|(_, login, password)| Some(((*login)?.to_owned(), (*password)?.to_owned())))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn index_host() {
+        assert_eq!(
+            host("sparse+https://index.example.com/crates/"),
+            Some("index.example.com")
+        );
+        assert_eq!(
+            host("sparse+https://user@example.com:8443/"),
+            Some("example.com")
+        );
+        assert_eq!(host("example.com"), None);
+    }
+
+    #[test]
+    fn provider_strings() {
+        assert_eq!(
+            provider_args(&toml::Value::String("my-provider --flag".to_owned())),
+            vec!["my-provider", "--flag"]
+        );
+        assert_eq!(
+            provider_args(&toml::Value::Array(vec![toml::Value::String(
+                "/path with space/provider".to_owned()
+            )])),
+            vec!["/path with space/provider"]
+        );
+    }
+
+    #[test]
+    fn netrc() {
+        let content = "machine other.com login a password b\n\
+                       machine example.com\n  login user\n  password secret\n\
+                       default login anon password guest\n";
+        assert_eq!(
+            netrc_login(content, "example.com"),
+            Some(("user".to_owned(), "secret".to_owned()))
+        );
+        assert_eq!(
+            netrc_login(content, "unknown.com"),
+            Some(("anon".to_owned(), "guest".to_owned()))
+        );
+        assert_eq!(
+            netrc_login("machine other.com login a password b", "x"),
+            None
+        );
+    }
+}
@@ -0,0 +1,50 @@
+use tame_index::external::reqwest;
+
+use crate::error::CargoResult;
+
+const URL: &str = "https://docs.rs";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BuildStatus {
+    /// Queued or in-progress
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+pub struct DocsRs {
+    client: reqwest::Client,
+}
+
+impl DocsRs {
+    pub fn open() -> CargoResult<Self> {
+        let client = super::http::default_async_client()?;
+        Ok(Self { client })
+    }
+
+    pub async fn build_status(&self, name: &str, version: &str) -> CargoResult<BuildStatus> {
+        #[derive(serde::Deserialize)]
+        struct Status {
+            doc_status: bool,
+        }
+
+        let url = format!("{URL}/crate/{name}/{version}/status.json");
+        let res = self.client.get(url).send().await?;
+        match res.status() {
+            reqwest::StatusCode::OK => {
+                let body = res.bytes().await?;
+                let status: Status = serde_json::from_slice(&body).map_err(|e| {
+                    anyhow::format_err!("unrecognized response from docs.rs for {name}: {e}")
+                })?;
+                if status.doc_status {
+                    Ok(BuildStatus::Succeeded)
+                } else {
+                    Ok(BuildStatus::Failed)
+                }
+            }
+            // docs.rs doesn't know about the release until its build is done
+            reqwest::StatusCode::NOT_FOUND => Ok(BuildStatus::Pending),
+            status => anyhow::bail!("unexpected response from docs.rs for {name}: {status}"),
+        }
+    }
+}
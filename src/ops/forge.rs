@@ -0,0 +1,282 @@
+//! Queries against the forge hosting the repository
+//!
+//! Only GitHub is supported.  Set `GITHUB_TOKEN` (or `GH_TOKEN`) to see private repositories and
+//! to avoid the anonymous rate limit.
+
+use tame_index::external::reqwest;
+
+use crate::error::CargoResult;
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const GITHUB_UPLOADS_URL: &str = "https://uploads.github.com";
+
+/// The web URL of the repository behind a git remote URL
+///
+/// Supports `https://host/owner/repo.git`, `ssh://git@host/owner/repo.git`, and
+/// `git@host:owner/repo.git`.
+pub fn repository_url(remote_url: &str) -> Option<String> {
+    let remote_url = remote_url.trim();
+    let (host, path) = if let Some((_, rest)) = remote_url.split_once("://") {
+        let rest = rest.split_once('@').map(|(_, r)| r).unwrap_or(rest);
+        let (host, path) = rest.split_once('/')?;
+        // Drop any port, it is for the git transport rather than the web UI
+        (host.split(':').next().unwrap_or(host), path)
+    } else {
+        let rest = remote_url
+            .split_once('@')
+            .map(|(_, r)| r)
+            .unwrap_or(remote_url);
+        rest.split_once(':')?
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if host.is_empty() || path.is_empty() || path.contains('\\') {
+        return None;
+    }
+    Some(format!("https://{host}/{path}"))
+}
+
+/// Why direct pushes to `branch` would be rejected, if the forge is supported
+///
+/// An empty list means pushing is allowed.
+pub fn push_restrictions(repository_url: &str, branch: &str) -> CargoResult<Option<Vec<String>>> {
+    #[derive(serde::Deserialize)]
+    struct Protection {
+        required_pull_request_reviews: Option<serde_json::Value>,
+        restrictions: Option<serde_json::Value>,
+        required_status_checks: Option<serde_json::Value>,
+        lock_branch: Option<Enabled>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Enabled {
+        enabled: bool,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Rule {
+        #[serde(rename = "type")]
+        kind: String,
+    }
+
+    let repo = if let Some(repo) = repository_url.strip_prefix("https://github.com/") {
+        repo
+    } else {
+        log::debug!("not checking branch protection, {repository_url} isn't on GitHub");
+        return Ok(None);
+    };
+
+    let client = super::http::default_client()?;
+    let get = |url: String| {
+        let mut req = client
+            .get(url)
+            .header("Accept", "application/vnd.github+json");
+        if let Some(token) = github_token() {
+            req = req.bearer_auth(token);
+        }
+        req.send()
+    };
+
+    let mut restrictions = Vec::new();
+
+    // Protection only blocks pushes through its rules, not by being enabled
+    let res = get(format!(
+        "{GITHUB_API_URL}/repos/{repo}/branches/{branch}/protection"
+    ))?;
+    match res.status() {
+        reqwest::StatusCode::OK => {
+            let protection: Protection = serde_json::from_slice(&res.bytes()?)?;
+            if protection.required_pull_request_reviews.is_some() {
+                restrictions.push("branch protection requires pull request reviews".to_owned());
+            }
+            if protection.restrictions.is_some() {
+                restrictions.push("branch protection restricts who can push".to_owned());
+            }
+            if protection.required_status_checks.is_some() {
+                restrictions.push("branch protection requires status checks".to_owned());
+            }
+            if protection.lock_branch.map(|l| l.enabled).unwrap_or(false) {
+                restrictions.push("branch protection locks the branch".to_owned());
+            }
+        }
+        // Unprotected or unpushed branches
+        reqwest::StatusCode::NOT_FOUND => {}
+        // Reading protection takes admin rights; rulesets are still visible
+        reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+            log::debug!("not allowed to read branch protection for {repo}");
+        }
+        status => anyhow::bail!("unexpected response from GitHub for {repo}: {status}"),
+    }
+
+    let res = get(format!(
+        "{GITHUB_API_URL}/repos/{repo}/rules/branches/{branch}"
+    ))?;
+    match res.status() {
+        reqwest::StatusCode::OK => {
+            let rules: Vec<Rule> = serde_json::from_slice(&res.bytes()?)?;
+            for rule in rules {
+                let reason = match rule.kind.as_str() {
+                    "pull_request" => "a ruleset requires pull requests",
+                    "update" => "a ruleset restricts updates",
+                    "required_status_checks" => "a ruleset requires status checks",
+                    _ => continue,
+                };
+                if !restrictions.iter().any(|r| r == reason) {
+                    restrictions.push(reason.to_owned());
+                }
+            }
+        }
+        reqwest::StatusCode::NOT_FOUND => {}
+        status => anyhow::bail!("unexpected response from GitHub for {repo}: {status}"),
+    }
+
+    Ok(Some(restrictions))
+}
+
+/// Delete the forge release for `tag`
+///
+/// Returns `None` when the forge isn't supported and `Some(false)` when there is no such release.
+pub fn delete_release(repository_url: &str, tag: &str, dry_run: bool) -> CargoResult<Option<bool>> {
+    let repo = if let Some(repo) = repository_url.strip_prefix("https://github.com/") {
+        repo
+    } else {
+        log::debug!("not deleting forge release, {repository_url} isn't on GitHub");
+        return Ok(None);
+    };
+    let token = github_token();
+
+    let client = super::http::default_client()?;
+    let release = if let Some(release) = release_id(&client, repo, tag, token.as_deref())? {
+        release
+    } else {
+        return Ok(Some(false));
+    };
+    if dry_run {
+        return Ok(Some(true));
+    }
+
+    let token = token.ok_or_else(|| {
+        anyhow::format_err!("set `GITHUB_TOKEN` to delete the GitHub release for {tag}")
+    })?;
+    let res = client
+        .delete(format!("{GITHUB_API_URL}/repos/{repo}/releases/{release}"))
+        .header("Accept", "application/vnd.github+json")
+        .bearer_auth(token)
+        .send()?;
+    match res.status() {
+        reqwest::StatusCode::NO_CONTENT => Ok(Some(true)),
+        status => anyhow::bail!("failed to delete the GitHub release for {tag}: {status}"),
+    }
+}
+
+/// Attach `files` to the forge release for `tag`
+///
+/// Returns `None` when the forge isn't supported and `Some(false)` when there is no such release.
+pub fn upload_release_assets(
+    repository_url: &str,
+    tag: &str,
+    files: &[std::path::PathBuf],
+    dry_run: bool,
+) -> CargoResult<Option<bool>> {
+    let repo = if let Some(repo) = repository_url.strip_prefix("https://github.com/") {
+        repo
+    } else {
+        log::debug!("not uploading release assets, {repository_url} isn't on GitHub");
+        return Ok(None);
+    };
+    let token = github_token();
+
+    let client = super::http::default_client()?;
+    let release = if let Some(release) = release_id(&client, repo, tag, token.as_deref())? {
+        release
+    } else {
+        return Ok(Some(false));
+    };
+    if dry_run {
+        return Ok(Some(true));
+    }
+
+    let token = token.ok_or_else(|| {
+        anyhow::format_err!("set `GITHUB_TOKEN` to attach assets to the GitHub release for {tag}")
+    })?;
+    for file in files {
+        let name = file
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| anyhow::format_err!("invalid asset name {}", file.display()))?;
+        let res = client
+            .post(format!(
+                "{GITHUB_UPLOADS_URL}/repos/{repo}/releases/{release}/assets"
+            ))
+            .query(&[("name", name)])
+            .header("Accept", "application/vnd.github+json")
+            .header("Content-Type", "application/octet-stream")
+            .bearer_auth(&token)
+            .body(std::fs::read(file)?)
+            .send()?;
+        match res.status() {
+            reqwest::StatusCode::CREATED => {}
+            status => {
+                anyhow::bail!("failed to attach {name} to the GitHub release for {tag}: {status}")
+            }
+        }
+    }
+    Ok(Some(true))
+}
+
+/// The id of the GitHub release for `tag`, if there is one
+fn release_id(
+    client: &reqwest::blocking::Client,
+    repo: &str,
+    tag: &str,
+    token: Option<&str>,
+) -> CargoResult<Option<u64>> {
+    #[derive(serde::Deserialize)]
+    struct Release {
+        id: u64,
+    }
+
+    let mut req = client
+        .get(format!("{GITHUB_API_URL}/repos/{repo}/releases/tags/{tag}"))
+        .header("Accept", "application/vnd.github+json");
+    if let Some(token) = token {
+        req = req.bearer_auth(token);
+    }
+    let res = req.send()?;
+    match res.status() {
+        reqwest::StatusCode::OK => {
+            let release: Release = serde_json::from_slice(&res.bytes()?)?;
+            Ok(Some(release.id))
+        }
+        reqwest::StatusCode::NOT_FOUND => Ok(None),
+        status => anyhow::bail!("unexpected response from GitHub for {repo}: {status}"),
+    }
+}
+
+fn github_token() -> Option<String> {
+    std::env::var("GITHUB_TOKEN")
+        .ok()
+        .or_else(|| std::env::var("GH_TOKEN").ok())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn repository_urls() {
+        for remote in [
+            "https://github.com/o/r.git",
+            "ssh://git@github.com:22/o/r.git",
+            "git@github.com:o/r.git",
+            "git@github.com:o/r",
+        ] {
+            assert_eq!(
+                repository_url(remote).as_deref(),
+                Some("https://github.com/o/r"),
+                "{remote}"
+            );
+        }
+        assert_eq!(repository_url("/tmp/rehearsal.git"), None);
+    }
+}
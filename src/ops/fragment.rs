@@ -0,0 +1,188 @@
+//! Changelog fragments: one file per change, assembled into the changelog when releasing
+//!
+//! Fragments are named `<id>.<type>.md`, where `<id>` is an issue number, a slug, or both as
+//! `<issue>-<slug>`, and `<type>` is a [Keep a Changelog](https://keepachangelog.com/) category.
+//! A slug on its own that starts with a digit is written as `_<slug>` so it isn't read as an issue.
+
+use std::path::{Path, PathBuf};
+
+use crate::error::CargoResult;
+
+/// Fragment types, in the order their sections are rendered
+pub const TYPES: &[&str] = &[
+    "added",
+    "changed",
+    "deprecated",
+    "removed",
+    "fixed",
+    "security",
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fragment {
+    pub path: PathBuf,
+    pub issue: Option<String>,
+    pub kind: &'static str,
+    pub text: String,
+}
+
+/// The issue number and type of the fragment named `name`
+pub fn parse_name(name: &str) -> Option<(Option<&str>, &'static str)> {
+    let stem = name.strip_suffix(".md")?;
+    let (id, kind) = stem.rsplit_once('.')?;
+    let kind = *TYPES.iter().find(|t| **t == kind)?;
+    if id.is_empty() || id.contains('.') {
+        return None;
+    }
+    let issue = id
+        .split('-')
+        .next()
+        .filter(|issue| !issue.is_empty() && issue.bytes().all(|b| b.is_ascii_digit()));
+    Some((issue, kind))
+}
+
+/// The file name for a fragment, the inverse of [`parse_name`]
+pub fn file_name(issue: Option<u64>, slug: Option<&str>, kind: &str) -> String {
+    let id = match (issue, slug) {
+        (Some(issue), Some(slug)) => format!("{issue}-{slug}"),
+        (Some(issue), None) => issue.to_string(),
+        (None, Some(slug)) if slug.starts_with(|c: char| c.is_ascii_digit()) => format!("_{slug}"),
+        (None, Some(slug)) => slug.to_owned(),
+        (None, None) => "change".to_owned(),
+    };
+    format!("{id}.{kind}.md")
+}
+
+/// `text` lowercased with runs of anything but letters and digits replaced by `-`
+pub fn slugify(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// The fragments in `dir`, sorted by file name
+///
+/// Hidden files, like `.gitkeep`, are skipped.  Any other file not named like a fragment is an
+/// error so typos don't silently drop a change from the changelog.
+pub fn load(dir: &Path) -> CargoResult<Vec<Fragment>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut fragments = Vec::new();
+    let mut invalid = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') {
+            continue;
+        }
+        if let Some((issue, kind)) = parse_name(&name) {
+            let text = std::fs::read_to_string(entry.path())?;
+            fragments.push(Fragment {
+                path: entry.path(),
+                issue: issue.map(ToOwned::to_owned),
+                kind,
+                text: text.trim().to_owned(),
+            });
+        } else {
+            invalid.push(name.into_owned());
+        }
+    }
+    if !invalid.is_empty() {
+        invalid.sort();
+        anyhow::bail!(
+            "{} has files not named `<id>.<type>.md`, with `<type>` one of {}: {}",
+            dir.display(),
+            TYPES.join(", "),
+            invalid.join(", ")
+        );
+    }
+
+    fragments.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(fragments)
+}
+
+/// `fragments` as Markdown, under a `### <Type>` heading per type
+pub fn render(fragments: &[Fragment]) -> String {
+    TYPES
+        .iter()
+        .filter_map(|kind| {
+            let items = fragments
+                .iter()
+                .filter(|f| f.kind == *kind)
+                .map(|f| {
+                    let text = f.text.strip_prefix("- ").unwrap_or(&f.text);
+                    let text = text.replace('\n', "\n  ");
+                    match f.issue.as_deref() {
+                        Some(issue) => format!("- {text} (#{issue})"),
+                        None => format!("- {text}"),
+                    }
+                })
+                .collect::<Vec<_>>();
+            if items.is_empty() {
+                return None;
+            }
+            let mut heading = kind.to_string();
+            heading[..1].make_ascii_uppercase();
+            Some(format!("### {heading}\n\n{}", items.join("\n")))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn names() {
+        assert_eq!(parse_name("123.fixed.md"), Some((Some("123"), "fixed")));
+        assert_eq!(
+            parse_name("123-fix-crash.fixed.md"),
+            Some((Some("123"), "fixed"))
+        );
+        assert_eq!(parse_name("new-flag.added.md"), Some((None, "added")));
+        assert_eq!(parse_name("123.fix.md"), None);
+        assert_eq!(parse_name("fixed.md"), None);
+        assert_eq!(parse_name("123.fixed.txt"), None);
+
+        let name = file_name(Some(123), Some(&slugify("Fix crash!")), "fixed");
+        assert_eq!(name, "123-fix-crash.fixed.md");
+        assert_eq!(parse_name(&name), Some((Some("123"), "fixed")));
+
+        let name = file_name(None, Some(&slugify("2 bugs fixed")), "fixed");
+        assert_eq!(name, "_2-bugs-fixed.fixed.md");
+        assert_eq!(parse_name(&name), Some((None, "fixed")));
+    }
+
+    #[test]
+    fn grouped_by_type() {
+        let fragment = |name: &str, text: &str| {
+            let (issue, kind) = parse_name(name).unwrap();
+            Fragment {
+                path: PathBuf::from(name),
+                issue: issue.map(ToOwned::to_owned),
+                kind,
+                text: text.to_owned(),
+            }
+        };
+        let fragments = [
+            fragment("12.fixed.md", "Don't crash on empty input"),
+            fragment("flag.added.md", "- Add `--flag`"),
+            fragment("10.added.md", "Support TOML"),
+        ];
+        assert_eq!(
+            render(&fragments),
+            "### Added\n\n- Add `--flag`\n- Support TOML (#10)\n\n### Fixed\n\n- Don't crash on empty input (#12)"
+        );
+    }
+}
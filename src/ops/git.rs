@@ -5,10 +5,132 @@ use std::process::Command;
 use bstr::ByteSlice;
 
 use crate::error::CargoResult;
-use crate::ops::cmd::call_on_path;
+use crate::ops::cmd::call_with_env;
+
+/// How to run `git`, from `git-path` and `git-env`
+#[derive(Clone, Debug, Default)]
+pub struct GitOptions {
+    program: Option<String>,
+    envs: std::collections::BTreeMap<String, String>,
+}
+
+impl GitOptions {
+    /// Run `program` in place of `git`, with `envs` added to its environment
+    pub fn new(program: Option<&str>, envs: &std::collections::BTreeMap<String, String>) -> Self {
+        Self {
+            program: program.map(ToOwned::to_owned),
+            envs: envs.clone(),
+        }
+    }
+
+    /// The options from the workspace config, see [`set_options`]
+    pub fn workspace() -> Self {
+        OPTIONS.lock().unwrap().clone()
+    }
+
+    /// Whether `git` is replaced or run with a changed environment
+    fn is_customized(&self) -> bool {
+        self.program.is_some() || !self.envs.is_empty()
+    }
+}
+
+static OPTIONS: once_cell::sync::Lazy<std::sync::Mutex<GitOptions>> =
+    once_cell::sync::Lazy::new(Default::default);
+
+/// Run `program` in place of `git`, with `envs` added to its environment
+pub fn set_options(program: Option<&str>, envs: &std::collections::BTreeMap<String, String>) {
+    *OPTIONS.lock().unwrap() = GitOptions::new(program, envs);
+}
+
+fn git() -> Command {
+    let options = GitOptions::workspace();
+    let mut cmd = Command::new(options.program.as_deref().unwrap_or("git"));
+    cmd.envs(absolute_git_env());
+    cmd.envs(&options.envs);
+    cmd
+}
+
+/// Run `git` with `options` outside of the project's repository, like to clone
+///
+/// `GIT_DIR` and `GIT_WORK_TREE` are meant for the project, so they are left out.
+pub fn call_git_outside(
+    options: &GitOptions,
+    args: impl IntoIterator<Item = impl AsRef<std::ffi::OsStr>>,
+) -> CargoResult<bool> {
+    let program = options.program.as_deref().unwrap_or("git");
+    let mut cmd = Command::new(program);
+    cmd.args(args)
+        .env_remove("GIT_DIR")
+        .env_remove("GIT_WORK_TREE")
+        .envs(&options.envs);
+    log::trace!("{cmd:?}");
+    let status = cmd
+        .status()
+        .map_err(|e| anyhow::format_err!("failed to launch `{program}`: {e}"))?;
+    Ok(status.success())
+}
+
+/// Open the repository containing `dir`, respecting `GIT_DIR` and `GIT_WORK_TREE` like `git` does
+///
+/// Worktrees, whose `.git` is a file pointing into the main repository, are found as usual.
+pub fn open(dir: &Path) -> Result<git2::Repository, git2::Error> {
+    git2::Repository::open_ext(
+        dir,
+        git2::RepositoryOpenFlags::FROM_ENV,
+        std::iter::empty::<&std::ffi::OsStr>(),
+    )
+}
+
+/// Whether the repository is selected through `GIT_DIR` rather than found from the working directory
+pub fn is_env_selected() -> bool {
+    std::env::var_os("GIT_DIR").is_some()
+}
+
+/// `GIT_DIR` and `GIT_WORK_TREE` made absolute, as `git` resolves them from the directory it runs in
+fn absolute_git_env() -> Vec<(&'static str, std::ffi::OsString)> {
+    let cwd = if let Ok(cwd) = std::env::current_dir() {
+        cwd
+    } else {
+        return Vec::new();
+    };
+    ["GIT_DIR", "GIT_WORK_TREE"]
+        .into_iter()
+        .filter_map(|name| {
+            let value = PathBuf::from(std::env::var_os(name)?);
+            value
+                .is_relative()
+                .then(|| (name, cwd.join(value).into_os_string()))
+        })
+        .collect()
+}
+
+/// Like [`call_with_env`] for a `git` command, running `git` as configured by [`set_options`]
+fn call_git(
+    command: impl IntoIterator<Item = impl Into<String>>,
+    dir: &Path,
+    dry_run: bool,
+) -> CargoResult<bool> {
+    let options = GitOptions::workspace();
+    let mut command = command.into_iter().map(Into::into).collect::<Vec<String>>();
+    if let Some(program) = options.program {
+        command[0] = program;
+    }
+    let absolute = absolute_git_env();
+    let envs = absolute
+        .iter()
+        .map(|(key, value)| (std::ffi::OsStr::new(key), value.as_os_str()))
+        .chain(
+            options
+                .envs
+                .iter()
+                .map(|(key, value)| (std::ffi::OsStr::new(key), std::ffi::OsStr::new(value))),
+        )
+        .collect();
+    call_with_env(command, envs, false, None, dir, dry_run)
+}
 
 pub fn fetch(dir: &Path, remote: &str, branch: &str) -> CargoResult<()> {
-    Command::new("git")
+    git()
         .arg("fetch")
         .arg(remote)
         .arg(branch)
@@ -19,7 +141,7 @@ pub fn fetch(dir: &Path, remote: &str, branch: &str) -> CargoResult<()> {
 }
 
 pub fn is_behind_remote(dir: &Path, remote: &str, branch: &str) -> CargoResult<bool> {
-    let repo = git2::Repository::discover(dir)?;
+    let repo = open(dir)?;
 
     let branch_id = repo.revparse_single(branch)?.id();
 
@@ -47,7 +169,7 @@ pub fn is_behind_remote(dir: &Path, remote: &str, branch: &str) -> CargoResult<b
 }
 
 pub fn is_local_unchanged(dir: &Path, remote: &str, branch: &str) -> CargoResult<bool> {
-    let repo = git2::Repository::discover(dir)?;
+    let repo = open(dir)?;
 
     let branch_id = repo.revparse_single(branch)?.id();
 
@@ -75,7 +197,7 @@ pub fn is_local_unchanged(dir: &Path, remote: &str, branch: &str) -> CargoResult
 }
 
 pub fn current_branch(dir: &Path) -> CargoResult<String> {
-    let repo = git2::Repository::discover(dir)?;
+    let repo = open(dir)?;
 
     let resolved = repo.head()?.resolve()?;
     let name = resolved.shorthand().unwrap_or("HEAD");
@@ -83,7 +205,16 @@ pub fn current_branch(dir: &Path) -> CargoResult<String> {
 }
 
 pub fn is_dirty(dir: &Path) -> CargoResult<Option<Vec<String>>> {
-    let repo = git2::Repository::discover(dir)?;
+    dirty_entries(dir, false)
+}
+
+/// Like [`is_dirty`], leaving submodules to [`submodule_problems`]
+pub fn is_dirty_except_submodules(dir: &Path) -> CargoResult<Option<Vec<String>>> {
+    dirty_entries(dir, true)
+}
+
+fn dirty_entries(dir: &Path, exclude_submodules: bool) -> CargoResult<Option<Vec<String>>> {
+    let repo = open(dir)?;
 
     let mut entries = Vec::new();
 
@@ -96,7 +227,8 @@ pub fn is_dirty(dir: &Path) -> CargoResult<Option<Vec<String>>> {
     let mut options = git2::StatusOptions::new();
     options
         .show(git2::StatusShow::IndexAndWorkdir)
-        .include_untracked(true);
+        .include_untracked(true)
+        .exclude_submodules(exclude_submodules);
     let statuses = repo.statuses(Some(&mut options))?;
     let dirty_tree = !statuses.is_empty();
     if dirty_tree {
@@ -116,7 +248,7 @@ pub fn is_dirty(dir: &Path) -> CargoResult<Option<Vec<String>>> {
 pub fn changed_files(dir: &Path, tag: &str) -> CargoResult<Option<Vec<PathBuf>>> {
     let root = top_level(dir)?;
 
-    let output = Command::new("git")
+    let output = git()
         .arg("diff")
         .arg(&format!("{}..HEAD", tag))
         .arg("--name-only")
@@ -139,18 +271,110 @@ pub fn changed_files(dir: &Path, tag: &str) -> CargoResult<Option<Vec<PathBuf>>>
     }
 }
 
-pub fn commit_all(dir: &Path, msg: &str, sign: bool, dry_run: bool) -> CargoResult<bool> {
-    let repo = git2::Repository::discover(dir)?;
+pub fn commits_since(dir: &Path, tag: &str) -> CargoResult<Option<usize>> {
+    let output = git()
+        .arg("rev-list")
+        .arg("--count")
+        .arg(&format!("{}..HEAD", tag))
+        .arg("--")
+        .arg(".")
+        .current_dir(dir)
+        .output()?;
+    if !output.status.success() {
+        // For cases like non-existent tag
+        return Ok(None);
+    }
+    let count = String::from_utf8_lossy(&output.stdout).trim().parse().ok();
+    Ok(count)
+}
+
+/// `git shortlog` of the commits touching `dir`, since `since` if set
+pub fn shortlog(dir: &Path, since: Option<&str>) -> CargoResult<String> {
+    let range = since
+        .map(|since| format!("{}..HEAD", since))
+        .unwrap_or_else(|| "HEAD".to_owned());
+    let output = git()
+        .arg("shortlog")
+        // Without a revision, `git shortlog` reads from stdin
+        .arg(&range)
+        .arg("--")
+        .arg(".")
+        .current_dir(dir)
+        .output()
+        .map_err(|_| anyhow::format_err!("`git` not found"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to summarize commits for {}: {}",
+            range,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_owned())
+}
+
+/// Names and emails of the authors of commits touching `dir`, since `since` if set
+///
+/// Authors are listed once, in order of their first contribution.
+pub fn authors(dir: &Path, since: Option<&str>) -> CargoResult<Vec<(String, String)>> {
+    let range = since
+        .map(|since| format!("{}..HEAD", since))
+        .unwrap_or_else(|| "HEAD".to_owned());
+    let output = git()
+        .arg("log")
+        .arg("--reverse")
+        .arg("--format=%aN%x09%aE")
+        .arg(&range)
+        .arg("--")
+        .arg(".")
+        .current_dir(dir)
+        .output()
+        .map_err(|_| anyhow::format_err!("`git` not found"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "failed to list authors for {}: {}",
+            range,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let mut authors: Vec<(String, String)> = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Some((name, email)) = line.split_once('\t') {
+            if !authors.iter().any(|(n, e)| n == name || e == email) {
+                authors.push((name.to_owned(), email.to_owned()));
+            }
+        }
+    }
+    Ok(authors)
+}
+
+/// Commit all changes, skipping the `pre-commit` and `commit-msg` hooks unless `verify`
+pub fn commit_all(
+    dir: &Path,
+    msg: &str,
+    sign: bool,
+    verify: bool,
+    dry_run: bool,
+) -> CargoResult<bool> {
+    let repo = open(dir)?;
     let mut options = git2::StatusOptions::new();
     options
         .show(git2::StatusShow::IndexAndWorkdir)
-        .include_untracked(true);
+        .include_untracked(true)
+        .exclude_submodules(true);
     let statuses = repo.statuses(Some(&mut options))?;
     let dirty_tree = !statuses.is_empty();
 
     if dirty_tree || dry_run {
-        call_on_path(
-            vec!["git", "commit", if sign { "-S" } else { "" }, "-am", msg],
+        commit_tracked(
+            &repo,
+            vec![
+                if sign { "-S" } else { "" },
+                if verify { "" } else { "--no-verify" },
+                "-m",
+                msg,
+            ],
             dir,
             dry_run,
         )
@@ -160,6 +384,99 @@ pub fn commit_all(dir: &Path, msg: &str, sign: bool, dry_run: bool) -> CargoResu
     }
 }
 
+/// Fold all changes into the `HEAD` commit, keeping its message
+pub fn amend_all(dir: &Path, sign: bool, verify: bool, dry_run: bool) -> CargoResult<bool> {
+    let repo = open(dir)?;
+    commit_tracked(
+        &repo,
+        vec![
+            if sign { "-S" } else { "" },
+            if verify { "" } else { "--no-verify" },
+            "--amend",
+            "--no-edit",
+        ],
+        dir,
+        dry_run,
+    )
+}
+
+/// `git commit -a`, except submodule pointers are left at what's already recorded
+fn commit_tracked(
+    repo: &git2::Repository,
+    args: Vec<&str>,
+    dir: &Path,
+    dry_run: bool,
+) -> CargoResult<bool> {
+    let moved = moved_submodules(repo)?;
+    if moved.is_empty() {
+        let mut command = vec!["git", "commit", "-a"];
+        command.extend(args);
+        return call_git(command, dir, dry_run);
+    }
+
+    let _ = crate::ops::shell::note(format!(
+        "leaving submodule pointers unchanged: {}",
+        moved.join(", ")
+    ));
+    let excludes = moved
+        .iter()
+        .map(|path| format!(":(top,exclude){path}"))
+        .collect::<Vec<_>>();
+    let mut add = vec!["git", "add", "--update", "--", ":/"];
+    add.extend(excludes.iter().map(|s| s.as_str()));
+    if !call_git(add, dir, dry_run)? {
+        return Ok(false);
+    }
+    let mut command = vec!["git", "commit"];
+    command.extend(args);
+    call_git(command, dir, dry_run)
+}
+
+/// Submodules checked out at a different commit than the index records
+fn moved_submodules(repo: &git2::Repository) -> CargoResult<Vec<String>> {
+    let mut moved = Vec::new();
+    for submodule in repo.submodules()? {
+        let name = if let Some(name) = submodule.name() {
+            name
+        } else {
+            continue;
+        };
+        let status = repo.submodule_status(name, git2::SubmoduleIgnore::None)?;
+        if status.contains(git2::SubmoduleStatus::WD_MODIFIED) {
+            moved.push(submodule.path().to_string_lossy().replace('\\', "/"));
+        }
+    }
+    Ok(moved)
+}
+
+/// Describe each submodule with local changes or not at its recorded commit
+pub fn submodule_problems(dir: &Path) -> CargoResult<Vec<String>> {
+    let repo = open(dir)?;
+    let mut problems = Vec::new();
+    for submodule in repo.submodules()? {
+        let name = if let Some(name) = submodule.name() {
+            name
+        } else {
+            continue;
+        };
+        let path = submodule.path().display();
+        let status = repo.submodule_status(name, git2::SubmoduleIgnore::None)?;
+        if status
+            .intersects(git2::SubmoduleStatus::WD_MODIFIED | git2::SubmoduleStatus::INDEX_MODIFIED)
+        {
+            problems.push(format!("{path} (not at the recorded commit)"));
+        }
+        if status.intersects(
+            git2::SubmoduleStatus::WD_INDEX_MODIFIED
+                | git2::SubmoduleStatus::WD_WD_MODIFIED
+                | git2::SubmoduleStatus::WD_UNTRACKED,
+        ) {
+            problems.push(format!("{path} (uncommitted changes)"));
+        }
+    }
+    Ok(problems)
+}
+
 pub fn tag(dir: &Path, name: &str, msg: &str, sign: bool, dry_run: bool) -> CargoResult<bool> {
     let mut cmd = vec!["git", "tag", name];
     if !msg.is_empty() {
@@ -168,18 +485,431 @@ pub fn tag(dir: &Path, name: &str, msg: &str, sign: bool, dry_run: bool) -> Carg
             cmd.push("-s");
         }
     }
-    call_on_path(cmd, dir, dry_run)
+    call_git(cmd, dir, dry_run)
+}
+
+/// A tag for [`tag_all`] to create at `HEAD`
+pub struct NewTag<'s> {
+    pub name: &'s str,
+    /// Creates a lightweight tag when empty
+    pub message: &'s str,
+    pub sign: bool,
+}
+
+/// Create `tags` at `HEAD`
+///
+/// Unsigned tags are created in-process rather than spawning `git` for each one.  Signing needs
+/// the user's GPG setup, so those still go through `git tag`, as does everything when
+/// `GIT_COMMITTER_DATE` asks for a date only git knows how to parse or when `git-path` or
+/// `git-env` change how `git` runs.
+pub fn tag_all(dir: &Path, tags: &[NewTag<'_>], dry_run: bool) -> CargoResult<bool> {
+    if GitOptions::workspace().is_customized() {
+        for tag in tags {
+            if !self::tag(dir, tag.name, tag.message, tag.sign, dry_run)? {
+                return Ok(false);
+            }
+        }
+        return Ok(true);
+    }
+
+    let repo = open(dir)?;
+    // `git tag` signs, and so annotates, every tag on its own with this set
+    let gpg_sign = repo
+        .config()
+        .and_then(|c| c.get_bool("tag.gpgSign"))
+        .unwrap_or(false);
+    let fixed_date = std::env::var_os("GIT_COMMITTER_DATE").is_some();
+    let (signed, unsigned): (Vec<_>, Vec<_>) = tags
+        .iter()
+        .partition(|t| t.sign || gpg_sign || (fixed_date && !t.message.is_empty()));
+
+    if dry_run {
+        for tag in &unsigned {
+            log::trace!("git tag {}", tag.name);
+        }
+    } else if !unsigned.is_empty() {
+        let head = repo.head()?.peel(git2::ObjectType::Commit)?;
+        let tagger = if unsigned.iter().any(|t| !t.message.is_empty()) {
+            Some(tagger(&repo)?)
+        } else {
+            None
+        };
+        for tag in &unsigned {
+            match tagger.as_ref() {
+                Some(tagger) if !tag.message.is_empty() => {
+                    // Match `git tag -m`'s whitespace cleanup
+                    let message = git2::message_prettify(tag.message, None)?;
+                    repo.tag(tag.name, &head, tagger, &message, false)?;
+                }
+                _ => {
+                    repo.tag_lightweight(tag.name, &head, false)?;
+                }
+            }
+        }
+    }
+
+    for tag in signed {
+        if !self::tag(dir, tag.name, tag.message, tag.sign, dry_run)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Who `git tag` would record as the tagger, honoring `GIT_COMMITTER_NAME` and
+/// `GIT_COMMITTER_EMAIL` like it does
+fn tagger(repo: &git2::Repository) -> CargoResult<git2::Signature<'static>> {
+    let name = std::env::var("GIT_COMMITTER_NAME").ok();
+    let email = std::env::var("GIT_COMMITTER_EMAIL").ok();
+    let signature = match (name, email) {
+        (Some(name), Some(email)) => git2::Signature::now(&name, &email)?,
+        (name, email) => {
+            let default = repo.signature()?;
+            let name = name.as_deref().or(default.name()).unwrap_or_default();
+            let email = email.as_deref().or(default.email()).unwrap_or_default();
+            git2::Signature::now(name, email)?
+        }
+    };
+    Ok(signature)
+}
+
+pub fn delete_tags(dir: &Path, names: &[&str], dry_run: bool) -> CargoResult<bool> {
+    if names.is_empty() {
+        return Ok(true);
+    }
+    let mut cmd = vec!["git", "tag", "-d"];
+    cmd.extend(names);
+    call_git(cmd, dir, dry_run)
+}
+
+pub fn delete_remote_tags(
+    dir: &Path,
+    remote: &str,
+    names: &[&str],
+    dry_run: bool,
+) -> CargoResult<bool> {
+    if names.is_empty() {
+        return Ok(true);
+    }
+    let refspecs = names
+        .iter()
+        .map(|name| format!("refs/tags/{}", name))
+        .collect::<Vec<_>>();
+    let mut cmd = vec!["git", "push", remote, "--delete"];
+    cmd.extend(refspecs.iter().map(|r| r.as_str()));
+    call_git(cmd, dir, dry_run)
+}
+
+pub fn remote_tag_exists(dir: &Path, remote: &str, name: &str) -> CargoResult<bool> {
+    Ok(!remote_tags(dir, remote, &[name])?.is_empty())
+}
+
+/// Which of `names` exist as tags on `remote`, looked up in one request
+pub fn remote_tags(dir: &Path, remote: &str, names: &[&str]) -> CargoResult<Vec<String>> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+    let output = git()
+        .arg("ls-remote")
+        .arg("--exit-code")
+        .arg("--tags")
+        .arg(remote)
+        .args(names.iter().map(|name| format!("refs/tags/{}", name)))
+        .current_dir(dir)
+        .output()
+        .map_err(|_| anyhow::format_err!("`git` not found"))?;
+    // `--exit-code` reports no matching refs with 2
+    match output.status.code() {
+        Some(0) => {}
+        Some(2) => return Ok(Vec::new()),
+        _ => anyhow::bail!(
+            "failed to look up tags on {}: {}",
+            remote,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .filter_map(|r| r.strip_prefix("refs/tags/"))
+        // Annotated tags are also listed peeled, as `<name>^{}`
+        .filter(|name| !name.ends_with("^{}"))
+        .filter(|name| names.contains(name))
+        .map(ToOwned::to_owned)
+        .collect())
+}
+
+/// The commit `name` points to, if the tag exists
+pub fn tag_commit(dir: &Path, name: &str) -> CargoResult<Option<String>> {
+    let repo = open(dir)?;
+    let tag = match repo.revparse_single(&format!("refs/tags/{name}")) {
+        Ok(tag) => tag,
+        Err(err) if err.code() == git2::ErrorCode::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    Ok(Some(tag.peel_to_commit()?.id().to_string()))
 }
 
 pub fn tag_exists(dir: &Path, name: &str) -> CargoResult<bool> {
-    let repo = git2::Repository::discover(dir)?;
+    let repo = open(dir)?;
 
     let names = repo.tag_names(Some(name))?;
     Ok(!names.is_empty())
 }
 
+/// Whether `name` is an annotated tag, the only kind `git push --follow-tags` brings along
+pub fn is_annotated_tag(dir: &Path, name: &str) -> CargoResult<bool> {
+    let repo = open(dir)?;
+
+    let reference = repo.find_reference(&format!("refs/tags/{name}"))?;
+    Ok(reference
+        .target()
+        .map(|oid| repo.find_tag(oid).is_ok())
+        .unwrap_or(false))
+}
+
+/// Names of the tags matching the glob `pattern`, like `v1.*`
+pub fn tags_matching(dir: &Path, pattern: &str) -> CargoResult<Vec<String>> {
+    let repo = open(dir)?;
+
+    let names = repo.tag_names(Some(pattern))?;
+    Ok(names.iter().flatten().map(|n| n.to_owned()).collect())
+}
+
+/// Check the signature on tag `name`
+///
+/// Returns `None` for lightweight and unsigned tags, which have nothing to verify.
+pub fn verify_tag(dir: &Path, name: &str) -> CargoResult<Option<bool>> {
+    let repo = open(dir)?;
+
+    let tag = repo.revparse_single(&format!("refs/tags/{name}"))?;
+    let tag = if let Some(tag) = tag.as_tag() {
+        tag
+    } else {
+        return Ok(None);
+    };
+    // Signatures are appended to the message of annotated tags
+    let message = tag.message_bytes().unwrap_or_default();
+    if message.find(b"-----BEGIN ").is_none() {
+        return Ok(None);
+    }
+
+    let output = git()
+        .arg("verify-tag")
+        .arg(name)
+        .current_dir(dir)
+        .output()
+        .map_err(|_| anyhow::format_err!("`git` not found"))?;
+    Ok(Some(output.status.success()))
+}
+
+/// The content of each of `paths` in the commit `tag` points to, if it existed then
+pub fn files_at_tag(dir: &Path, tag: &str, paths: &[PathBuf]) -> CargoResult<Vec<Option<Vec<u8>>>> {
+    let repo = open(dir)?;
+    let workdir = if let Some(workdir) = repo.workdir() {
+        workdir.to_owned()
+    } else {
+        return Ok(vec![None; paths.len()]);
+    };
+    let tree = repo
+        .revparse_single(&format!("refs/tags/{tag}"))?
+        .peel_to_tree()?;
+
+    let mut content = Vec::with_capacity(paths.len());
+    for path in paths {
+        let relative = if let Ok(relative) = path.strip_prefix(&workdir) {
+            relative
+        } else {
+            content.push(None);
+            continue;
+        };
+        let entry = match tree.get_path(relative) {
+            Ok(entry) => entry,
+            Err(err) if err.code() == git2::ErrorCode::NotFound => {
+                content.push(None);
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let blob = entry.to_object(&repo)?.peel_to_blob()?;
+        content.push(Some(blob.content().to_owned()));
+    }
+    Ok(content)
+}
+
+/// Every file under `dir` in the commit `tag` points to
+pub fn files_in_tag(dir: &Path, tag: &str) -> CargoResult<Vec<PathBuf>> {
+    let repo = open(dir)?;
+    let workdir = if let Some(workdir) = repo.workdir() {
+        workdir.to_owned()
+    } else {
+        return Ok(Vec::new());
+    };
+    let tree = repo
+        .revparse_single(&format!("refs/tags/{tag}"))?
+        .peel_to_tree()?;
+
+    let mut files = Vec::new();
+    tree.walk(git2::TreeWalkMode::PreOrder, |parent, entry| {
+        if entry.kind() != Some(git2::ObjectType::Blob) {
+            return git2::TreeWalkResult::Ok;
+        }
+        let name = if let Some(name) = entry.name() {
+            name
+        } else {
+            return git2::TreeWalkResult::Ok;
+        };
+        let path = workdir.join(parent).join(name);
+        if path.starts_with(&dir) {
+            files.push(path);
+        }
+        git2::TreeWalkResult::Ok
+    })?;
+    Ok(files)
+}
+
+/// Sign a throwaway message the way git would sign commits and tags
+///
+/// This brings up any passphrase prompt or smartcard check before the release changes anything.
+/// Returns the signer's error output on failure.
+pub fn test_signing(dir: &Path) -> CargoResult<Result<(), String>> {
+    use std::io::Write as _;
+
+    // Read through `git` itself, so `git-path` and `git-env` see the same config it signs with
+    let config = |key: &str| -> CargoResult<Option<String>> {
+        let output = git()
+            .args(["config", "--get", key])
+            .current_dir(dir)
+            .output()
+            .map_err(|_| anyhow::format_err!("`git` not found"))?;
+        Ok(output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_owned()))
+    };
+    let envs = GitOptions::workspace().envs;
+    let format = config("gpg.format")?.unwrap_or_else(|| "openpgp".to_owned());
+    let key = config("user.signingkey")?;
+    let program = if let Some(program) = config(&format!("gpg.{format}.program"))? {
+        Some(program)
+    } else if format == "openpgp" {
+        config("gpg.program")?
+    } else {
+        None
+    };
+
+    let mut cmd = match format.as_str() {
+        "ssh" => {
+            let key = if let Some(key) = key {
+                key
+            } else {
+                return Ok(Err(
+                    "`user.signingkey` is required for ssh signing".to_owned()
+                ));
+            };
+            if key.starts_with("key::") || key.starts_with("ssh-") {
+                // Literal keys are only usable through the agent
+                let output = Command::new("ssh-add")
+                    .arg("-L")
+                    .envs(&envs)
+                    .output()
+                    .map_err(|_| anyhow::format_err!("`ssh-add` not found"))?;
+                let public_key = key.trim_start_matches("key::");
+                let loaded = ByteSlice::lines(output.stdout.as_slice())
+                    .any(|l| l.starts_with(public_key.as_bytes()));
+                return Ok(if loaded {
+                    Ok(())
+                } else {
+                    Err("the signing key isn't loaded in the ssh agent".to_owned())
+                });
+            }
+            let mut cmd = Command::new(program.as_deref().unwrap_or("ssh-keygen"));
+            cmd.args(["-Y", "sign", "-n", "git", "-f"]).arg(key);
+            cmd
+        }
+        _ => {
+            let default = if format == "x509" { "gpgsm" } else { "gpg" };
+            let mut cmd = Command::new(program.as_deref().unwrap_or(default));
+            cmd.args(["--detach-sign", "--armor"]);
+            if let Some(key) = key {
+                cmd.arg("--local-user").arg(key);
+            }
+            cmd
+        }
+    };
+    let mut child = cmd
+        .current_dir(dir)
+        .envs(&envs)
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|err| anyhow::format_err!("could not run {:?}: {}", cmd.get_program(), err))?;
+    child
+        .stdin
+        .take()
+        .expect("piped")
+        .write_all(b"cargo-release signing check\n")?;
+    let output = child.wait_with_output()?;
+    if output.status.success() {
+        Ok(Ok(()))
+    } else {
+        Ok(Err(String::from_utf8_lossy(&output.stderr)
+            .trim()
+            .to_owned()))
+    }
+}
+
+/// The commit `HEAD` points to
+pub fn head_id(dir: &Path) -> CargoResult<String> {
+    let repo = open(dir)?;
+
+    let head = repo.head()?.peel_to_commit()?;
+    Ok(head.id().to_string())
+}
+
+/// The first line of `HEAD`'s commit message
+pub fn head_subject(dir: &Path) -> CargoResult<String> {
+    let repo = open(dir)?;
+
+    let head = repo.head()?.peel_to_commit()?;
+    Ok(head.summary().unwrap_or_default().to_owned())
+}
+
+/// Whether `HEAD` is the commit `name` points to
+pub fn is_head_at_tag(dir: &Path, name: &str) -> CargoResult<bool> {
+    let repo = open(dir)?;
+
+    let tag = repo.revparse_single(&format!("refs/tags/{name}"))?;
+    let tag_commit = tag.peel_to_commit()?;
+    let head_commit = repo.head()?.peel_to_commit()?;
+    Ok(tag_commit.id() == head_commit.id())
+}
+
 pub fn find_last_tag(dir: &Path, glob: &globset::GlobMatcher) -> Option<String> {
-    let repo = git2::Repository::discover(dir).ok()?;
+    let repo = open(dir).ok()?;
+    let head = repo.head().ok()?.peel_to_commit().ok()?.id();
+    last_tag_from(&repo, glob, head)
+}
+
+/// The last tag matching `glob` from before the commit `tag` points to
+pub fn find_tag_before(dir: &Path, glob: &globset::GlobMatcher, tag: &str) -> Option<String> {
+    let repo = open(dir).ok()?;
+    let tagged = repo
+        .revparse_single(&format!("refs/tags/{tag}"))
+        .ok()?
+        .peel_to_commit()
+        .ok()?;
+    let parent = tagged.parent_id(0).ok()?;
+    last_tag_from(&repo, glob, parent)
+}
+
+fn last_tag_from(
+    repo: &git2::Repository,
+    glob: &globset::GlobMatcher,
+    start: git2::Oid,
+) -> Option<String> {
     let mut tags: std::collections::HashMap<git2::Oid, String> = Default::default();
     repo.tag_foreach(|id, name| {
         let name = String::from_utf8_lossy(name);
@@ -201,7 +931,7 @@ pub fn find_last_tag(dir: &Path, glob: &globset::GlobMatcher) -> Option<String>
     revwalk.simplify_first_parent().ok()?;
     // If just walking first parents, shouldn't really need to sort
     revwalk.set_sorting(git2::Sort::NONE).ok()?;
-    revwalk.push_head().ok()?;
+    revwalk.push(start).ok()?;
     let name = revwalk.find_map(|id| {
         let id = id.ok()?;
         tags.remove(&id)
@@ -214,11 +944,15 @@ pub fn push<'s>(
     remote: &str,
     refs: impl IntoIterator<Item = &'s str>,
     options: impl IntoIterator<Item = &'s str>,
+    follow_tags: bool,
     dry_run: bool,
 ) -> CargoResult<bool> {
     // Use an atomic push to ensure that e.g. if main and a tag are pushed together, and the local
     // main diverges from the remote main, that the push fails entirely.
     let mut command = vec!["git", "push", "--atomic"];
+    if follow_tags {
+        command.push("--follow-tags");
+    }
 
     for option in options {
         command.push("--push-option");
@@ -236,23 +970,126 @@ pub fn push<'s>(
         return Ok(true);
     }
 
-    call_on_path(command, dir, dry_run)
+    call_git(command, dir, dry_run)
+}
+
+/// Clone `src` into `dest`, including local branches and tags
+pub fn scratch_clone(src: &Path, dest: &Path) -> CargoResult<bool> {
+    call_git_outside(
+        &GitOptions::workspace(),
+        [
+            std::ffi::OsStr::new("clone"),
+            std::ffi::OsStr::new("--quiet"),
+            std::ffi::OsStr::new("--no-hardlinks"),
+            src.as_os_str(),
+            dest.as_os_str(),
+        ],
+    )
+}
+
+/// Check out `rev` without a branch, like a tag in a scratch clone
+pub fn checkout_detached(dir: &Path, rev: &str) -> CargoResult<bool> {
+    call_git(["git", "checkout", "--quiet", "--detach", rev], dir, false)
+}
+
+/// Whether a release commit would leave changes to `path` behind, as it is neither tracked nor
+/// ignored
+pub fn is_untracked(dir: &Path, path: &Path) -> CargoResult<bool> {
+    let repo = open(dir)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow::format_err!("bare repos are unsupported"))?;
+    let rel_path = path.strip_prefix(workdir).unwrap_or(path);
+    if repo.is_path_ignored(rel_path)? {
+        return Ok(false);
+    }
+    Ok(repo.index()?.get_path(rel_path, 0).is_none())
+}
+
+pub fn add(dir: &Path, path: &Path, dry_run: bool) -> CargoResult<bool> {
+    if dry_run {
+        log::trace!("cd {}", dir.display());
+        log::trace!("git add {}", path.display());
+        return Ok(true);
+    }
+    // Not through `call_git`, which only takes UTF-8 arguments
+    let status = git()
+        .arg("add")
+        .arg(path)
+        .current_dir(dir)
+        .status()
+        .map_err(|_| anyhow::format_err!("`git` not found"))?;
+    Ok(status.success())
+}
+
+pub fn add_remote(dir: &Path, name: &str, url: &str) -> CargoResult<bool> {
+    call_git(["git", "remote", "add", name, url], dir, false)
+}
+
+/// The URL `remote` is pushed to, if it exists
+pub fn remote_url(dir: &Path, remote: &str) -> CargoResult<Option<String>> {
+    let repo = open(dir)?;
+    let remote = match repo.find_remote(remote) {
+        Ok(remote) => remote,
+        Err(err) if err.code() == git2::ErrorCode::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    Ok(remote
+        .pushurl()
+        .or_else(|| remote.url())
+        .map(ToOwned::to_owned))
+}
+
+/// Replace the remotes of `dest` with those of `src`
+pub fn mirror_remotes(src: &Path, dest: &Path) -> CargoResult<()> {
+    let src = open(src)?;
+    let dest = git2::Repository::open(dest)?;
+    for name in dest.remotes()?.iter().flatten() {
+        dest.remote_delete(name)?;
+    }
+    for name in src.remotes()?.iter().flatten() {
+        let remote = src.find_remote(name)?;
+        if let Some(url) = remote.url() {
+            dest.remote(name, url)?;
+        }
+        if let Some(push_url) = remote.pushurl() {
+            dest.remote_set_pushurl(name, Some(push_url))?;
+        }
+    }
+    Ok(())
+}
+
+/// Fast-forward `branch` and fetch all tags from the clone at `src`
+pub fn pull_release(dir: &Path, src: &Path, branch: &str) -> CargoResult<bool> {
+    let src = src.display().to_string();
+    Ok(call_git(
+        ["git", "pull", "--quiet", "--ff-only", &src, branch],
+        dir,
+        false,
+    )? && call_git(
+        ["git", "fetch", "--quiet", &src, "refs/tags/*:refs/tags/*"],
+        dir,
+        false,
+    )?)
 }
 
 pub fn top_level(dir: &Path) -> CargoResult<PathBuf> {
-    let repo = git2::Repository::discover(dir)?;
+    let repo = open(dir)?;
 
     repo.workdir()
         .map(|p| p.to_owned())
         .ok_or_else(|| anyhow::format_err!("bare repos are unsupported"))
 }
 
+/// Make sure the configured `git` can be run, see [`set_options`]
 pub fn git_version() -> CargoResult<()> {
-    Command::new("git")
-        .arg("--version")
-        .output()
-        .map(|_| ())
-        .map_err(|_| anyhow::format_err!("`git` not found"))
+    let options = GitOptions::workspace();
+    git().arg("--version").output().map(|_| ()).map_err(|_| {
+        anyhow::format_err!(
+            "`{}` not found",
+            options.program.as_deref().unwrap_or("git")
+        )
+    })
 }
 
 // From git2 crate
@@ -0,0 +1,172 @@
+//! HTTP clients shared by index lookups, crates.io, docs.rs, and remote config
+//!
+//! Clients are reused for identical settings so connections are pooled across subsystems, and the
+//! workspace's [`ClientOptions`] apply to every request.  Proxies come from `index-proxy` or the
+//! usual `HTTPS_PROXY`-style environment variables.
+//!
+//! Requests that can overlap, like prefetching index entries or polling several crates, go through
+//! async clients on a small shared runtime with [`run_all`].
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use tame_index::external::reqwest;
+use tame_index::external::reqwest::blocking::Client;
+
+use crate::config::IndexHttpVersion;
+use crate::error::CargoResult;
+
+/// Apply [`ClientOptions`] to a blocking or async `ClientBuilder`, which share no trait
+macro_rules! configure {
+    ($builder:expr, $options:expr, $ca_info:expr, $protocol:expr) => {{
+        let options: &ClientOptions = $options;
+        let user_agent = if let Some(suffix) = options.user_agent_suffix.as_deref() {
+            format!("{} {}", crate::ops::USER_AGENT, suffix)
+        } else {
+            crate::ops::USER_AGENT.to_owned()
+        };
+        let mut builder = $builder.user_agent(user_agent);
+        match $protocol {
+            IndexHttpVersion::Auto => {}
+            IndexHttpVersion::Http1 => builder = builder.http1_only(),
+            IndexHttpVersion::Http2 => builder = builder.http2_prior_knowledge(),
+        }
+        if let Some(timeout) = options.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = options.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        for (host, ip) in &options.hosts {
+            // The port comes from the URL, whatever the address says
+            builder = builder.resolve(host, std::net::SocketAddr::new(*ip, 0));
+        }
+        if let Some(proxy) = options.proxy.as_deref() {
+            builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        if let Some(ca_info) = $ca_info {
+            let pem = std::fs::read(ca_info)?;
+            let cert = reqwest::Certificate::from_pem(&pem)?;
+            builder = builder.add_root_certificate(cert);
+        }
+        builder
+    }};
+}
+
+/// Settings for the HTTP client
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ClientOptions {
+    pub timeout: Option<std::time::Duration>,
+    pub connect_timeout: Option<std::time::Duration>,
+    pub user_agent_suffix: Option<String>,
+    pub http_version: IndexHttpVersion,
+    /// Addresses to use instead of looking hosts up in DNS
+    pub hosts: Vec<(String, std::net::IpAddr)>,
+    /// Send every request through this proxy, rather than any from the environment
+    pub proxy: Option<String>,
+}
+
+impl ClientOptions {
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        Self {
+            timeout: config.index_timeout(),
+            connect_timeout: config.index_connect_timeout(),
+            user_agent_suffix: config.index_user_agent_suffix().map(ToOwned::to_owned),
+            http_version: config.index_http_version(),
+            hosts: config
+                .index_hosts()
+                .map(|(host, ip)| (host.to_owned(), ip))
+                .collect(),
+            proxy: config.index_proxy().map(ToOwned::to_owned),
+        }
+    }
+}
+
+type ClientKey = (ClientOptions, Option<PathBuf>, IndexHttpVersion);
+
+/// Requests in flight at once, to stay polite to the services being polled
+const MAX_IN_FLIGHT: usize = 8;
+
+static DEFAULT_OPTIONS: once_cell::sync::Lazy<std::sync::Mutex<ClientOptions>> =
+    once_cell::sync::Lazy::new(Default::default);
+static CLIENTS: once_cell::sync::Lazy<std::sync::Mutex<HashMap<ClientKey, Client>>> =
+    once_cell::sync::Lazy::new(Default::default);
+static ASYNC_CLIENTS: once_cell::sync::Lazy<std::sync::Mutex<HashMap<ClientKey, reqwest::Client>>> =
+    once_cell::sync::Lazy::new(Default::default);
+static RUNTIME: once_cell::sync::Lazy<tokio::runtime::Runtime> = once_cell::sync::Lazy::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .thread_name("cargo-release-http")
+        .enable_all()
+        .build()
+        .expect("failed to start the HTTP runtime")
+});
+
+/// Run `futures` with at most [`MAX_IN_FLIGHT`] at a time, returning their outputs in order
+pub fn run_all<F: std::future::Future>(futures: impl IntoIterator<Item = F>) -> Vec<F::Output> {
+    use futures_util::StreamExt as _;
+
+    RUNTIME.block_on(
+        futures_util::stream::iter(futures)
+            .buffered(MAX_IN_FLIGHT)
+            .collect(),
+    )
+}
+
+/// Use `options` for requests that aren't tied to a specific registry
+pub fn set_default_options(options: ClientOptions) {
+    *DEFAULT_OPTIONS.lock().unwrap() = options;
+}
+
+/// A client for general requests, like to the crates.io API or docs.rs
+pub fn default_client() -> CargoResult<Client> {
+    let options = DEFAULT_OPTIONS.lock().unwrap().clone();
+    client(&options, None, IndexHttpVersion::Auto)
+}
+
+/// An async client for general requests, see [`default_client`]
+pub fn default_async_client() -> CargoResult<reqwest::Client> {
+    let options = DEFAULT_OPTIONS.lock().unwrap().clone();
+    async_client(&options, None, IndexHttpVersion::Auto)
+}
+
+/// A pooled client speaking `protocol`, trusting `ca_info` in addition to the system roots
+pub fn client(
+    options: &ClientOptions,
+    ca_info: Option<&Path>,
+    protocol: IndexHttpVersion,
+) -> CargoResult<Client> {
+    let key = (options.clone(), ca_info.map(ToOwned::to_owned), protocol);
+    let mut clients = CLIENTS.lock().unwrap();
+    if let Some(client) = clients.get(&key) {
+        // Clones share the connection pool
+        return Ok(client.clone());
+    }
+    let client = configure!(
+        reqwest::blocking::ClientBuilder::new(),
+        options,
+        ca_info,
+        protocol
+    )
+    .build()?;
+    clients.insert(key, client.clone());
+    Ok(client)
+}
+
+/// The async counterpart of [`client`], for use with [`run_all`]
+pub fn async_client(
+    options: &ClientOptions,
+    ca_info: Option<&Path>,
+    protocol: IndexHttpVersion,
+) -> CargoResult<reqwest::Client> {
+    let key = (options.clone(), ca_info.map(ToOwned::to_owned), protocol);
+    let mut clients = ASYNC_CLIENTS.lock().unwrap();
+    if let Some(client) = clients.get(&key) {
+        return Ok(client.clone());
+    }
+    // Built within the runtime its connections will be driven by
+    let _guard = RUNTIME.enter();
+    let client = configure!(reqwest::ClientBuilder::new(), options, ca_info, protocol).build()?;
+    clients.insert(key, client.clone());
+    Ok(client)
+}
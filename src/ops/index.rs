@@ -1,21 +1,92 @@
 use tame_index::krate::IndexKrate;
 use tame_index::utils::flock::FileLock;
 
+use crate::config::IndexHttpVersion;
+use crate::config::RegistryConfig;
+use crate::ops::credentials::IndexAuth;
+use crate::ops::http::ClientOptions;
+
+/// Add [`IndexAuth`] to a blocking or async `RequestBuilder`, which share no trait
+macro_rules! authorize {
+    ($req:expr, $auth:expr) => {{
+        let req = $req;
+        match $auth {
+            Some(IndexAuth::Token(token)) => req.header(
+                tame_index::external::reqwest::header::AUTHORIZATION,
+                token.as_str(),
+            ),
+            Some(IndexAuth::Basic { login, password }) => req.basic_auth(login, Some(password)),
+            None => req,
+        }
+    }};
+}
+
+/// Root of crates.io's web API
+const CRATES_IO_API: &str = "https://crates.io";
+
+/// `dl` from crates.io's `config.json`
+const CRATES_IO_DL: &str = "https://static.crates.io/crates";
+
 #[derive(Default)]
 pub struct CratesIoIndex {
-    index: Option<RemoteIndex>,
-    cache: std::collections::HashMap<String, Option<IndexKrate>>,
+    options: ClientOptions,
+    api_fallback: bool,
+    cache_max_age: std::time::Duration,
+    indexes: std::collections::HashMap<Option<String>, AnyIndex>,
+    registries: std::collections::HashMap<Option<String>, RegistryConfig>,
+    cache: std::collections::HashMap<(Option<String>, String), Option<IndexKrate>>,
+    /// Crates whose next lookup must go to the network, like after publishing them
+    refresh: std::collections::HashSet<(Option<String>, String)>,
 }
 
 impl CratesIoIndex {
     #[inline]
     pub fn new() -> Self {
         Self {
-            index: None,
+            options: ClientOptions::default(),
+            api_fallback: false,
+            cache_max_age: std::time::Duration::ZERO,
+            indexes: std::collections::HashMap::new(),
+            registries: std::collections::HashMap::new(),
             cache: std::collections::HashMap::new(),
+            refresh: std::collections::HashSet::new(),
         }
     }
 
+    /// Configure the HTTP client for indexes not yet connected to
+    pub fn set_client_options(&mut self, options: ClientOptions) {
+        self.options = options;
+    }
+
+    /// Look crates up through the registry's web API when its index can't be reached
+    pub fn set_api_fallback(&mut self, api_fallback: bool) {
+        self.api_fallback = api_fallback;
+    }
+
+    /// Trust cargo's local cache of a sparse index when it was updated within `max_age`
+    pub fn set_cache_max_age(&mut self, max_age: std::time::Duration) {
+        self.cache_max_age = max_age;
+    }
+
+    /// Connect to `registry` (`None` for crates.io) according to `config`
+    ///
+    /// Without an `index` URL, crates in a non-default registry can't be looked up.
+    pub fn add_registry(&mut self, registry: Option<&str>, config: &RegistryConfig) {
+        self.registries
+            .insert(registry.map(ToOwned::to_owned), config.clone());
+    }
+
+    /// Whether crates in `registry` can be looked up, through its index or API
+    pub fn can_look_up(&self, registry: Option<&str>) -> bool {
+        let registry = registry.map(ToOwned::to_owned);
+        registry.is_none()
+            || self
+                .registries
+                .get(&registry)
+                .map_or(false, |c| c.index.is_some())
+            || self.api_url(&registry).is_some()
+    }
+
     /// Determines if the specified crate exists in the crates.io index
     #[inline]
     pub fn has_krate(
@@ -38,13 +109,165 @@ impl CratesIoIndex {
         Ok(krate.map(|ik| ik.versions.iter().any(|iv| iv.version == version)))
     }
 
+    /// Determines the highest non-yanked version of the specified crate in the crates.io index
+    #[inline]
+    pub fn latest_krate_version(
+        &mut self,
+        registry: Option<&str>,
+        name: &str,
+    ) -> Result<Option<semver::Version>, crate::error::CliError> {
+        let versions = self.krate_versions(registry, name)?;
+        Ok(versions.and_then(|v| v.into_iter().max()))
+    }
+
+    /// Lists the non-yanked versions of the specified crate in the crates.io index
+    #[inline]
+    pub fn krate_versions(
+        &mut self,
+        registry: Option<&str>,
+        name: &str,
+    ) -> Result<Option<Vec<semver::Version>>, crate::error::CliError> {
+        let krate = self.krate(registry, name)?;
+        Ok(krate.map(|ik| {
+            ik.versions
+                .iter()
+                .filter(|iv| !iv.yanked)
+                .filter_map(|iv| semver::Version::parse(&iv.version).ok())
+                .collect()
+        }))
+    }
+
+    /// The checksum of the `.crate` published for the specified crate version
+    #[inline]
+    pub fn krate_checksum(
+        &mut self,
+        registry: Option<&str>,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<[u8; 32]>, crate::error::CliError> {
+        let krate = self.krate(registry, name)?;
+        Ok(krate.and_then(|ik| {
+            ik.versions
+                .iter()
+                .find(|iv| iv.version == version)
+                .map(|iv| iv.checksum.0)
+        }))
+    }
+
+    /// The published `.crate` for the specified crate version, checked against the index
+    pub fn download_krate(
+        &mut self,
+        registry: Option<&str>,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<Vec<u8>>, crate::error::CliError> {
+        use sha2::Digest as _;
+
+        let checksum = if let Some(checksum) = self.krate_checksum(registry, name, version)? {
+            checksum
+        } else {
+            return Ok(None);
+        };
+        let key = registry.map(ToOwned::to_owned);
+        if !self.connect(&key)? {
+            return Ok(None);
+        }
+        let checksum_hex = checksum
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        let body = match self.indexes.get_mut(&key).unwrap() {
+            AnyIndex::Sparse(index) => {
+                let url = download_url(&index.dl()?, name, version, &checksum_hex);
+                index.get(&url)?
+            }
+            AnyIndex::Git(index) => {
+                let url = download_url(&index.dl()?, name, version, &checksum_hex);
+                let ca_info = self
+                    .registries
+                    .get(&key)
+                    .and_then(|c| c.ca_info.as_deref())
+                    .map(std::path::Path::new);
+                let client =
+                    crate::ops::http::client(&self.options, ca_info, IndexHttpVersion::Auto)?;
+                let res = client.get(&url).send()?;
+                let status = res.status();
+                if !status.is_success() {
+                    return Err(
+                        anyhow::format_err!("unexpected response from {url}: {status}").into(),
+                    );
+                }
+                res.bytes()?.to_vec()
+            }
+        };
+        if sha2::Sha256::digest(&body).as_slice() != checksum.as_slice() {
+            return Err(anyhow::format_err!(
+                "downloaded {name} v{version} doesn't match the index's checksum"
+            )
+            .into());
+        }
+        Ok(Some(body))
+    }
+
     #[inline]
     pub fn update_krate(&mut self, registry: Option<&str>, name: &str) {
-        if registry.is_some() {
-            return;
+        let registry = registry.map(ToOwned::to_owned);
+        if let Some(AnyIndex::Git(index)) = self.indexes.get_mut(&registry) {
+            index.invalidate();
         }
+        let key = (registry, name.to_owned());
+        self.cache.remove(&key);
+        self.refresh.insert(key);
+    }
 
-        self.cache.remove(name);
+    /// Look up `names` concurrently so later queries are answered from the cache
+    pub fn prefetch(
+        &mut self,
+        registry: Option<&str>,
+        names: &[&str],
+    ) -> Result<(), crate::error::CliError> {
+        let registry = registry.map(ToOwned::to_owned);
+        let names = names
+            .iter()
+            .filter(|name| {
+                !self
+                    .cache
+                    .contains_key(&(registry.clone(), (**name).to_owned()))
+            })
+            .map(|name| (*name).to_owned())
+            .collect::<Vec<_>>();
+        let names = names
+            .into_iter()
+            .filter(|name| {
+                let key = (registry.clone(), name.clone());
+                if let Some(entry) = self.fresh_krate(&key) {
+                    self.cache.insert(key, Some(entry));
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect::<Vec<_>>();
+        if names.is_empty() || !self.connect(&registry)? {
+            return Ok(());
+        }
+
+        // Git indexes are read from a local checkout, so there is nothing to overlap
+        if let Some(AnyIndex::Sparse(index)) = self.indexes.get_mut(&registry) {
+            log::trace!("Prefetching index for {}", names.join(", "));
+            for (name, entry) in index.krates(&names)? {
+                match entry {
+                    Ok(entry) => {
+                        self.cache.insert((registry.clone(), name), entry);
+                    }
+                    Err(err) => {
+                        // Left uncached, so `krate` retries it on its own, including from the API
+                        log::debug!("failed to prefetch {name}: {err}");
+                    }
+                }
+            }
+        }
+        Ok(())
     }
 
     pub(crate) fn krate(
@@ -52,54 +275,375 @@ impl CratesIoIndex {
         registry: Option<&str>,
         name: &str,
     ) -> Result<Option<IndexKrate>, crate::error::CliError> {
-        if let Some(registry) = registry {
-            log::trace!("Cannot connect to registry `{registry}`");
+        let key = (registry.map(ToOwned::to_owned), name.to_owned());
+        if let Some(entry) = self.cache.get(&key) {
+            log::trace!("Reusing index for {name}");
+            return Ok(entry.clone());
+        }
+        if let Some(entry) = self.fresh_krate(&key) {
+            log::trace!("Reusing cargo's cache of the index for {name}");
+            let entry = Some(entry);
+            self.cache.insert(key, entry.clone());
+            return Ok(entry);
+        }
+
+        let entry = match self.index_krate(&key.0, name) {
+            Ok(Some(entry)) => entry,
+            Ok(None) => {
+                // No index to ask, like a registry only configured with its `api`
+                log::debug!("no index for {name}, trying the API");
+                match self.krate_from_api(&key.0, name) {
+                    Some(entry) => entry,
+                    None => return Ok(None),
+                }
+            }
+            Err(err) => {
+                log::debug!("index lookup for {name} failed, trying the API: {err}");
+                match self.krate_from_api(&key.0, name) {
+                    Some(entry) => entry,
+                    None => self.stale_krate(&key, err)?,
+                }
+            }
+        };
+        self.refresh.remove(&key);
+        self.cache.insert(key, entry.clone());
+        Ok(entry)
+    }
+
+    /// `key`'s entry in cargo's cache of the index, if it is recent enough to skip the network
+    fn fresh_krate(&mut self, key: &(Option<String>, String)) -> Option<IndexKrate> {
+        if self.cache_max_age.is_zero() || self.refresh.contains(key) {
+            return None;
+        }
+        self.cached_krate(&key.0, &key.1, Some(self.cache_max_age))
+    }
+
+    /// Answer from cargo's cache of the index, however old, when the index can't be reached
+    fn stale_krate(
+        &mut self,
+        key: &(Option<String>, String),
+        err: crate::error::CliError,
+    ) -> Result<Option<IndexKrate>, crate::error::CliError> {
+        // Waiting on a publish needs the live index
+        if self.refresh.contains(key) {
+            return Err(err);
+        }
+        if let Some(entry) = self.cached_krate(&key.0, &key.1, None) {
+            let _ = crate::ops::shell::warn(format!(
+                "couldn't reach the index, using cargo's cached entry for {}: {err}",
+                key.1
+            ));
+            Ok(Some(entry))
+        } else {
+            Err(err)
+        }
+    }
+
+    /// `name`'s entry in cargo's cache of a sparse index, if updated within `max_age`
+    fn cached_krate(
+        &mut self,
+        registry: &Option<String>,
+        name: &str,
+        max_age: Option<std::time::Duration>,
+    ) -> Option<IndexKrate> {
+        match self.connect(registry) {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(err) => {
+                log::debug!("failed to open the index: {err}");
+                return None;
+            }
+        }
+        let index = if let Some(AnyIndex::Sparse(index)) = self.indexes.get(registry) {
+            index
+        } else {
+            return None;
+        };
+        match index.cached_krate(name, max_age) {
+            Ok(entry) => entry,
+            Err(err) => {
+                log::debug!("failed to read cargo's cache of the index for {name}: {err}");
+                None
+            }
+        }
+    }
+
+    /// Look `name` up in the index, or `None` if the index can't be connected to
+    fn index_krate(
+        &mut self,
+        registry: &Option<String>,
+        name: &str,
+    ) -> Result<Option<Option<IndexKrate>>, crate::error::CliError> {
+        if !self.connect(registry)? {
             return Ok(None);
         }
+        log::trace!("Downloading index for {name}");
+        let entry = match self.indexes.get_mut(registry).unwrap() {
+            AnyIndex::Sparse(index) => index.krate(name)?,
+            AnyIndex::Git(index) => index.krate(name)?,
+        };
+        Ok(Some(entry))
+    }
 
-        if let Some(entry) = self.cache.get(name) {
-            log::trace!("Reusing index for {name}");
-            return Ok(entry.clone());
+    /// Look `name` up through `registry`'s web API instead of its index, if enabled
+    fn krate_from_api(&self, registry: &Option<String>, name: &str) -> Option<Option<IndexKrate>> {
+        let api = self.api_url(registry)?;
+        let ca_info = self
+            .registries
+            .get(registry)
+            .and_then(|c| c.ca_info.as_deref())
+            .map(std::path::Path::new);
+        match api_krate(api, ca_info, &self.options, name) {
+            Ok(entry) => Some(entry),
+            Err(api_err) => {
+                log::debug!("{api} lookup for {name} failed: {api_err}");
+                None
+            }
         }
+    }
 
-        if self.index.is_none() {
+    /// The web API to fall back to for `registry`, if enabled
+    fn api_url(&self, registry: &Option<String>) -> Option<&str> {
+        if !self.api_fallback {
+            return None;
+        }
+        self.registries
+            .get(registry)
+            .and_then(|c| c.api.as_deref())
+            .or_else(|| registry.is_none().then_some(CRATES_IO_API))
+    }
+
+    /// Open the index for `registry`, returning whether it can be reached
+    fn connect(&mut self, registry: &Option<String>) -> Result<bool, crate::error::CliError> {
+        let config = self.registries.get(registry);
+        let url = config.and_then(|c| c.index.as_deref());
+        if let (Some(registry), None) = (registry.as_deref(), url) {
+            log::trace!("Cannot connect to registry `{registry}`");
+            return Ok(false);
+        }
+
+        if !self.indexes.contains_key(registry) {
             log::trace!("Connecting to index");
-            self.index = Some(RemoteIndex::open()?);
+            let index = match url {
+                Some(url) if !url.starts_with("sparse+") => AnyIndex::Git(GitIndex::open(url)?),
+                _ => {
+                    let ca_info = config.and_then(|c| c.ca_info.as_deref());
+                    let mut index =
+                        RemoteIndex::open(url, ca_info.map(std::path::Path::new), &self.options)?;
+                    if let (Some(registry), Some(config)) = (registry.as_deref(), config) {
+                        index.credentials = Some((registry.to_owned(), config.clone()));
+                    }
+                    AnyIndex::Sparse(index)
+                }
+            };
+            self.indexes.insert(registry.clone(), index);
         }
-        let index = self.index.as_mut().unwrap();
-        log::trace!("Downloading index for {name}");
-        let entry = index.krate(name)?;
-        self.cache.insert(name.to_owned(), entry.clone());
-        Ok(entry)
+        Ok(true)
     }
 }
 
+/// Look `name` up through the registry's web API, as an index entry
+///
+/// The API doesn't list dependencies, so the entry only has each version's checksum and whether
+/// it is yanked.
+fn api_krate(
+    api: &str,
+    ca_info: Option<&std::path::Path>,
+    options: &ClientOptions,
+    name: &str,
+) -> Result<Option<IndexKrate>, crate::error::CliError> {
+    #[derive(serde::Deserialize)]
+    struct Krate {
+        versions: Vec<Version>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Version {
+        num: String,
+        checksum: String,
+        yanked: bool,
+    }
+
+    let client = crate::ops::http::client(options, ca_info, IndexHttpVersion::Auto)?;
+    let url = format!("{}/api/v1/crates/{name}", api.trim_end_matches('/'));
+    log::trace!("Downloading {url}");
+    let res = client.get(&url).send()?;
+    let krate: Krate = match res.status() {
+        tame_index::external::reqwest::StatusCode::OK => serde_json::from_slice(&res.bytes()?)?,
+        tame_index::external::reqwest::StatusCode::NOT_FOUND => return Ok(None),
+        status => {
+            return Err(anyhow::format_err!("unexpected response from {url}: {status}").into())
+        }
+    };
+
+    // The API lists the newest first while the index appends
+    let lines = krate
+        .versions
+        .iter()
+        .rev()
+        .map(|v| {
+            serde_json::json!({
+                "name": name,
+                "vers": v.num,
+                "deps": [],
+                "cksum": v.checksum,
+                "features": {},
+                "yanked": v.yanked,
+            })
+            .to_string()
+        })
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(IndexKrate::from_slice(lines.join("\n").as_bytes())?))
+}
+
+enum AnyIndex {
+    Sparse(RemoteIndex),
+    Git(GitIndex),
+}
+
 pub struct RemoteIndex {
     index: tame_index::SparseIndex,
+    /// `None` for crates.io
+    url: Option<String>,
     client: tame_index::external::reqwest::blocking::Client,
+    /// What `client` speaks, with `Auto` meaning it is negotiated
+    protocol: IndexHttpVersion,
+    options: ClientOptions,
+    ca_info: Option<std::path::PathBuf>,
+    /// The registry's name and config, for looking up credentials
+    credentials: Option<(String, RegistryConfig)>,
+    /// Looked up on the first request, so cached lookups stay offline
+    auth: once_cell::unsync::OnceCell<Option<IndexAuth>>,
     lock: FileLock,
     etags: Vec<(String, String)>,
 }
 
 impl RemoteIndex {
+    /// Open the sparse index at `url`, defaulting to crates.io
     #[inline]
-    pub fn open() -> Result<Self, crate::error::CliError> {
-        let index = tame_index::SparseIndex::new(tame_index::IndexLocation::new(
-            tame_index::IndexUrl::CratesIoSparse,
-        ))?;
-        let client = tame_index::external::reqwest::blocking::ClientBuilder::new()
-            .http2_prior_knowledge()
-            .build()?;
+    pub fn open(
+        url: Option<&str>,
+        ca_info: Option<&std::path::Path>,
+        options: &ClientOptions,
+    ) -> Result<Self, crate::error::CliError> {
+        let protocol = match options.http_version {
+            // Only crates.io is known to speak HTTP/2 without negotiation
+            IndexHttpVersion::Auto if url.is_none() => IndexHttpVersion::Http2,
+            protocol => protocol,
+        };
+        let index_url = if let Some(url) = url {
+            tame_index::IndexUrl::NonCratesIo(url.into())
+        } else {
+            tame_index::IndexUrl::CratesIoSparse
+        };
+        let index = tame_index::SparseIndex::new(tame_index::IndexLocation::new(index_url))?;
+        let client = crate::ops::http::client(options, ca_info, protocol)?;
         let lock = FileLock::unlocked();
 
         Ok(Self {
             index,
+            url: url.map(ToOwned::to_owned),
             client,
+            protocol,
+            options: options.clone(),
+            ca_info: ca_info.map(ToOwned::to_owned),
+            credentials: None,
+            auth: Default::default(),
             lock,
             etags: Vec::new(),
         })
     }
 
+    /// Where the index says `.crate` files are downloaded from
+    pub(crate) fn dl(&self) -> Result<String, crate::error::CliError> {
+        let url = if let Some(url) = self.url.as_deref() {
+            url.strip_prefix("sparse+").unwrap_or(url)
+        } else {
+            return Ok(CRATES_IO_DL.to_owned());
+        };
+        let config = self.get(&format!("{}/config.json", url.trim_end_matches('/')))?;
+        config_dl(&config)
+    }
+
+    /// GET `url` as the index would be, with its credentials
+    pub(crate) fn get(&self, url: &str) -> Result<Vec<u8>, crate::error::CliError> {
+        let req = self.client.get(url);
+        let res = authorize!(req, self.auth()?).send()?;
+        let status = res.status();
+        if !status.is_success() {
+            return Err(anyhow::format_err!("unexpected response from {url}: {status}").into());
+        }
+        Ok(res.bytes()?.to_vec())
+    }
+
+    /// Credentials for the index, if it requires them
+    fn auth(&self) -> Result<Option<&IndexAuth>, crate::error::CliError> {
+        let auth = self.auth.get_or_try_init(|| {
+            let (registry, config, url) = match (&self.credentials, self.url.as_deref()) {
+                (Some((registry, config)), Some(url)) => (registry, config, url),
+                _ => return Ok::<_, crate::error::CliError>(None),
+            };
+            if !self.requires_auth(url) {
+                return Ok(None);
+            }
+            let auth = crate::ops::credentials::index_auth(registry, config, url)?;
+            if auth.is_none() {
+                let _ = crate::ops::shell::warn(format!(
+                    "the index for `{registry}` requires authentication; set `token-env` or `credential-provider`, `cargo login`, or add it to `.netrc`"
+                ));
+            }
+            Ok(auth)
+        })?;
+        Ok(auth.as_ref())
+    }
+
+    /// Whether the index is private, like cargo's `auth-required` registries
+    ///
+    /// Unreachable indexes are assumed to be public, leaving the failure to the lookups.
+    fn requires_auth(&self, url: &str) -> bool {
+        let url = url.strip_prefix("sparse+").unwrap_or(url);
+        let config_url = format!("{}/config.json", url.trim_end_matches('/'));
+        let res = match self.client.get(&config_url).send() {
+            Ok(res) => res,
+            Err(err) => {
+                log::debug!("failed to read {config_url}: {err}");
+                return false;
+            }
+        };
+        match res.status() {
+            tame_index::external::reqwest::StatusCode::UNAUTHORIZED
+            | tame_index::external::reqwest::StatusCode::FORBIDDEN => true,
+            _ => res
+                .bytes()
+                .ok()
+                .and_then(|body| serde_json::from_slice::<serde_json::Value>(&body).ok())
+                .and_then(|config| config.get("auth-required")?.as_bool())
+                .unwrap_or(false),
+        }
+    }
+
+    /// `name`'s entry in cargo's local cache, if cargo fetched it within `max_age`
+    pub(crate) fn cached_krate(
+        &self,
+        name: &str,
+        max_age: Option<std::time::Duration>,
+    ) -> Result<Option<IndexKrate>, crate::error::CliError> {
+        let krate_name = name.try_into()?;
+        if let Some(max_age) = max_age {
+            let age = std::fs::metadata(self.index.cache_path(krate_name))
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok());
+            if !age.map(|age| age <= max_age).unwrap_or(false) {
+                return Ok(None);
+            }
+        }
+        Ok(self.index.cached_krate(krate_name, &self.lock)?)
+    }
+
     pub(crate) fn krate(
         &mut self,
         name: &str,
@@ -124,10 +668,32 @@ impl RemoteIndex {
             },
             _,
         ) = req.into_parts();
-        let mut req = self.client.request(method, uri.to_string());
-        req = req.version(version);
-        req = req.headers(headers);
-        let res = self.client.execute(req.build()?)?;
+        let uri = uri.to_string();
+        let auth = self.auth()?.cloned();
+        let res = match self.send(
+            method.clone(),
+            &uri,
+            version,
+            headers.clone(),
+            auth.as_ref(),
+        ) {
+            Ok(res) => res,
+            Err(err)
+                if self.protocol == IndexHttpVersion::Http2
+                    && self.options.http_version == IndexHttpVersion::Auto =>
+            {
+                // Some proxies only speak HTTP/1.1
+                log::debug!("HTTP/2 index request failed, retrying with HTTP/1.1: {err}");
+                self.protocol = IndexHttpVersion::Http1;
+                self.client = crate::ops::http::client(
+                    &self.options,
+                    self.ca_info.as_deref(),
+                    IndexHttpVersion::Http1,
+                )?;
+                self.send(method, &uri, version, headers, auth.as_ref())?
+            }
+            Err(err) => return Err(err.into()),
+        };
 
         // Grab the etag if it exists for future requests
         if let Some(etag) = res
@@ -161,4 +727,272 @@ impl RemoteIndex {
             .parse_remote_response(krate_name, response, false, &self.lock)
             .map_err(Into::into)
     }
+
+    /// Fetch several crates concurrently, see [`crate::ops::http::run_all`]
+    ///
+    /// Each crate gets its own result, so one failure doesn't lose the rest of the batch.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn krates(
+        &mut self,
+        names: &[String],
+    ) -> Result<
+        Vec<(String, Result<Option<IndexKrate>, crate::error::CliError>)>,
+        crate::error::CliError,
+    > {
+        let client =
+            crate::ops::http::async_client(&self.options, self.ca_info.as_deref(), self.protocol)?;
+        let auth = self.auth()?.cloned();
+        let mut krates = Vec::with_capacity(names.len());
+        let mut requests = Vec::with_capacity(names.len());
+        for name in names {
+            let req = tame_index::KrateName::try_from(name.as_str()).and_then(|krate_name| {
+                self.index.make_remote_request(krate_name, None, &self.lock)
+            });
+            match req {
+                Ok(req) => {
+                    let (parts, _) = req.into_parts();
+                    requests.push((name, parts));
+                }
+                Err(err) => krates.push((name.clone(), Err(err.into()))),
+            }
+        }
+
+        let force_http1 = self.protocol == IndexHttpVersion::Http1;
+        let requested = requests.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        let responses = crate::ops::http::run_all(requests.into_iter().map(|(_, parts)| {
+            let client = client.clone();
+            let auth = auth.clone();
+            async move {
+                let version = if force_http1 {
+                    tame_index::external::http::Version::HTTP_11
+                } else {
+                    parts.version
+                };
+                let req = client
+                    .request(parts.method, parts.uri.to_string())
+                    .version(version)
+                    .headers(parts.headers);
+                let res = authorize!(req, auth.as_ref()).send().await?;
+                let mut builder = tame_index::external::http::Response::builder()
+                    .status(res.status())
+                    .version(res.version());
+                builder
+                    .headers_mut()
+                    .unwrap()
+                    .extend(res.headers().iter().map(|(k, v)| (k.clone(), v.clone())));
+                let body = res.bytes().await?;
+                Ok::<_, tame_index::external::reqwest::Error>((builder, body))
+            }
+        }));
+
+        for (name, response) in requested.into_iter().zip(responses) {
+            let entry =
+                response
+                    .map_err(crate::error::CliError::from)
+                    .and_then(|(builder, body)| {
+                        let response = builder.body(body.to_vec()).map_err(|e| {
+                            tame_index::Error::from(tame_index::error::HttpError::from(e))
+                        })?;
+                        self.index
+                            .parse_remote_response(
+                                name.as_str().try_into()?,
+                                response,
+                                false,
+                                &self.lock,
+                            )
+                            .map_err(Into::into)
+                    });
+            krates.push((name.clone(), entry));
+        }
+        Ok(krates)
+    }
+
+    fn send(
+        &self,
+        method: tame_index::external::http::Method,
+        uri: &str,
+        version: tame_index::external::http::Version,
+        headers: tame_index::external::http::HeaderMap,
+        auth: Option<&IndexAuth>,
+    ) -> Result<
+        tame_index::external::reqwest::blocking::Response,
+        tame_index::external::reqwest::Error,
+    > {
+        let version = if self.protocol == IndexHttpVersion::Http1 {
+            tame_index::external::http::Version::HTTP_11
+        } else {
+            version
+        };
+        let req = self
+            .client
+            .request(method, uri)
+            .version(version)
+            .headers(headers);
+        let req = authorize!(req, auth).build()?;
+        self.client.execute(req)
+    }
+}
+
+/// A git-protocol index, kept as a shallow clone under the user's cache directory
+pub struct GitIndex {
+    url: String,
+    checkout: std::path::PathBuf,
+    fetched: bool,
+}
+
+impl GitIndex {
+    pub fn open(url: &str) -> Result<Self, crate::error::CliError> {
+        use sha2::Digest as _;
+
+        let url = url.strip_prefix("registry+").unwrap_or(url);
+        let hash = sha2::Sha256::digest(url.as_bytes())
+            .iter()
+            .take(8)
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        let cache_dir = dirs_next::cache_dir()
+            .ok_or_else(|| anyhow::format_err!("no cache directory for cloning `{url}`"))?;
+        let checkout = cache_dir.join("cargo-release").join("git-index").join(hash);
+        Ok(Self {
+            url: url.to_owned(),
+            checkout,
+            fetched: false,
+        })
+    }
+
+    /// Fetch the index again on the next lookup
+    pub fn invalidate(&mut self) {
+        self.fetched = false;
+    }
+
+    /// Where the index says `.crate` files are downloaded from
+    pub(crate) fn dl(&mut self) -> Result<String, crate::error::CliError> {
+        if !self.fetched {
+            self.fetch()?;
+            self.fetched = true;
+        }
+        config_dl(&std::fs::read(self.checkout.join("config.json"))?)
+    }
+
+    pub(crate) fn krate(
+        &mut self,
+        name: &str,
+    ) -> Result<Option<IndexKrate>, crate::error::CliError> {
+        if !self.fetched {
+            self.fetch()?;
+            self.fetched = true;
+        }
+
+        let path = self.checkout.join(krate_path(name));
+        let content = match std::fs::read(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Some(IndexKrate::from_slice(&content)?))
+    }
+
+    fn fetch(&self) -> Result<(), crate::error::CliError> {
+        let checkout = self.checkout.to_str().unwrap();
+        let success = if self.checkout.join(".git").exists() {
+            index_git(&[
+                "-C", checkout, "fetch", "--quiet", "--depth", "1", "origin", "HEAD",
+            ])? && index_git(&["-C", checkout, "reset", "--quiet", "--hard", "FETCH_HEAD"])?
+        } else {
+            if let Some(parent) = self.checkout.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            index_git(&["clone", "--quiet", "--depth", "1", &self.url, checkout])?
+        };
+        if !success {
+            return Err(anyhow::format_err!("failed to fetch index `{}`", self.url).into());
+        }
+        Ok(())
+    }
+}
+
+/// Run `git` on an index checkout, ignoring any `GIT_DIR` meant for the project's repository
+fn index_git(args: &[&str]) -> Result<bool, crate::error::CliError> {
+    let options = crate::ops::git::GitOptions::workspace();
+    Ok(crate::ops::git::call_git_outside(&options, args)?)
+}
+
+/// `dl` from an index's `config.json`
+fn config_dl(config: &[u8]) -> Result<String, crate::error::CliError> {
+    #[derive(serde::Deserialize)]
+    struct IndexConfig {
+        dl: String,
+    }
+
+    let config: IndexConfig = serde_json::from_slice(config)?;
+    Ok(config.dl)
+}
+
+/// The URL of the `.crate` for `name` `version`, from the index's `dl` template
+fn download_url(dl: &str, name: &str, version: &str, checksum: &str) -> String {
+    const MARKERS: &[&str] = &[
+        "{crate}",
+        "{version}",
+        "{prefix}",
+        "{lowerprefix}",
+        "{sha256-checksum}",
+    ];
+    if !MARKERS.iter().any(|marker| dl.contains(marker)) {
+        return format!("{}/{name}/{version}/download", dl.trim_end_matches('/'));
+    }
+    let prefix = match name.len() {
+        1 => "1".to_owned(),
+        2 => "2".to_owned(),
+        3 => format!("3/{}", &name[..1]),
+        _ => format!("{}/{}", &name[..2], &name[2..4]),
+    };
+    dl.replace("{crate}", name)
+        .replace("{version}", version)
+        .replace("{lowerprefix}", &prefix.to_lowercase())
+        .replace("{prefix}", &prefix)
+        .replace("{sha256-checksum}", checksum)
+}
+
+/// Where an index keeps the entry for `name`
+fn krate_path(name: &str) -> std::path::PathBuf {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => ["1", &name].iter().collect(),
+        2 => ["2", &name].iter().collect(),
+        3 => ["3", &name[..1], &name].iter().collect(),
+        _ => [&name[..2], &name[2..4], &name].iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn download_urls() {
+        assert_eq!(
+            download_url(CRATES_IO_DL, "serde", "1.0.0", "abc"),
+            "https://static.crates.io/crates/serde/1.0.0/download"
+        );
+        assert_eq!(
+            download_url(
+                "https://example.com/{prefix}/{lowerprefix}/{crate}-{version}.crate?{sha256-checksum}",
+                "Abcd",
+                "0.1.0",
+                "abc"
+            ),
+            "https://example.com/Ab/cd/ab/cd/Abcd-0.1.0.crate?abc"
+        );
+    }
+
+    #[test]
+    fn krate_path_layout() {
+        assert_eq!(krate_path("a"), std::path::Path::new("1/a"));
+        assert_eq!(krate_path("ab"), std::path::Path::new("2/ab"));
+        assert_eq!(krate_path("abc"), std::path::Path::new("3/a/abc"));
+        assert_eq!(
+            krate_path("Cargo-Release"),
+            std::path::Path::new("ca/rg/cargo-release")
+        );
+    }
 }
@@ -0,0 +1,244 @@
+//! Reuse `cargo metadata` across steps and consecutive invocations
+//!
+//! Results are kept in memory for the rest of the process and in the cache dir for the next run,
+//! e.g. `cargo release` followed by `cargo release --execute`.  An entry is only reused while
+//! `Cargo.lock`, every local manifest, the cargo config, the `CARGO*`/`RUST*` environment, and the
+//! toolchain it was computed with are unchanged.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::CargoResult;
+
+static LOADED: once_cell::sync::Lazy<std::sync::Mutex<BTreeMap<PathBuf, CachedMetadata>>> =
+    once_cell::sync::Lazy::new(Default::default);
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+struct CachedMetadata {
+    lock_hash: Option<String>,
+    /// Hash of the environment `cargo metadata` ran in, see [`environment_hash`]
+    environment_hash: String,
+    /// Hash of each local manifest when the metadata was computed
+    manifest_hashes: BTreeMap<PathBuf, String>,
+    metadata: cargo_metadata::Metadata,
+}
+
+impl CachedMetadata {
+    fn new(manifest_path: &Path, metadata: cargo_metadata::Metadata) -> Self {
+        let manifest_hashes = local_manifests(&metadata)
+            .into_iter()
+            .filter_map(|path| hash_file(&path).map(|hash| (path, hash)))
+            .collect();
+        Self {
+            lock_hash: find_lock(manifest_path).and_then(|path| hash_file(&path)),
+            environment_hash: environment_hash(manifest_path),
+            manifest_hashes,
+            metadata,
+        }
+    }
+
+    fn is_fresh(&self, manifest_path: &Path) -> bool {
+        self.lock_hash == find_lock(manifest_path).and_then(|path| hash_file(&path))
+            && self.environment_hash == environment_hash(manifest_path)
+            && self
+                .manifest_hashes
+                .iter()
+                .all(|(path, hash)| hash_file(path).as_ref() == Some(hash))
+    }
+}
+
+/// `cargo metadata` for the workspace at `manifest_path`, including optional dependencies
+pub fn workspace(manifest_path: Option<&Path>) -> CargoResult<cargo_metadata::Metadata> {
+    let cache_dir = dirs_next::cache_dir().map(|d| d.join("cargo-release").join("metadata"));
+    workspace_cached_in(manifest_path, cache_dir.as_deref())
+}
+
+fn workspace_cached_in(
+    manifest_path: Option<&Path>,
+    cache_dir: Option<&Path>,
+) -> CargoResult<cargo_metadata::Metadata> {
+    let cwd = std::env::current_dir()?;
+    let manifest_path = match manifest_path {
+        Some(path) => cwd.join(path),
+        None => {
+            if let Some(path) = cwd
+                .ancestors()
+                .map(|dir| dir.join("Cargo.toml"))
+                .find(|path| path.is_file())
+            {
+                path
+            } else {
+                // Let cargo report the missing manifest
+                return exec(None);
+            }
+        }
+    };
+
+    let mut loaded = LOADED.lock().unwrap();
+    if let Some(cached) = loaded.get(&manifest_path) {
+        if cached.is_fresh(&manifest_path) {
+            return Ok(cached.metadata.clone());
+        }
+    }
+    if let Some(cached) = read_cache(cache_dir, &manifest_path) {
+        if cached.is_fresh(&manifest_path) {
+            log::debug!("reusing cached metadata for {}", manifest_path.display());
+            let metadata = cached.metadata.clone();
+            loaded.insert(manifest_path, cached);
+            return Ok(metadata);
+        }
+    }
+
+    let metadata = exec(Some(&manifest_path))?;
+    // `cargo metadata` may have just updated `Cargo.lock`, so hash after running it
+    let cached = CachedMetadata::new(&manifest_path, metadata.clone());
+    write_cache(cache_dir, &manifest_path, &cached);
+    loaded.insert(manifest_path, cached);
+    Ok(metadata)
+}
+
+fn exec(manifest_path: Option<&Path>) -> CargoResult<cargo_metadata::Metadata> {
+    let mut cmd = cargo_metadata::MetadataCommand::new();
+    if let Some(manifest_path) = manifest_path {
+        cmd.manifest_path(manifest_path);
+    }
+    // When evaluating dependency ordering, we need to consider optional dependencies
+    cmd.features(cargo_metadata::CargoOpt::AllFeatures);
+    Ok(cmd.exec()?)
+}
+
+/// Manifests whose edits can change the metadata: the workspace's and any path dependencies'
+fn local_manifests(metadata: &cargo_metadata::Metadata) -> Vec<PathBuf> {
+    let mut manifests = vec![metadata
+        .workspace_root
+        .join("Cargo.toml")
+        .into_std_path_buf()];
+    manifests.extend(
+        metadata
+            .packages
+            .iter()
+            .filter(|pkg| pkg.source.is_none())
+            .map(|pkg| pkg.manifest_path.clone().into_std_path_buf()),
+    );
+    manifests.sort();
+    manifests.dedup();
+    manifests
+}
+
+fn find_lock(manifest_path: &Path) -> Option<PathBuf> {
+    manifest_path
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.is_file())
+}
+
+/// Everything outside the workspace that `cargo metadata` depends on
+///
+/// - `CARGO*` and `RUST*` environment variables, like `CARGO_HOME` or `RUSTUP_TOOLCHAIN`
+/// - the cargo config files that apply to the workspace
+/// - the toolchain, by `cargo --version`
+fn environment_hash(manifest_path: &Path) -> String {
+    use sha2::Digest as _;
+
+    let mut hasher = sha2::Sha256::new();
+    let mut vars = std::env::vars_os()
+        .filter(|(key, _)| {
+            let key = key.to_string_lossy();
+            key.starts_with("CARGO") || key.starts_with("RUST")
+        })
+        .collect::<Vec<_>>();
+    vars.sort();
+    for (key, value) in vars {
+        hasher.update(key.as_encoded_bytes());
+        hasher.update(b"=");
+        hasher.update(value.as_encoded_bytes());
+        hasher.update(b"\0");
+    }
+    let dir = manifest_path.parent().unwrap_or(manifest_path);
+    for path in crate::ops::credentials::cargo_config_files(dir) {
+        hasher.update(path.display().to_string().as_bytes());
+        hasher.update(hash_file(&path).unwrap_or_default().as_bytes());
+    }
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    if let Ok(output) = std::process::Command::new(cargo)
+        .arg("--version")
+        .current_dir(dir)
+        .output()
+    {
+        hasher.update(&output.stdout);
+    }
+    to_hex(&hasher.finalize())
+}
+
+fn hash_file(path: &Path) -> Option<String> {
+    use sha2::Digest as _;
+
+    let content = std::fs::read(path).ok()?;
+    Some(to_hex(&sha2::Sha256::digest(content)))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn cache_path(cache_dir: Option<&Path>, manifest_path: &Path) -> Option<PathBuf> {
+    use sha2::Digest as _;
+
+    let hash = sha2::Sha256::digest(manifest_path.display().to_string().as_bytes());
+    cache_dir.map(|d| d.join(format!("{}.json", to_hex(&hash[..8]))))
+}
+
+/// A missing or unreadable cache is treated as a miss
+fn read_cache(cache_dir: Option<&Path>, manifest_path: &Path) -> Option<CachedMetadata> {
+    let content = std::fs::read(cache_path(cache_dir, manifest_path)?).ok()?;
+    serde_json::from_slice(&content).ok()
+}
+
+/// Caching is best-effort, failing to write only costs the next run time
+fn write_cache(cache_dir: Option<&Path>, manifest_path: &Path, cached: &CachedMetadata) {
+    let path = if let Some(path) = cache_path(cache_dir, manifest_path) {
+        path
+    } else {
+        return;
+    };
+    let content = if let Ok(content) = serde_json::to_vec(cached) {
+        content
+    } else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(err) = std::fs::write(&path, content) {
+        log::debug!("failed to cache metadata at {}: {}", path.display(), err);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use assert_fs::prelude::*;
+
+    #[test]
+    fn invalidated_by_manifest_edit() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.copy_from("tests/fixtures/simple", &["**"]).unwrap();
+        let manifest_path = temp.child("Cargo.toml");
+        let cache_dir = temp.child("cache");
+
+        let meta = workspace_cached_in(Some(manifest_path.path()), Some(cache_dir.path())).unwrap();
+        assert_eq!(meta.packages[0].version.to_string(), "0.1.0");
+        assert!(cache_path(Some(cache_dir.path()), manifest_path.path())
+            .unwrap()
+            .is_file());
+
+        crate::ops::cargo::set_package_version(manifest_path.path(), "2.0.0", false).unwrap();
+
+        let meta = workspace_cached_in(Some(manifest_path.path()), Some(cache_dir.path())).unwrap();
+        assert_eq!(meta.packages[0].version.to_string(), "2.0.0");
+
+        temp.close().unwrap();
+    }
+}
@@ -1,9 +1,33 @@
 pub mod cargo;
+pub mod changelog;
 pub mod cmd;
+pub mod crates_io;
+pub mod credentials;
+pub mod docs_rs;
+pub mod forge;
+pub mod fragment;
 pub mod git;
+pub mod http;
 pub mod index;
+pub mod metadata;
+#[cfg(feature = "notify")]
+pub mod notify;
+pub mod rate_limit;
+pub mod registry_status;
+pub mod release_manifest;
+pub mod release_record;
 pub mod replace;
 pub mod shell;
 pub mod version;
+pub mod webhook;
 
 pub(crate) mod diff;
+
+pub(crate) const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_REPOSITORY"),
+    ")"
+);
@@ -0,0 +1,134 @@
+//! Release announcements outside of the forge
+
+use crate::config::{EmailNotify, MatrixNotify, SmtpTls};
+use crate::error::CargoResult;
+
+/// Send an announcement through the `[notify.email]` SMTP server
+pub fn send_email(config: &EmailNotify, subject: &str, body: &str) -> CargoResult<()> {
+    use lettre::Transport as _;
+
+    let server = config
+        .server
+        .as_deref()
+        .ok_or_else(|| anyhow::format_err!("`notify.email.server` is not set"))?;
+    let from = config
+        .from
+        .as_deref()
+        .ok_or_else(|| anyhow::format_err!("`notify.email.from` is not set"))?;
+    if config.to().is_empty() {
+        anyhow::bail!("`notify.email.to` is empty");
+    }
+
+    let mut message = lettre::Message::builder()
+        .from(from.parse()?)
+        .subject(subject)
+        .header(lettre::message::header::ContentType::TEXT_PLAIN);
+    for to in config.to() {
+        message = message.to(to.parse()?);
+    }
+    let message = message.body(body.to_owned())?;
+
+    let mut transport = match config.tls.unwrap_or_default() {
+        SmtpTls::Starttls => lettre::SmtpTransport::starttls_relay(server)?,
+        SmtpTls::Tls => lettre::SmtpTransport::relay(server)?,
+        SmtpTls::None => lettre::SmtpTransport::builder_dangerous(server),
+    };
+    if let Some(port) = config.port {
+        transport = transport.port(port);
+    }
+    let username = read_env(config.username_env.as_deref())?;
+    let password = read_env(config.password_env.as_deref())?;
+    if let (Some(username), Some(password)) = (username, password) {
+        transport = transport.credentials(
+            lettre::transport::smtp::authentication::Credentials::new(username, password),
+        );
+    }
+
+    transport.build().send(&message)?;
+    Ok(())
+}
+
+/// Credentials are only ever read from the environment, never the config
+fn read_env(name: Option<&str>) -> CargoResult<Option<String>> {
+    let name = if let Some(name) = name {
+        name
+    } else {
+        return Ok(None);
+    };
+    std::env::var(name)
+        .map(Some)
+        .map_err(|_| anyhow::format_err!("`{name}` is not set"))
+}
+
+/// Post an announcement to the `[notify.matrix]` room
+///
+/// `body` is markdown, sent along with its HTML rendering for clients that format messages.
+pub fn send_matrix(config: &MatrixNotify, body: &str) -> CargoResult<()> {
+    let homeserver = config
+        .homeserver
+        .as_deref()
+        .ok_or_else(|| anyhow::format_err!("`notify.matrix.homeserver` is not set"))?;
+    let room_id = config
+        .room_id
+        .as_deref()
+        .ok_or_else(|| anyhow::format_err!("`notify.matrix.room-id` is not set"))?;
+    let token = read_env(Some(config.token_env()))?.unwrap_or_default();
+
+    let mut formatted_body = String::new();
+    pulldown_cmark::html::push_html(&mut formatted_body, pulldown_cmark::Parser::new(body));
+    let message = serde_json::json!({
+        "msgtype": "m.text",
+        "body": body,
+        "format": "org.matrix.custom.html",
+        "formatted_body": formatted_body,
+    });
+
+    // Only needs to be unique for this access token, to deduplicate retries
+    let txn_id = format!(
+        "cargo-release-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos()
+    );
+    let url = format!(
+        "{}/_matrix/client/v3/rooms/{}/send/m.room.message/{}",
+        homeserver.trim_end_matches('/'),
+        percent_encode(room_id),
+        txn_id
+    );
+    let res = crate::ops::http::default_client()?
+        .put(url)
+        .bearer_auth(token)
+        .header("Content-Type", "application/json")
+        .body(serde_json::to_vec(&message)?)
+        .send()?;
+    if !res.status().is_success() {
+        anyhow::bail!("unexpected response from {homeserver}: {}", res.status());
+    }
+    Ok(())
+}
+
+/// Room ids, like `!abc:example.org`, must be escaped within a path
+fn percent_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn room_id_is_escaped() {
+        assert_eq!(percent_encode("!abc:example.org"), "%21abc%3Aexample.org");
+    }
+}
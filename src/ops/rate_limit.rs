@@ -0,0 +1,197 @@
+//! Account for publishes across invocations so back-to-back releases share one allowance
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use crate::config::RateLimit;
+use crate::error::CargoResult;
+
+/// Forget publishes older than this, they no longer count against any allowance
+const MAX_AGE: u64 = 24 * 60 * 60;
+
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct PublishLog {
+    entries: Vec<PublishEntry>,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+struct PublishEntry {
+    registry: Option<String>,
+    name: String,
+    new: bool,
+    /// Seconds since the Unix epoch
+    timestamp: u64,
+}
+
+impl PublishLog {
+    fn path() -> Option<PathBuf> {
+        dirs_next::cache_dir().map(|d| d.join("cargo-release").join("publish-log.json"))
+    }
+
+    /// Load the publishes recorded by previous runs
+    ///
+    /// A missing or unreadable log is treated as empty.
+    pub fn load() -> Self {
+        if let Some(path) = Self::path() {
+            Self::read(&path)
+        } else {
+            Self::default()
+        }
+    }
+
+    fn read(path: &std::path::Path) -> Self {
+        let content = if let Ok(content) = std::fs::read(path) {
+            content
+        } else {
+            return Self::default();
+        };
+        serde_json::from_slice(&content).unwrap_or_else(|e| {
+            log::debug!("ignoring unreadable {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    /// Record a publish, saving it for later runs
+    ///
+    /// The publish counts for this run even if saving it fails.
+    pub fn record(&mut self, registry: Option<&str>, name: &str, new: bool) -> CargoResult<()> {
+        let now = now();
+        let entry = PublishEntry {
+            registry: registry.map(ToOwned::to_owned),
+            name: name.to_owned(),
+            new,
+            timestamp: now,
+        };
+        self.entries
+            .retain(|e| now.saturating_sub(e.timestamp) < MAX_AGE);
+        self.entries.push(entry.clone());
+
+        let path = if let Some(path) = Self::path() {
+            path
+        } else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Concurrent releases share the log, so add to what is on disk now
+        let _lock = tame_index::utils::flock::LockOptions::new(&path.with_extension("lock"))
+            .exclusive(false)
+            .lock(|path| {
+                log::debug!("waiting on {}", path.display());
+                Some(Duration::from_secs(60))
+            })?;
+        let mut saved = Self::read(&path);
+        saved
+            .entries
+            .retain(|e| now.saturating_sub(e.timestamp) < MAX_AGE);
+        saved.entries.push(entry);
+        std::fs::write(&path, serde_json::to_vec(&saved)?)?;
+        *self = saved;
+        Ok(())
+    }
+
+    /// How long until `count` more `new` (or existing) crates can be published to `registry`
+    pub fn wait_for(
+        &self,
+        registry: Option<&str>,
+        limit: &RateLimit,
+        new: bool,
+        count: usize,
+    ) -> Duration {
+        let (burst, interval) = limit.bucket(new);
+        let available = self.available(registry, new, burst, interval, now());
+        let missing = count as f64 - available;
+        if missing <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs((missing * interval as f64).ceil() as u64)
+        }
+    }
+
+    /// Replay the recorded publishes through a token bucket of `burst` size, refilling one token
+    /// every `interval` seconds
+    fn available(
+        &self,
+        registry: Option<&str>,
+        new: bool,
+        burst: usize,
+        interval: u64,
+        now: u64,
+    ) -> f64 {
+        let burst = burst as f64;
+        let interval = interval.max(1) as f64;
+        let mut tokens = burst;
+        let mut last = None;
+        for entry in self
+            .entries
+            .iter()
+            .filter(|e| e.registry.as_deref() == registry && e.new == new)
+        {
+            if let Some(last) = last {
+                let elapsed = entry.timestamp.saturating_sub(last) as f64;
+                tokens = burst.min(tokens + elapsed / interval);
+            }
+            tokens -= 1.0;
+            last = Some(entry.timestamp);
+        }
+        if let Some(last) = last {
+            let elapsed = now.saturating_sub(last) as f64;
+            tokens = burst.min(tokens + elapsed / interval);
+        }
+        tokens
+    }
+}
+
+/// Block until `wait` has passed, periodically reporting what's left
+pub fn pause(wait: Duration, reason: &str) {
+    let end = std::time::Instant::now() + wait;
+    loop {
+        let remaining = end.saturating_duration_since(std::time::Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        let _ = crate::ops::shell::status(
+            "Waiting",
+            format!("{} for {}", format_duration(remaining), reason),
+        );
+        std::thread::sleep(remaining.min(Duration::from_secs(30)));
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}m{:02}s", secs / 60, secs % 60)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(timestamp: u64) -> PublishEntry {
+        PublishEntry {
+            registry: None,
+            name: "foo".to_owned(),
+            new: false,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn burst_refills_over_time() {
+        let log = PublishLog {
+            entries: (0..30).map(|_| entry(1000)).collect(),
+        };
+        assert_eq!(log.available(None, false, 30, 60, 1000), 0.0);
+        assert_eq!(log.available(None, false, 30, 60, 1000 + 5 * 60), 5.0);
+        assert_eq!(log.available(None, false, 30, 60, 1000 + 60 * 60), 30.0);
+        assert_eq!(log.available(None, true, 5, 600, 1000), 5.0);
+    }
+}
@@ -0,0 +1,69 @@
+//! Whether a registry is healthy enough to publish to
+//!
+//! Status pages in the [Statuspage](https://support.atlassian.com/statuspage/) format, like
+//! crates.io's, are judged by their indicator.  Any other endpoint is healthy when it responds
+//! successfully.
+
+use crate::error::CargoResult;
+
+/// crates.io's status page API
+pub const CRATES_IO_STATUS_URL: &str = "https://status.crates.io/api/v2/status.json";
+
+/// What is wrong with the registry behind the status endpoint `url`, if anything
+pub fn problem(url: &str) -> CargoResult<Option<String>> {
+    let client = super::http::default_client()?;
+    let res = match client.get(url).send() {
+        Ok(res) => res,
+        Err(err) => return Ok(Some(format!("couldn't reach {url}: {err}"))),
+    };
+    let status = res.status();
+    if !status.is_success() {
+        return Ok(Some(format!("{url} responded with {status}")));
+    }
+    Ok(reported_problem(&res.bytes()?))
+}
+
+/// The incident a Statuspage `status.json` reports, if `body` is one
+fn reported_problem(body: &[u8]) -> Option<String> {
+    #[derive(serde::Deserialize)]
+    struct Page {
+        status: Status,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Status {
+        indicator: String,
+        #[serde(default)]
+        description: String,
+    }
+
+    let page: Page = serde_json::from_slice(body).ok()?;
+    if page.status.indicator == "none" {
+        None
+    } else if page.status.description.is_empty() {
+        Some(format!("status is `{}`", page.status.indicator))
+    } else {
+        Some(page.status.description)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn statuspage() {
+        let operational =
+            br#"{"status":{"indicator":"none","description":"All Systems Operational"}}"#;
+        assert_eq!(reported_problem(operational), None);
+
+        let degraded =
+            br#"{"status":{"indicator":"minor","description":"Partially Degraded Service"}}"#;
+        assert_eq!(
+            reported_problem(degraded).as_deref(),
+            Some("Partially Degraded Service")
+        );
+
+        assert_eq!(reported_problem(b"OK"), None);
+    }
+}
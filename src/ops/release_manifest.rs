@@ -0,0 +1,101 @@
+//! A single statement of what a release contained, for downstream mirrors to verify against
+
+use std::path::{Path, PathBuf};
+
+use crate::config::ManifestSignature;
+use crate::error::CargoResult;
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct ReleaseManifest {
+    /// The commit the release was tagged at
+    pub commit: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub umbrella_tag: Option<String>,
+    pub crates: Vec<ReleasedCrate>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct ReleasedCrate {
+    pub name: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// Of the packaged `.crate`, when it was published
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+pub fn hash_file(path: &Path) -> CargoResult<String> {
+    use sha2::Digest as _;
+
+    let content = std::fs::read(path)?;
+    Ok(sha2::Sha256::digest(content)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// Write `manifest` to `path`, signing it if requested
+///
+/// Returns the files written, the manifest first.
+pub fn write(
+    path: &Path,
+    manifest: &ReleaseManifest,
+    signature: Option<ManifestSignature>,
+    dry_run: bool,
+) -> CargoResult<Vec<PathBuf>> {
+    let mut written = vec![path.to_owned()];
+    let signature_path = signature.map(|signature| {
+        let extension = match signature {
+            ManifestSignature::Gpg => "asc",
+            ManifestSignature::Sigstore => "sigstore.json",
+        };
+        let mut signature_path = path.as_os_str().to_owned();
+        signature_path.push(".");
+        signature_path.push(extension);
+        (signature, PathBuf::from(signature_path))
+    });
+    if let Some((_, signature_path)) = &signature_path {
+        written.push(signature_path.clone());
+    }
+    if dry_run {
+        return Ok(written);
+    }
+
+    let mut content = serde_json::to_string_pretty(manifest)?;
+    content.push('\n');
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+
+    if let Some((signature, signature_path)) = signature_path {
+        let path = path.display().to_string();
+        let signature_path = signature_path.display().to_string();
+        let command = match signature {
+            ManifestSignature::Gpg => vec![
+                "gpg",
+                "--batch",
+                "--yes",
+                "--armor",
+                "--detach-sign",
+                "--output",
+                &signature_path,
+                &path,
+            ],
+            ManifestSignature::Sigstore => vec![
+                "cosign",
+                "sign-blob",
+                "--yes",
+                "--bundle",
+                &signature_path,
+                &path,
+            ],
+        };
+        if !crate::ops::cmd::call(command, false)? {
+            anyhow::bail!("failed to sign {}", path);
+        }
+    }
+
+    Ok(written)
+}
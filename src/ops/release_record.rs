@@ -0,0 +1,149 @@
+//! An in-repo history of every release, appended to as part of the release commit
+//!
+//! Tags deleted by `cargo release delete-tag` are recorded as `[[retraction]]` tables.
+
+use std::path::Path;
+
+use crate::error::CargoResult;
+
+#[derive(Clone, Debug)]
+pub struct Entry<'a> {
+    pub name: &'a str,
+    pub version: &'a str,
+    pub date: &'a str,
+    pub tag: Option<&'a str>,
+    /// The commit the release was cut from, as the release commit can't name itself
+    pub commit: &'a str,
+}
+
+#[derive(Clone, Debug)]
+pub struct Retraction<'a> {
+    pub tag: &'a str,
+    /// The commit the tag pointed to
+    pub commit: &'a str,
+    pub date: &'a str,
+    pub reason: Option<&'a str>,
+    /// Remotes the tag was deleted from
+    pub remotes: &'a [&'a str],
+}
+
+/// Append `entries` to the `[[release]]` tables at `path`, creating the file if needed
+///
+/// Existing entries and formatting are left untouched.
+pub fn append(path: &Path, entries: &[Entry<'_>], dry_run: bool) -> CargoResult<()> {
+    let content = read(path)?;
+    let content = render(path, &content, entries)?;
+    write(path, content, dry_run)
+}
+
+/// Append `retraction` as a `[[retraction]]` table at `path`, creating the file if needed
+pub fn retract(path: &Path, retraction: &Retraction<'_>, dry_run: bool) -> CargoResult<()> {
+    let mut table = toml_edit::Table::new();
+    table["tag"] = toml_edit::value(retraction.tag);
+    table["commit"] = toml_edit::value(retraction.commit);
+    table["date"] = toml_edit::value(retraction.date);
+    if let Some(reason) = retraction.reason {
+        table["reason"] = toml_edit::value(reason);
+    }
+    table["remotes"] = toml_edit::value(
+        retraction
+            .remotes
+            .iter()
+            .copied()
+            .collect::<toml_edit::Array>(),
+    );
+
+    let content = read(path)?;
+    let content = push_tables(path, &content, "retraction", vec![table])?;
+    write(path, content, dry_run)
+}
+
+fn read(path: &Path) -> CargoResult<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn write(path: &Path, content: String, dry_run: bool) -> CargoResult<()> {
+    if dry_run {
+        log::debug!("would write {}:\n{}", path.display(), content);
+    } else {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+fn render(path: &Path, content: &str, entries: &[Entry<'_>]) -> CargoResult<String> {
+    let tables = entries
+        .iter()
+        .map(|entry| {
+            let mut table = toml_edit::Table::new();
+            table["crate"] = toml_edit::value(entry.name);
+            table["version"] = toml_edit::value(entry.version);
+            table["date"] = toml_edit::value(entry.date);
+            if let Some(tag) = entry.tag {
+                table["tag"] = toml_edit::value(tag);
+            }
+            table["commit"] = toml_edit::value(entry.commit);
+            table
+        })
+        .collect();
+    push_tables(path, content, "release", tables)
+}
+
+fn push_tables(
+    path: &Path,
+    content: &str,
+    key: &str,
+    tables: Vec<toml_edit::Table>,
+) -> CargoResult<String> {
+    let mut record: toml_edit::DocumentMut = content
+        .parse()
+        .map_err(|err| anyhow::format_err!("failed to parse {}: {}", path.display(), err))?;
+    let array = record
+        .entry(key)
+        .or_insert_with(|| toml_edit::ArrayOfTables::new().into())
+        .as_array_of_tables_mut()
+        .ok_or_else(|| anyhow::format_err!("`{key}` in {} must be `[[{key}]]`", path.display()))?;
+    for table in tables {
+        array.push(table);
+    }
+    Ok(record.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn appends_to_existing() {
+        let path = Path::new("releases.toml");
+        let entry = Entry {
+            name: "foo",
+            version: "0.1.0",
+            date: "2024-01-01",
+            tag: Some("v0.1.0"),
+            commit: "abc123",
+        };
+        let first = render(path, "# Release history\n", &[entry.clone()]).unwrap();
+        let entry = Entry {
+            version: "0.2.0",
+            tag: None,
+            ..entry
+        };
+        let second = render(path, &first, &[entry]).unwrap();
+        assert!(second.starts_with("# Release history\n"));
+
+        let record: toml::Table = toml::from_str(&second).unwrap();
+        let releases = record["release"].as_array().unwrap();
+        assert_eq!(releases.len(), 2);
+        assert_eq!(releases[0]["tag"].as_str(), Some("v0.1.0"));
+        assert_eq!(releases[1]["version"].as_str(), Some("0.2.0"));
+        assert!(releases[1].get("tag").is_none());
+    }
+}
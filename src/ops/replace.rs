@@ -1,5 +1,6 @@
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
 
 use crate::config::Replace;
 use crate::error::CargoResult;
@@ -17,45 +18,284 @@ pub struct Template<'a> {
     pub version: Option<&'a str>,
     pub metadata: Option<&'a str>,
     pub crate_name: Option<&'a str>,
+    pub package_path: Option<&'a str>,
+    pub manifest_dir: Option<&'a str>,
     pub date: Option<&'a str>,
 
     pub prefix: Option<&'a str>,
     pub tag_name: Option<&'a str>,
+    pub prev_tag_name: Option<&'a str>,
+    pub next_version: Option<&'a str>,
+    pub shortlog: Option<&'a str>,
+    pub contributors: Option<&'a str>,
+    pub releases: Option<&'a str>,
+    pub changelog: Option<&'a str>,
+    pub commits: Option<&'a str>,
+    pub install: Option<&'a str>,
+    pub links: Option<&'a str>,
 }
 
 impl<'a> Template<'a> {
+    /// Substitute `{{...}}` expressions in `input`
+    ///
+    /// An expression is a variable or a function, like `env("NAME")`, followed by any number of
+    /// `| filter` stages.  Expressions that can't be evaluated are left as-is.
     pub fn render(&self, input: &str) -> String {
-        const PREV_VERSION: &str = "{{prev_version}}";
-        const PREV_METADATA: &str = "{{prev_metadata}}";
-        const VERSION: &str = "{{version}}";
-        const METADATA: &str = "{{metadata}}";
-        const CRATE_NAME: &str = "{{crate_name}}";
-        const DATE: &str = "{{date}}";
-
-        const PREFIX: &str = "{{prefix}}";
-        const TAG_NAME: &str = "{{tag_name}}";
-
-        let mut s = input.to_owned();
-        s = render_var(s, PREV_VERSION, self.prev_version);
-        s = render_var(s, PREV_METADATA, self.prev_metadata);
-        s = render_var(s, VERSION, self.version);
-        s = render_var(s, METADATA, self.metadata);
-        s = render_var(s, CRATE_NAME, self.crate_name);
-        s = render_var(s, DATE, self.date);
-
-        s = render_var(s, PREFIX, self.prefix);
-        s = render_var(s, TAG_NAME, self.tag_name);
-        s
+        static EXPR: once_cell::sync::Lazy<regex::Regex> =
+            once_cell::sync::Lazy::new(|| regex::Regex::new(r"\{\{([^{}]*)\}\}").unwrap());
+
+        EXPR.replace_all(input, |caps: &regex::Captures<'_>| {
+            if let Some(value) = self.eval(&caps[1]) {
+                value
+            } else {
+                log::debug!("Unrendered {} present in template {:?}", &caps[0], input);
+                caps[0].to_owned()
+            }
+        })
+        .into_owned()
+    }
+
+    /// Whether `input` refers to placeholder `name`, possibly with filters
+    pub fn uses(input: &str, name: &str) -> bool {
+        input.match_indices("{{").any(|(i, _)| {
+            let expr = &input[i + 2..];
+            let expr = expr.trim_start();
+            expr.starts_with(name)
+                && !expr[name.len()..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+        })
+    }
+
+    fn var(&self, name: &str) -> Option<&'a str> {
+        match name {
+            "prev_version" => self.prev_version,
+            "prev_metadata" => self.prev_metadata,
+            "version" => self.version,
+            "metadata" => self.metadata,
+            "crate_name" => self.crate_name,
+            "package_path" => self.package_path,
+            "manifest_dir" => self.manifest_dir,
+            "date" => self.date,
+            "prefix" => self.prefix,
+            "tag_name" => self.tag_name,
+            "prev_tag_name" => self.prev_tag_name,
+            "next_version" => self.next_version,
+            "shortlog" => self.shortlog,
+            "contributors" => self.contributors,
+            "releases" => self.releases,
+            "changelog" => self.changelog,
+            "commits" => self.commits,
+            "install" => self.install,
+            "links" => self.links,
+            _ => None,
+        }
+    }
+
+    fn eval(&self, expr: &str) -> Option<String> {
+        let mut stages = split_outside_quotes(expr, '|').into_iter();
+        let (name, args) = parse_call(stages.next()?)?;
+        let mut value = match (name, args.as_deref()) {
+            (name, None) => self.var(name)?.to_owned(),
+            ("env", Some([Arg::Str(var)])) => std::env::var(var).ok()?,
+            ("date", Some([Arg::Str(format)])) => {
+                let format = time::format_description::parse(format).ok()?;
+                time::OffsetDateTime::now_utc().format(&format).ok()?
+            }
+            _ => return None,
+        };
+        for stage in stages {
+            let (name, args) = parse_call(stage)?;
+            value = match (name, args.as_deref().unwrap_or_default()) {
+                ("upper", []) => value.to_uppercase(),
+                ("lower", []) => value.to_lowercase(),
+                ("trim", []) => value.trim().to_owned(),
+                ("replace", [Arg::Str(from), Arg::Str(to)]) => value.replace(from, to),
+                ("truncate", [Arg::Int(len)]) => value.chars().take(*len).collect(),
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Arg<'s> {
+    Str(&'s str),
+    Int(usize),
+}
+
+/// Parse `name` or `name(arg, ...)`
+fn parse_call(s: &str) -> Option<(&str, Option<Vec<Arg<'_>>>)> {
+    let s = s.trim();
+    let name_end = s
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(s.len());
+    let (name, rest) = s.split_at(name_end);
+    if name.is_empty() {
+        return None;
+    }
+    let rest = rest.trim();
+    if rest.is_empty() {
+        return Some((name, None));
+    }
+    let inner = rest.strip_prefix('(')?.strip_suffix(')')?.trim();
+    if inner.is_empty() {
+        return Some((name, Some(Vec::new())));
+    }
+    let args = split_outside_quotes(inner, ',')
+        .into_iter()
+        .map(|arg| {
+            let arg = arg.trim();
+            if let Some(arg) = arg.strip_prefix('"').and_then(|a| a.strip_suffix('"')) {
+                Some(Arg::Str(arg))
+            } else {
+                arg.parse().ok().map(Arg::Int)
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some((name, Some(args)))
+}
+
+fn split_outside_quotes(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == sep && !in_quotes {
+            parts.push(&s[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// The line ending a text file mostly uses
+///
+/// Files are edited as `\n`-terminated text so patterns and templates behave the same everywhere,
+/// then converted back so CRLF files stay CRLF.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn detect(text: &str) -> Self {
+        let crlf = text.matches("\r\n").count();
+        let lf = text.matches('\n').count() - crlf;
+        if lf < crlf {
+            Self::CrLf
+        } else {
+            Self::Lf
+        }
+    }
+
+    pub fn normalize(self, text: &str) -> String {
+        match self {
+            Self::Lf => text.to_owned(),
+            Self::CrLf => text.replace("\r\n", "\n"),
+        }
+    }
+
+    pub fn restore(self, text: &str) -> String {
+        match self {
+            Self::Lf => text.to_owned(),
+            Self::CrLf => text.replace('\n', "\r\n"),
+        }
+    }
+}
+
+/// Allow `path` to exceed Windows' `MAX_PATH` in deeply nested workspaces
+#[cfg(windows)]
+pub fn long_path(path: &Path) -> PathBuf {
+    const MAX_PATH: usize = 260;
+    const VERBATIM: &str = r"\\?\";
+
+    let s = path.as_os_str().to_string_lossy();
+    if s.len() < MAX_PATH || s.starts_with(VERBATIM) || !path.is_absolute() {
+        return path.to_owned();
+    }
+    // Verbatim paths are used as-is, so resolve `.` and `..` ourselves
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                resolved.pop();
+            }
+            component => resolved.push(component),
+        }
+    }
+    let resolved = resolved.as_os_str().to_string_lossy();
+    if let Some(unc) = resolved.strip_prefix(r"\\") {
+        PathBuf::from(format!(r"{VERBATIM}UNC\{unc}"))
+    } else {
+        PathBuf::from(format!("{VERBATIM}{resolved}"))
     }
 }
 
-fn render_var(mut template: String, var_name: &str, var_value: Option<&str>) -> String {
-    if let Some(var_value) = var_value {
-        template = template.replace(var_name, var_value);
-    } else if template.contains(var_name) {
-        log::debug!("Unrendered {} present in template {:?}", var_name, template);
+#[cfg(not(windows))]
+pub fn long_path(path: &Path) -> PathBuf {
+    path.to_owned()
+}
+
+/// Whether `content` looks like a binary file, by the same NUL-byte heuristic as git
+fn is_binary(content: &[u8]) -> bool {
+    const PEEK: usize = 8000;
+    content.iter().take(PEEK).any(|b| *b == 0)
+}
+
+/// Fail when `replace` matched `actual` times, outside of its `min`/`max`/`exactly`
+fn check_count(replace: &Replace, path: &Path, actual: usize) -> CargoResult<()> {
+    let pattern = replace.search.as_str();
+    let min = replace.min.or(replace.exactly).unwrap_or(1);
+    let max = replace.max.or(replace.exactly).unwrap_or(std::usize::MAX);
+    if actual < min {
+        anyhow::bail!(
+            "for `{}` in '{}', at least {} replacements expected, found {}",
+            pattern,
+            path.display(),
+            min,
+            actual
+        );
+    } else if max < actual {
+        anyhow::bail!(
+            "for `{}` in '{}', at most {} replacements expected, found {}",
+            pattern,
+            path.display(),
+            max,
+            actual
+        );
+    }
+    Ok(())
+}
+
+/// `replaces` applied to the raw bytes of a file that isn't UTF-8
+fn replace_bytes(
+    data: &[u8],
+    replaces: &[&Replace],
+    template: &Template<'_>,
+    path: &Path,
+    prerelease: bool,
+) -> CargoResult<Vec<u8>> {
+    let mut replaced = data.to_owned();
+    for replace in replaces {
+        if prerelease && !replace.prerelease {
+            log::debug!("pre-release, not replacing {}", replace.search);
+            continue;
+        }
+
+        let r = regex::bytes::RegexBuilder::new(&replace.search)
+            .multi_line(true)
+            .build()?;
+        check_count(replace, path, r.find_iter(&replaced).count())?;
+
+        let replacer = template.render(&replace.replace);
+        replaced = r.replace_all(&replaced, replacer.as_bytes()).into_owned();
     }
-    template
+    Ok(replaced)
 }
 
 pub fn do_file_replacements(
@@ -74,12 +314,48 @@ pub fn do_file_replacements(
     }
 
     for (path, replaces) in by_file {
-        let file = cwd.join(&path);
+        let file = long_path(&cwd.join(&path));
         log::debug!("processing replacements for file {}", file.display());
         if !file.exists() {
             anyhow::bail!("unable to find file {} to perform replace", file.display());
         }
-        let data = std::fs::read_to_string(&file)?;
+        let bytes = std::fs::read(&file)?;
+        if is_binary(&bytes) {
+            if let Some(replace) = replaces.iter().find(|r| !r.force) {
+                anyhow::bail!(
+                    "refusing to replace `{}` in '{}' as it looks binary, set `force = true` to allow",
+                    replace.search,
+                    path.display()
+                );
+            }
+        }
+        let original = match String::from_utf8(bytes) {
+            Ok(original) => original,
+            Err(err) if replaces.iter().all(|r| r.force) => {
+                // Forced replacements work on the bytes as-is, line endings included
+                let data = err.into_bytes();
+                let replaced = replace_bytes(&data, &replaces, template, &path, prerelease)?;
+                if data == replaced {
+                    log::trace!("{} is unchanged", file.display());
+                } else if dry_run {
+                    let _ = crate::ops::shell::status(
+                        "Replacing",
+                        format!("in {} (binary)", path.display()),
+                    );
+                } else {
+                    std::fs::write(&file, replaced)?;
+                }
+                continue;
+            }
+            Err(_) => {
+                anyhow::bail!(
+                    "unable to replace in '{}' as it is not valid UTF-8, set `force = true` to replace its bytes",
+                    path.display()
+                );
+            }
+        };
+        let line_ending = LineEnding::detect(&original);
+        let data = line_ending.normalize(&original);
         let mut replaced = data.clone();
 
         for replace in replaces {
@@ -90,27 +366,7 @@ pub fn do_file_replacements(
 
             let pattern = replace.search.as_str();
             let r = regex::RegexBuilder::new(pattern).multi_line(true).build()?;
-
-            let min = replace.min.or(replace.exactly).unwrap_or(1);
-            let max = replace.max.or(replace.exactly).unwrap_or(std::usize::MAX);
-            let actual = r.find_iter(&replaced).count();
-            if actual < min {
-                anyhow::bail!(
-                    "for `{}` in '{}', at least {} replacements expected, found {}",
-                    pattern,
-                    path.display(),
-                    min,
-                    actual
-                );
-            } else if max < actual {
-                anyhow::bail!(
-                    "for `{}` in '{}', at most {} replacements expected, found {}",
-                    pattern,
-                    path.display(),
-                    max,
-                    actual
-                );
-            }
+            check_count(replace, &path, r.find_iter(&replaced).count())?;
 
             let to_replace = replace.replace.as_str();
             let replacer = template.render(to_replace);
@@ -134,7 +390,7 @@ pub fn do_file_replacements(
                         crate::ops::shell::status("Replacing", format!("in {}", path.display()));
                 }
             } else {
-                std::fs::write(&file, replaced)?;
+                std::fs::write(&file, line_ending.restore(&replaced))?;
             }
         } else {
             log::trace!("{} is unchanged", file.display());
@@ -142,3 +398,76 @@ pub fn do_file_replacements(
     }
     Ok(true)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_helpers() {
+        let template = Template {
+            crate_name: Some("my-crate"),
+            version: Some("1.2.3"),
+            ..Default::default()
+        };
+        assert_eq!(
+            template.render("{{crate_name}} v{{ version }}"),
+            "my-crate v1.2.3"
+        );
+        assert_eq!(template.render("{{crate_name | upper}}"), "MY-CRATE");
+        assert_eq!(
+            template.render(r#"{{ version | replace(".", "_") }}"#),
+            "1_2_3"
+        );
+        assert_eq!(
+            template.render("{{crate_name | truncate(2) | upper}}"),
+            "MY"
+        );
+        assert_eq!(
+            template.render(r#"{{env("CARGO_PKG_NAME")}}"#),
+            "cargo-release"
+        );
+        assert_eq!(template.render(r#"{{date("[year]")}}"#).len(), 4);
+        // Unknown or unset expressions are left for the user to notice
+        assert_eq!(
+            template.render("{{tag_name}} {{nope()}}"),
+            "{{tag_name}} {{nope()}}"
+        );
+    }
+
+    #[test]
+    fn forced_replace_in_binary() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let path = temp.path().join("firmware.bin");
+        std::fs::write(&path, b"\xff\x00VERSION=0.1.0\x00").unwrap();
+        let replace = Replace {
+            file: PathBuf::from("firmware.bin"),
+            search: "VERSION=[0-9.]+".to_owned(),
+            replace: "VERSION={{version}}".to_owned(),
+            min: None,
+            max: None,
+            exactly: None,
+            prerelease: false,
+            force: true,
+        };
+        let template = Template {
+            version: Some("0.2.0"),
+            ..Default::default()
+        };
+
+        do_file_replacements(&[replace], &template, temp.path(), false, false, false).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"\xff\x00VERSION=0.2.0\x00");
+    }
+
+    #[test]
+    fn line_ending_round_trip() {
+        let crlf = "# Changelog\r\n\r\n## [Unreleased]\r\n";
+        let ending = LineEnding::detect(crlf);
+        assert_eq!(ending, LineEnding::CrLf);
+        let normalized = ending.normalize(crlf);
+        assert_eq!(normalized, "# Changelog\n\n## [Unreleased]\n");
+        assert_eq!(ending.restore(&normalized), crlf);
+
+        assert_eq!(LineEnding::detect("a\nb\r\nc\n"), LineEnding::Lf);
+    }
+}
@@ -184,6 +184,21 @@ pub fn upgrade_requirement(req: &str, version: &semver::Version) -> CargoResult<
     }
 }
 
+/// Requirement that only matches `version`
+pub fn pin_requirement(version: &semver::Version) -> String {
+    let mut version = version.clone();
+    // Build metadata is ignored when matching requirements
+    version.build = semver::BuildMetadata::EMPTY;
+    format!("={version}")
+}
+
+/// Requirement that matches `version` and the releases compatible with it
+pub fn caret_requirement(version: &semver::Version) -> String {
+    let mut version = version.clone();
+    version.build = semver::BuildMetadata::EMPTY;
+    version.to_string()
+}
+
 fn set_comparator(
     mut pred: semver::Comparator,
     version: &semver::Version,
@@ -300,6 +315,16 @@ mod test {
         }
     }
 
+    mod pin_requirement {
+        use super::*;
+
+        #[test]
+        fn strips_metadata() {
+            let version = semver::Version::parse("1.2.3-alpha.1+git.123456").unwrap();
+            assert_eq!(pin_requirement(&version), "=1.2.3-alpha.1");
+        }
+    }
+
     mod upgrade_requirement {
         use super::*;
 
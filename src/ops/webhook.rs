@@ -0,0 +1,44 @@
+//! Ask an external policy engine whether a release may go ahead
+
+use crate::error::CargoResult;
+
+/// The policy engine's answer
+#[derive(Clone, Debug, PartialEq, Eq, serde::Deserialize)]
+pub struct Decision {
+    pub allow: bool,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// POST `plan` as JSON to `url` and wait for the [`Decision`]
+///
+/// Any unsuccessful response is a denial.
+pub fn ask(
+    url: &str,
+    token: Option<&str>,
+    timeout: Option<std::time::Duration>,
+    plan: &impl serde::Serialize,
+) -> CargoResult<Decision> {
+    let mut req = super::http::default_client()?
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(serde_json::to_vec(plan)?);
+    if let Some(token) = token {
+        req = req.bearer_auth(token);
+    }
+    if let Some(timeout) = timeout {
+        req = req.timeout(timeout);
+    }
+    let res = req.send()?;
+    let status = res.status();
+    if !status.is_success() {
+        return Ok(Decision {
+            allow: false,
+            reason: Some(format!("{url} responded with {status}")),
+        });
+    }
+    let body = res.bytes()?;
+    let decision = serde_json::from_slice(&body)
+        .map_err(|e| anyhow::format_err!("unrecognized response from {url}: {e}"))?;
+    Ok(decision)
+}
@@ -0,0 +1,206 @@
+use std::io::Write;
+
+use crate::error::CliError;
+use crate::ops::git;
+use crate::ops::replace::{Template, NOW};
+use crate::steps::plan;
+
+/// Render a release announcement to share
+#[derive(Debug, Clone, clap::Args)]
+pub struct AnnouncementStep {
+    #[command(flatten)]
+    manifest: clap_cargo::Manifest,
+
+    #[command(flatten)]
+    workspace: clap_cargo::Workspace,
+
+    /// Custom config file
+    #[arg(short, long = "config", value_name = "PATH")]
+    custom_config: Option<std::path::PathBuf>,
+
+    /// Ignore implicit configuration files.
+    #[arg(long)]
+    isolated: bool,
+
+    /// Write the announcement to `announcement-path`, rather than printing it
+    #[arg(short = 'x', long)]
+    execute: bool,
+}
+
+impl AnnouncementStep {
+    pub fn run(&self) -> Result<(), CliError> {
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
+        let config = self.to_config();
+        let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
+        let mut pkgs = plan::load(&config, &ws_meta)?;
+
+        let (_selected_pkgs, excluded_pkgs) = self.workspace.partition_packages(&ws_meta);
+        for excluded_pkg in excluded_pkgs {
+            let pkg = if let Some(pkg) = pkgs.get_mut(&excluded_pkg.id) {
+                pkg
+            } else {
+                // Either not in workspace or marked as `release = false`.
+                continue;
+            };
+            pkg.config.release = Some(false.into());
+        }
+
+        let pkgs = plan::plan(pkgs)?;
+
+        let (mut selected_pkgs, _excluded_pkgs): (Vec<_>, Vec<_>) = pkgs
+            .into_iter()
+            .map(|(_, pkg)| pkg)
+            .partition(|p| p.config.release());
+        if selected_pkgs.is_empty() {
+            let _ = crate::ops::shell::error("no packages selected");
+            return Err(2.into());
+        }
+
+        for pkg in &mut selected_pkgs {
+            // Once released, the last tag is this release's, so look at what came before it
+            let tag = pkg.tag_for(&pkg.initial_version);
+            if pkg.prior_tag.as_deref() == Some(tag.as_str()) {
+                let glob = globset::Glob::new(&pkg.tag_glob())?.compile_matcher();
+                pkg.prior_tag = git::find_tag_before(&pkg.package_root, &glob, &tag);
+            }
+        }
+
+        let root = ws_meta.workspace_root.as_std_path();
+        let mut announcements = vec![(
+            root.join(ws_config.announcement_path()),
+            ws_config.announcement_template().to_owned(),
+        )];
+        for extra in ws_config.announcements() {
+            let template_path = root.join(&extra.template_path);
+            let template = std::fs::read_to_string(&template_path).map_err(|err| {
+                anyhow::format_err!("failed to read {}: {}", template_path.display(), err)
+            })?;
+            announcements.push((root.join(&extra.path), template));
+        }
+
+        // Once released, the latest umbrella tag is this release's
+        let umbrella_tag = super::tag::prior_umbrella_tag(&ws_config, &ws_meta);
+        for (path, template) in &announcements {
+            let announcement = render(&ws_meta, template, &selected_pkgs, umbrella_tag.as_deref())?;
+            if self.execute {
+                let _ = crate::ops::shell::status(
+                    "Writing",
+                    format!("announcement to {}", path.display()),
+                );
+                std::fs::write(path, announcement)?;
+            } else {
+                if 1 < announcements.len() {
+                    let _ = crate::ops::shell::status(
+                        "Rendering",
+                        format!("announcement for {}", path.display()),
+                    );
+                }
+                std::io::stdout().write_all(announcement.as_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn to_config(&self) -> crate::config::ConfigArgs {
+        crate::config::ConfigArgs {
+            custom_config: self.custom_config.clone(),
+            isolated: self.isolated,
+            ..Default::default()
+        }
+    }
+}
+
+fn render(
+    ws_meta: &cargo_metadata::Metadata,
+    template: &str,
+    pkgs: &[plan::PackageRelease],
+    tag_name: Option<&str>,
+) -> Result<String, CliError> {
+    let releases = pkgs
+        .iter()
+        .map(|pkg| {
+            format!(
+                "{} {}",
+                pkg.meta.name, pkg.initial_version.bare_version_string
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let changelog = super::notify::changelog(ws_meta, pkgs);
+
+    let mut contributors = Vec::new();
+    for pkg in pkgs {
+        if let Some(names) = super::contributors(pkg, template)? {
+            for name in names.split(", ") {
+                if !name.is_empty() && !contributors.contains(&name.to_owned()) {
+                    contributors.push(name.to_owned());
+                }
+            }
+        }
+    }
+    let contributors = contributors.join(", ");
+
+    let install = pkgs
+        .iter()
+        .filter(|pkg| pkg.config.publish())
+        .map(|pkg| {
+            let command = if pkg.bin { "install" } else { "add" };
+            format!(
+                "cargo {command} {}@{}",
+                pkg.meta.name, pkg.initial_version.bare_version_string
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // Only crates.io has well-known pages
+    let links = pkgs
+        .iter()
+        .filter(|pkg| pkg.config.publish() && pkg.config.registry().is_none())
+        .map(|pkg| {
+            let name = pkg.meta.name.as_str();
+            let version = pkg.initial_version.bare_version_string.as_str();
+            format!(
+                "- [{name} {version}](https://crates.io/crates/{name}/{version}) ([docs](https://docs.rs/{name}/{version}))"
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let crate_name = match pkgs {
+        [pkg] => Some(pkg.meta.name.as_str()),
+        _ => None,
+    };
+    let version = match pkgs {
+        [pkg] => Some(pkg.initial_version.bare_version_string.as_str()),
+        _ => None,
+    };
+    let next_version = match pkgs {
+        [pkg] => Some(pkg.next_version()),
+        _ => None,
+    };
+    let prev_tag_name = match pkgs {
+        [pkg] => pkg.prior_tag.as_deref(),
+        _ => None,
+    };
+    let template_vars = Template {
+        version,
+        next_version: next_version.as_deref(),
+        crate_name,
+        tag_name,
+        prev_tag_name,
+        date: Some(NOW.as_str()),
+        releases: Some(releases.as_str()),
+        changelog: Some(changelog.as_str()),
+        contributors: Some(contributors.as_str()),
+        install: Some(install.as_str()),
+        links: Some(links.as_str()),
+        ..Default::default()
+    };
+    let mut announcement = template_vars.render(template);
+    if !announcement.ends_with('\n') {
+        announcement.push('\n');
+    }
+    Ok(announcement)
+}
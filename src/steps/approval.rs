@@ -0,0 +1,197 @@
+use std::io::Write as _;
+
+use crate::error::CliError;
+use crate::steps::plan;
+
+/// Block publishing until the workspace's `approval` conditions are met
+///
+/// In dry-run, conditions are reported without prompting anyone.
+pub fn approve(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &crate::config::Config,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<bool, CliError> {
+    let approval = if let Some(approval) = ws_config.approval() {
+        approval
+    } else {
+        return Ok(true);
+    };
+    let mut approved = true;
+
+    if let Some(file) = approval.file.as_deref() {
+        let path = ws_meta.workspace_root.as_std_path().join(file);
+        let content = std::fs::read_to_string(&path).unwrap_or_default();
+        for pkg in pkgs {
+            if !pkg.config.publish() {
+                continue;
+            }
+            let crate_name = pkg.meta.name.as_str();
+            let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+            let version = version.full_version_string.as_str();
+            let listed = content.lines().any(|line| {
+                let mut words = line.split_whitespace();
+                words.next() == Some(crate_name) && words.next() == Some(version)
+            });
+            if !listed {
+                approved = false;
+                let _ = crate::ops::shell::error(format!(
+                    "{} does not approve {} {}",
+                    path.display(),
+                    crate_name,
+                    version
+                ));
+            }
+        }
+    }
+
+    if let Some(env) = approval.env.as_deref() {
+        if std::env::var_os(env).unwrap_or_default().is_empty() {
+            approved = false;
+            let _ = crate::ops::shell::error(format!("release is not approved, `{env}` is unset"));
+        }
+    }
+
+    if approval.other_user == Some(true) {
+        if dry_run {
+            let _ = crate::ops::shell::note("another user will be asked to approve publishing");
+        } else if !approved_by_other_user()? {
+            approved = false;
+        }
+    }
+
+    if !approved && !dry_run {
+        return Err(101.into());
+    }
+
+    Ok(approved)
+}
+
+/// What is about to be released, for `approval.webhook`
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct ReleasePlan {
+    pub workspace_root: std::path::PathBuf,
+    pub branch: String,
+    pub commit: String,
+    pub push_remotes: Vec<String>,
+    pub packages: Vec<PlannedPackage>,
+    pub changes: Vec<super::version::PlannedChange>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct PlannedPackage {
+    pub name: String,
+    pub from: String,
+    pub to: String,
+    pub tag: Option<String>,
+    pub publish: bool,
+    pub registry: Option<String>,
+}
+
+/// Ask `approval.webhook` whether the release may happen, before anything is changed
+pub fn approve_plan(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &crate::config::Config,
+    selected_pkgs: &[plan::PackageRelease],
+    excluded_pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<bool, CliError> {
+    let approval = if let Some(approval) = ws_config.approval() {
+        approval
+    } else {
+        return Ok(true);
+    };
+    let url = if let Some(url) = approval.webhook.as_deref() {
+        url
+    } else {
+        return Ok(true);
+    };
+
+    let root = ws_meta.workspace_root.as_std_path();
+    let plan = ReleasePlan {
+        workspace_root: root.to_owned(),
+        branch: crate::ops::git::current_branch(root)?,
+        commit: crate::ops::git::head_id(root)?,
+        push_remotes: if ws_config.push() {
+            ws_config.push_remotes().map(ToOwned::to_owned).collect()
+        } else {
+            Vec::new()
+        },
+        packages: selected_pkgs
+            .iter()
+            .map(|pkg| PlannedPackage {
+                name: pkg.meta.name.clone(),
+                from: pkg.initial_version.full_version_string.clone(),
+                to: pkg
+                    .planned_version
+                    .as_ref()
+                    .unwrap_or(&pkg.initial_version)
+                    .full_version_string
+                    .clone(),
+                tag: pkg.planned_tag.clone(),
+                publish: pkg.config.publish(),
+                registry: pkg.config.registry().map(ToOwned::to_owned),
+            })
+            .collect(),
+        changes: super::version::planned_changes(ws_meta, selected_pkgs, excluded_pkgs),
+    };
+
+    if dry_run {
+        let _ = crate::ops::shell::note(format!(
+            "the release plan will be sent to {url} for approval"
+        ));
+        log::debug!("release plan: {}", serde_json::to_string_pretty(&plan)?);
+        return Ok(true);
+    }
+
+    let token = if let Some(env) = approval.webhook_token_env.as_deref() {
+        Some(std::env::var(env).map_err(|_| anyhow::format_err!("`{env}` is not set"))?)
+    } else {
+        None
+    };
+    let _ = crate::ops::shell::status("Requesting", format!("approval from {url}"));
+    let timeout = approval.webhook_timeout.map(|i| i.0);
+    let decision = match crate::ops::webhook::ask(url, token.as_deref(), timeout, &plan) {
+        Ok(decision) => decision,
+        Err(err) => crate::ops::webhook::Decision {
+            allow: false,
+            reason: Some(format!("{err:#}")),
+        },
+    };
+    if decision.allow {
+        let _ = crate::ops::shell::status("Approved", format!("by {url}"));
+        Ok(true)
+    } else {
+        let reason = decision
+            .reason
+            .map(|r| format!(": {r}"))
+            .unwrap_or_default();
+        let _ = crate::ops::shell::error(format!("release was denied by {url}{reason}"));
+        Err(101.into())
+    }
+}
+
+fn approved_by_other_user() -> Result<bool, CliError> {
+    let current = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default();
+
+    print!("Approver's username: ");
+    std::io::stdout().flush()?;
+    let mut approver = String::new();
+    std::io::stdin().read_line(&mut approver)?;
+    let approver = approver.trim();
+    if approver.is_empty() || approver == current {
+        let _ = crate::ops::shell::error("release must be approved by a different user");
+        return Ok(false);
+    }
+
+    // Authenticating as the approver proves they are present
+    let authenticated = crate::ops::cmd::call(["su", approver, "-c", "true"], false)?;
+    if authenticated {
+        let _ = crate::ops::shell::status("Approved", format!("by {approver}"));
+    } else {
+        let _ = crate::ops::shell::error(format!("{approver} failed to authenticate"));
+    }
+    Ok(authenticated)
+}
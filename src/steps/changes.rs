@@ -31,14 +31,7 @@ pub struct ChangesStep {
 
 impl ChangesStep {
     pub fn run(&self) -> Result<(), CliError> {
-        git::git_version()?;
-
-        let ws_meta = self
-            .manifest
-            .metadata()
-            // When evaluating dependency ordering, we need to consider optional dependencies
-            .features(cargo_metadata::CargoOpt::AllFeatures)
-            .exec()?;
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
         let config = self.to_config();
         let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
         let mut pkgs = plan::load(&config, &ws_meta)?;
@@ -110,69 +103,7 @@ pub fn changes(
         let crate_name = pkg.meta.name.as_str();
         if let Some(prior_tag_name) = &pkg.prior_tag {
             let workspace_root = ws_meta.workspace_root.as_std_path();
-            let repo = git2::Repository::discover(workspace_root)?;
-
-            let mut tag_id = None;
-            let fq_prior_tag_name = format!("refs/tags/{}", prior_tag_name);
-            repo.tag_foreach(|id, name| {
-                if name == fq_prior_tag_name.as_bytes() {
-                    tag_id = Some(id);
-                    false
-                } else {
-                    true
-                }
-            })?;
-            let tag_id = tag_id
-                .ok_or_else(|| anyhow::format_err!("could not find tag {}", prior_tag_name))?;
-
-            let head_id = repo.head()?.peel_to_commit()?.id();
-
-            let mut revwalk = repo.revwalk()?;
-            revwalk.push_range(&format!("{tag_id}..{head_id}"))?;
-
-            let mut commits = Vec::new();
-            for commit_id in revwalk {
-                let commit_id = commit_id?;
-                let commit = repo.find_commit(commit_id)?;
-                if 1 < commit.parent_count() {
-                    // Assuming merge commits can be ignored
-                    continue;
-                }
-                let parent_tree = commit.parent(0).ok().map(|c| c.tree()).transpose()?;
-                let tree = commit.tree()?;
-                let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
-
-                let mut changed_paths = std::collections::BTreeSet::new();
-                for delta in diff.deltas() {
-                    let old_path = delta.old_file().path();
-                    let new_path = delta.new_file().path();
-                    for entry_relpath in [old_path, new_path].into_iter().flatten() {
-                        for path in pkg
-                            .package_content
-                            .iter()
-                            .filter_map(|p| p.strip_prefix(workspace_root).ok())
-                        {
-                            if path == entry_relpath {
-                                changed_paths.insert(path.to_owned());
-                            }
-                        }
-                    }
-                }
-
-                if !changed_paths.is_empty() {
-                    let short_id =
-                        String::from_utf8_lossy(&repo.find_object(commit_id, None)?.short_id()?)
-                            .into_owned();
-                    commits.push(PackageCommit {
-                        id: commit_id,
-                        short_id,
-                        summary: String::from_utf8_lossy(commit.summary_bytes().unwrap_or(b""))
-                            .into_owned(),
-                        message: String::from_utf8_lossy(commit.message_bytes()).into_owned(),
-                        paths: changed_paths,
-                    });
-                }
-            }
+            let commits = package_commits(workspace_root, pkg, prior_tag_name)?;
 
             if !commits.is_empty() {
                 crate::ops::shell::status(
@@ -294,6 +225,114 @@ fn write_status(status: Option<CommitStatus>) {
     }
 }
 
+/// Commits since `prior_tag_name` touching `pkg`'s files, newest first
+pub fn package_commits(
+    workspace_root: &std::path::Path,
+    pkg: &plan::PackageRelease,
+    prior_tag_name: &str,
+) -> CargoResult<Vec<PackageCommit>> {
+    let repo = git::open(workspace_root)?;
+
+    let mut tag_id = None;
+    let fq_prior_tag_name = format!("refs/tags/{}", prior_tag_name);
+    repo.tag_foreach(|id, name| {
+        if name == fq_prior_tag_name.as_bytes() {
+            tag_id = Some(id);
+            false
+        } else {
+            true
+        }
+    })?;
+    let tag_id =
+        tag_id.ok_or_else(|| anyhow::format_err!("could not find tag {}", prior_tag_name))?;
+
+    let head_id = repo.head()?.peel_to_commit()?.id();
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_range(&format!("{tag_id}..{head_id}"))?;
+
+    let mut commits = Vec::new();
+    for commit_id in revwalk {
+        let commit_id = commit_id?;
+        let commit = repo.find_commit(commit_id)?;
+        if 1 < commit.parent_count() {
+            // Assuming merge commits can be ignored
+            continue;
+        }
+        let parent_tree = commit.parent(0).ok().map(|c| c.tree()).transpose()?;
+        let tree = commit.tree()?;
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+
+        let mut changed_paths = std::collections::BTreeSet::new();
+        for delta in diff.deltas() {
+            let old_path = delta.old_file().path();
+            let new_path = delta.new_file().path();
+            for entry_relpath in [old_path, new_path].into_iter().flatten() {
+                for path in pkg
+                    .package_content
+                    .iter()
+                    .filter_map(|p| p.strip_prefix(workspace_root).ok())
+                {
+                    if path == entry_relpath {
+                        changed_paths.insert(path.to_owned());
+                    }
+                }
+            }
+        }
+
+        if !changed_paths.is_empty() {
+            let short_id = String::from_utf8_lossy(&repo.find_object(commit_id, None)?.short_id()?)
+                .into_owned();
+            commits.push(PackageCommit {
+                id: commit_id,
+                short_id,
+                summary: String::from_utf8_lossy(commit.summary_bytes().unwrap_or(b""))
+                    .into_owned(),
+                message: String::from_utf8_lossy(commit.message_bytes()).into_owned(),
+                paths: changed_paths,
+            });
+        }
+    }
+    Ok(commits)
+}
+
+/// `commits` as Markdown, grouped under a heading per kind of change
+///
+/// Conventional commits that don't affect users, like `chore:`, are left out.
+pub fn commit_notes(commits: &[PackageCommit]) -> String {
+    let mut sections: [(&str, Vec<String>); 4] = [
+        ("Breaking Changes", Vec::new()),
+        ("Features", Vec::new()),
+        ("Fixes", Vec::new()),
+        ("Other", Vec::new()),
+    ];
+    for commit in commits {
+        let section = match commit.status() {
+            Some(CommitStatus::Breaking) => 0,
+            Some(CommitStatus::Feature) => 1,
+            Some(CommitStatus::Fix) => 2,
+            None => 3,
+            Some(CommitStatus::Ignore) => continue,
+        };
+        let description = match git_conventional::Commit::parse(&commit.message) {
+            Ok(parts) => match parts.scope() {
+                Some(scope) => format!("{}: {}", scope, parts.description()),
+                None => parts.description().to_owned(),
+            },
+            Err(_) => commit.summary.clone(),
+        };
+        sections[section]
+            .1
+            .push(format!("- {} ({})", description, commit.short_id));
+    }
+    sections
+        .iter()
+        .filter(|(_, lines)| !lines.is_empty())
+        .map(|(heading, lines)| format!("### {}\n\n{}", heading, lines.join("\n")))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 #[derive(Clone, Debug)]
 pub struct PackageCommit {
     pub id: git2::Oid,
@@ -41,19 +41,12 @@ pub struct CommitStep {
 
 impl CommitStep {
     pub fn run(&self) -> Result<(), CliError> {
-        git::git_version()?;
-
         if self.dry_run {
             let _ =
                 crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
         }
 
-        let ws_meta = self
-            .manifest
-            .metadata()
-            // When evaluating dependency ordering, we need to consider optional dependencies
-            .features(cargo_metadata::CargoOpt::AllFeatures)
-            .exec()?;
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
         let config = self.to_config();
         let ws_config = config::load_workspace_config(&config, &ws_meta)?;
         let pkgs = plan::load(&config, &ws_meta)?;
@@ -119,26 +112,166 @@ impl CommitStep {
     }
 }
 
+/// Append the releases in `pkgs` to the `release-record`, staging it for the release commit
+pub fn record_releases(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &config::Config,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let root = ws_meta.workspace_root.as_std_path();
+    let path = if let Some(path) = ws_config.release_record() {
+        root.join(path)
+    } else {
+        return Ok(());
+    };
+
+    let commit = git::head_id(root)?;
+    let entries = pkgs
+        .iter()
+        .filter_map(|pkg| {
+            let version = pkg.planned_version.as_ref()?;
+            Some(crate::ops::release_record::Entry {
+                name: pkg.meta.name.as_str(),
+                version: version.full_version_string.as_str(),
+                date: NOW.as_str(),
+                tag: pkg.planned_tag.as_deref(),
+                commit: commit.as_str(),
+            })
+        })
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let _ = crate::ops::shell::status("Recording", format!("releases in {}", path.display()));
+    crate::ops::release_record::append(&path, &entries, dry_run)?;
+    if !git::add(root, &path, dry_run)? {
+        return Err(101.into());
+    }
+    Ok(())
+}
+
 pub fn pkg_commit(pkg: &plan::PackageRelease, dry_run: bool) -> Result<(), CliError> {
+    commit_pkg(pkg, pkg.config.pre_release_commit_message(), dry_run)
+}
+
+/// Commit only the version bump, for `commit-strategy = "split"`
+pub fn pkg_version_commit(pkg: &plan::PackageRelease, dry_run: bool) -> Result<(), CliError> {
+    commit_pkg(pkg, pkg.config.version_commit_message(), dry_run)
+}
+
+fn commit_pkg(pkg: &plan::PackageRelease, commit_msg: &str, dry_run: bool) -> Result<(), CliError> {
     let cwd = &pkg.package_root;
+    let commit_msg = pkg_message(pkg, commit_msg);
+    let sign = pkg.config.sign_commit();
+    let verify = pkg.config.commit_verify();
+    if !git::commit_all(cwd, &commit_msg, sign, verify, dry_run)? {
+        // commit failed, abort release
+        return Err(101.into());
+    }
+
+    Ok(())
+}
+
+fn pkg_message(pkg: &plan::PackageRelease, commit_msg: &str) -> String {
     let crate_name = pkg.meta.name.as_str();
     let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
     let prev_version_var = pkg.initial_version.bare_version_string.as_str();
     let prev_metadata_var = pkg.initial_version.full_version.build.as_str();
     let version_var = version.bare_version_string.as_str();
     let metadata_var = version.full_version.build.as_str();
+    let next_version = pkg.next_version();
     let template = Template {
         prev_version: Some(prev_version_var),
         prev_metadata: Some(prev_metadata_var),
         version: Some(version_var),
         metadata: Some(metadata_var),
         crate_name: Some(crate_name),
+        package_path: Some(pkg.package_path.as_str()),
+        manifest_dir: pkg.package_root.to_str(),
+        prev_tag_name: pkg.prior_tag.as_deref(),
+        next_version: Some(next_version.as_str()),
         date: Some(NOW.as_str()),
         ..Default::default()
     };
-    let commit_msg = template.render(pkg.config.pre_release_commit_message());
-    let sign = pkg.config.sign_commit();
-    if !git::commit_all(cwd, &commit_msg, sign, dry_run)? {
+    template.render(commit_msg)
+}
+
+pub fn workspace_commit(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &config::Config,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let commit_msg = if pkgs.iter().all(|pkg| pkg.first_release) {
+        ws_config
+            .first_release_commit_message()
+            .unwrap_or_else(|| ws_config.pre_release_commit_message())
+    } else {
+        ws_config.pre_release_commit_message()
+    };
+    commit_workspace(ws_meta, ws_config, pkgs, commit_msg, dry_run)
+}
+
+/// Commit only the version bump, for `commit-strategy = "split"`
+pub fn workspace_version_commit(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &config::Config,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<(), CliError> {
+    commit_workspace(
+        ws_meta,
+        ws_config,
+        pkgs,
+        ws_config.version_commit_message(),
+        dry_run,
+    )
+}
+
+/// The subject lines the release commit of `pkgs` could have been given
+///
+/// Templates using `{{prev_version}}` or `{{date}}` may render differently than when the
+/// commit was made.
+pub fn release_commit_subjects(
+    ws_config: &config::Config,
+    pkgs: &[plan::PackageRelease],
+) -> Result<Vec<String>, CliError> {
+    let mut messages = vec![workspace_message(
+        ws_config,
+        pkgs,
+        ws_config.pre_release_commit_message(),
+    )?];
+    if let Some(commit_msg) = ws_config.first_release_commit_message() {
+        messages.push(workspace_message(ws_config, pkgs, commit_msg)?);
+    }
+    for pkg in pkgs {
+        messages.push(pkg_message(pkg, pkg.config.pre_release_commit_message()));
+    }
+    let subjects = messages
+        .iter()
+        .filter_map(|m| m.lines().next())
+        .map(|s| s.to_owned())
+        .collect();
+    Ok(subjects)
+}
+
+fn commit_workspace(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &config::Config,
+    pkgs: &[plan::PackageRelease],
+    commit_msg: &str,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let shared_commit_msg = workspace_message(ws_config, pkgs, commit_msg)?;
+    if !git::commit_all(
+        ws_meta.workspace_root.as_std_path(),
+        &shared_commit_msg,
+        ws_config.sign_commit(),
+        ws_config.commit_verify(),
+        dry_run,
+    )? {
         // commit failed, abort release
         return Err(101.into());
     }
@@ -146,12 +279,11 @@ pub fn pkg_commit(pkg: &plan::PackageRelease, dry_run: bool) -> Result<(), CliEr
     Ok(())
 }
 
-pub fn workspace_commit(
-    ws_meta: &cargo_metadata::Metadata,
+fn workspace_message(
     ws_config: &config::Config,
     pkgs: &[plan::PackageRelease],
-    dry_run: bool,
-) -> Result<(), CliError> {
+    commit_msg: &str,
+) -> Result<String, CliError> {
     let shared_version = super::find_shared_versions(pkgs)?;
 
     let shared_commit_msg = {
@@ -161,23 +293,44 @@ pub fn workspace_commit(
         let metadata_var = shared_version
             .as_ref()
             .map(|v| v.full_version.build.as_str());
+        let next_version = shared_version
+            .as_ref()
+            .map(|v| v.next_version(ws_config.dev_version_ext()));
         let template = Template {
             version: version_var,
             metadata: metadata_var,
+            next_version: next_version.as_deref(),
             date: Some(NOW.as_str()),
             ..Default::default()
         };
-        template.render(ws_config.pre_release_commit_message())
+        let mut commit_msg = template.render(commit_msg);
+        let body = pkgs
+            .iter()
+            .map(|pkg| {
+                let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+                let next_version = pkg.next_version();
+                let template = Template {
+                    prev_version: Some(pkg.initial_version.bare_version_string.as_str()),
+                    prev_metadata: Some(pkg.initial_version.full_version.build.as_str()),
+                    version: Some(version.bare_version_string.as_str()),
+                    metadata: Some(version.full_version.build.as_str()),
+                    crate_name: Some(pkg.meta.name.as_str()),
+                    package_path: Some(pkg.package_path.as_str()),
+                    manifest_dir: pkg.package_root.to_str(),
+                    prev_tag_name: pkg.prior_tag.as_deref(),
+                    next_version: Some(next_version.as_str()),
+                    date: Some(NOW.as_str()),
+                    ..Default::default()
+                };
+                template.render(ws_config.pre_release_commit_body())
+            })
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        if !body.is_empty() {
+            commit_msg.push_str("\n\n");
+            commit_msg.push_str(&body.join("\n"));
+        }
+        commit_msg
     };
-    if !git::commit_all(
-        ws_meta.workspace_root.as_std_path(),
-        &shared_commit_msg,
-        ws_config.sign_commit(),
-        dry_run,
-    )? {
-        // commit failed, abort release
-        return Err(101.into());
-    }
-
-    Ok(())
+    Ok(shared_commit_msg)
 }
@@ -2,8 +2,11 @@ use std::io::Write;
 
 use crate::config::load_package_config;
 use crate::config::load_workspace_config;
+use crate::config::package_config_layers;
+use crate::config::workspace_config_layers;
 use crate::config::Config;
 use crate::config::ConfigArgs;
+use crate::config::ConfigLayer;
 use crate::error::CliError;
 
 /// Dump workspace configuration
@@ -13,6 +16,10 @@ pub struct ConfigStep {
     #[arg(short, long, default_value = "-")]
     output: std::path::PathBuf,
 
+    /// Show which source supplied each value of a field, e.g. `sign-commit`
+    #[arg(long, value_name = "FIELD")]
+    explain: Option<String>,
+
     #[command(flatten)]
     manifest: clap_cargo::Manifest,
 
@@ -23,12 +30,24 @@ pub struct ConfigStep {
 impl ConfigStep {
     pub fn run(&self) -> Result<(), CliError> {
         log::trace!("initializing");
-        let ws_meta = self
-            .manifest
-            .metadata()
-            // When evaluating dependency ordering, we need to consider optional depednencies
-            .features(cargo_metadata::CargoOpt::AllFeatures)
-            .exec()?;
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
+
+        if let Some(field) = self.explain.as_deref() {
+            let mut layers = vec![ConfigLayer::new("default", Config::from_defaults())];
+            if let Some(root_id) = ws_meta.resolve.as_ref().and_then(|r| r.root.as_ref()) {
+                let pkg = ws_meta
+                    .packages
+                    .iter()
+                    .find(|p| p.id == *root_id)
+                    .expect("root should always be present");
+                layers.extend(package_config_layers(&self.config, &ws_meta, pkg)?);
+            } else {
+                layers.extend(workspace_config_layers(&self.config, &ws_meta)?);
+            }
+            let output = explain(&layers, field)?;
+            std::io::stdout().write_all(output.as_bytes())?;
+            return Ok(());
+        }
 
         let release_config =
             if let Some(root_id) = ws_meta.resolve.as_ref().and_then(|r| r.root.as_ref()) {
@@ -58,3 +77,28 @@ impl ConfigStep {
         Ok(())
     }
 }
+
+/// Report each layer's value for `field`, highest precedence first
+fn explain(layers: &[ConfigLayer], field: &str) -> Result<String, CliError> {
+    let field = field.replace('_', "-");
+    let mut values = Vec::new();
+    for layer in layers {
+        let table = toml::Value::try_from(&layer.config)?;
+        if let Some(value) = table.get(&field) {
+            values.push((layer.source.as_str(), value.clone()));
+        }
+    }
+
+    let mut output = String::new();
+    let mut values = values.into_iter().rev();
+    if let Some((source, value)) = values.next() {
+        output.push_str(&format!("{field} = {value}\n"));
+        output.push_str(&format!("    from {source}\n"));
+        for (source, value) in values {
+            output.push_str(&format!("    overrides {source}: {value}\n"));
+        }
+    } else {
+        output.push_str(&format!("{field} is unset\n"));
+    }
+    Ok(output)
+}
@@ -0,0 +1,170 @@
+use crate::error::CliError;
+use crate::ops::git;
+use crate::ops::replace::NOW;
+
+/// Delete a botched release's tags locally, on the remote, and on the forge
+#[derive(Debug, Clone, clap::Args)]
+pub struct DeleteTagStep {
+    /// Tags to delete
+    #[arg(required = true, value_name = "TAG")]
+    tags: Vec<String>,
+
+    #[command(flatten)]
+    manifest: clap_cargo::Manifest,
+
+    /// Custom config file
+    #[arg(short, long = "config", value_name = "PATH")]
+    custom_config: Option<std::path::PathBuf>,
+
+    /// Ignore implicit configuration files.
+    #[arg(long)]
+    isolated: bool,
+
+    /// Why the tags are being deleted, for the audit log
+    #[arg(long)]
+    reason: Option<String>,
+
+    /// Only delete the local tags
+    #[arg(long)]
+    local: bool,
+
+    /// Actually delete the tags. Dry-run mode is the default
+    #[arg(short = 'x', long)]
+    execute: bool,
+
+    #[arg(short = 'n', long, conflicts_with = "execute", hide = true)]
+    dry_run: bool,
+
+    /// Skip confirmation
+    #[arg(long)]
+    no_confirm: bool,
+}
+
+impl DeleteTagStep {
+    pub fn run(&self) -> Result<(), CliError> {
+        if self.dry_run {
+            let _ =
+                crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
+        }
+
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
+        let config = self.to_config();
+        let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
+        let root = ws_meta.workspace_root.as_std_path();
+        let dry_run = !self.execute;
+
+        let mut commits = Vec::new();
+        for tag in &self.tags {
+            if let Some(commit) = git::tag_commit(root, tag)? {
+                commits.push(commit);
+            } else {
+                let _ = crate::ops::shell::error(format!("tag `{tag}` doesn't exist"));
+                return Err(2.into());
+            }
+        }
+        let tags = self.tags.iter().map(|t| t.as_str()).collect::<Vec<_>>();
+
+        let remotes = if self.local {
+            Vec::new()
+        } else {
+            ws_config.push_remotes().collect::<Vec<_>>()
+        };
+
+        if !dry_run && !self.no_confirm {
+            let prompt = format!(
+                "Delete {} locally{}?",
+                tags.join(", "),
+                if remotes.is_empty() {
+                    String::new()
+                } else {
+                    format!(" and from {}", remotes.join(", "))
+                }
+            );
+            if !crate::ops::shell::confirm(&prompt) {
+                return Err(0.into());
+            }
+        }
+
+        // Forge releases first, since deleting a tag out from under one leaves it as a draft, then
+        // the remote, so a failure leaves the local tags to retry from
+        for remote in &remotes {
+            let repository_url = if let Some(url) = git::remote_url(root, remote)?
+                .as_deref()
+                .and_then(crate::ops::forge::repository_url)
+            {
+                url
+            } else {
+                continue;
+            };
+            for tag in &tags {
+                match crate::ops::forge::delete_release(&repository_url, tag, dry_run)? {
+                    Some(true) => {
+                        let _ = crate::ops::shell::status(
+                            "Deleting",
+                            format!("release {tag} on {repository_url}"),
+                        );
+                    }
+                    Some(false) => {
+                        log::debug!("no release for {tag} on {repository_url}");
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        let mut deleted_from = Vec::new();
+        for remote in &remotes {
+            let remote_tags = git::remote_tags(root, remote, &tags)?;
+            if !remote_tags.is_empty() {
+                let remote_tags = remote_tags.iter().map(|t| t.as_str()).collect::<Vec<_>>();
+                for tag in &remote_tags {
+                    let _ = crate::ops::shell::status("Deleting", format!("{tag} from {remote}"));
+                }
+                if !git::delete_remote_tags(root, remote, &remote_tags, dry_run)? {
+                    return Err(101.into());
+                }
+                deleted_from.push(*remote);
+            }
+        }
+
+        for tag in &tags {
+            let _ = crate::ops::shell::status("Deleting", format!("tag {tag}"));
+        }
+        if !git::delete_tags(root, &tags, dry_run)? {
+            return Err(101.into());
+        }
+
+        // Kept out of the repository, so recording a deletion doesn't dirty the tree
+        let audit_log =
+            dirs_next::data_local_dir().map(|d| d.join("cargo-release").join("retractions.toml"));
+        if let Some(audit_log) = audit_log {
+            let _ = crate::ops::shell::status(
+                "Recording",
+                format!("deletion in {}", audit_log.display()),
+            );
+            for (tag, commit) in tags.iter().zip(&commits) {
+                crate::ops::release_record::retract(
+                    &audit_log,
+                    &crate::ops::release_record::Retraction {
+                        tag,
+                        commit,
+                        date: NOW.as_str(),
+                        reason: self.reason.as_deref(),
+                        remotes: &deleted_from,
+                    },
+                    dry_run,
+                )?;
+            }
+        }
+
+        super::finish(false, dry_run)
+    }
+
+    fn to_config(&self) -> crate::config::ConfigArgs {
+        crate::config::ConfigArgs {
+            custom_config: self.custom_config.clone(),
+            isolated: self.isolated,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::config::{migrate_config, FILE_RELEASE_TABLES, MANIFEST_RELEASE_TABLES};
+use crate::error::CliError;
+
+/// Rewrite deprecated config fields to their current names
+#[derive(Debug, Clone, clap::Args)]
+pub struct FixConfigStep {
+    #[command(flatten)]
+    manifest: clap_cargo::Manifest,
+
+    /// Actually perform a release. Dry-run mode is the default
+    #[arg(short = 'x', long)]
+    execute: bool,
+
+    #[arg(short = 'n', long, conflicts_with = "execute", hide = true)]
+    dry_run: bool,
+}
+
+impl FixConfigStep {
+    pub fn run(&self) -> Result<(), CliError> {
+        if self.dry_run {
+            let _ =
+                crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
+        }
+
+        let ws_meta = self.manifest.metadata().no_deps().exec()?;
+        let dry_run = !self.execute;
+
+        let root = ws_meta.workspace_root.as_std_path();
+        let mut paths = std::collections::BTreeSet::new();
+        paths.insert(root.join("release.toml"));
+        paths.insert(root.join("Cargo.toml"));
+        for pkg in ws_meta.workspace_packages() {
+            let manifest_path = pkg.manifest_path.as_std_path();
+            if let Some(pkg_root) = manifest_path.parent() {
+                paths.insert(pkg_root.join("release.toml"));
+            }
+            paths.insert(manifest_path.to_owned());
+        }
+
+        let mut fixed = false;
+        for path in paths {
+            if !path.exists() {
+                continue;
+            }
+            let tables = if path.file_name() == Some(std::ffi::OsStr::new("Cargo.toml")) {
+                MANIFEST_RELEASE_TABLES
+            } else {
+                FILE_RELEASE_TABLES
+            };
+            fixed |= fix_file(&path, tables, dry_run)?;
+        }
+
+        if !fixed {
+            let _ = crate::ops::shell::status("Fixing", "no deprecated fields found");
+            return Ok(());
+        }
+
+        super::finish(false, dry_run)
+    }
+}
+
+fn fix_file(path: &std::path::Path, tables: &[&[&str]], dry_run: bool) -> Result<bool, CliError> {
+    let content = std::fs::read_to_string(path)?;
+    let mut doc: toml_edit::DocumentMut = content
+        .parse()
+        .map_err(|e| anyhow::format_err!("Failed to parse `{}`: {}", path.display(), e))?;
+    let deprecations = migrate_config(&mut doc, tables);
+    if deprecations.is_empty() {
+        return Ok(false);
+    }
+
+    let fixed = doc.to_string();
+    for deprecation in &deprecations {
+        let _ = crate::ops::shell::note(format!("{}: {}", path.display(), deprecation));
+    }
+    if dry_run {
+        let _ = crate::ops::shell::status(
+            "Fixing",
+            format!(
+                "{}\n{}",
+                path.display(),
+                crate::ops::diff::unified_diff(&content, &fixed, path, "fixed")
+            ),
+        );
+    } else {
+        let _ = crate::ops::shell::status("Fixing", path.display());
+        std::fs::write(path, fixed)?;
+    }
+
+    Ok(true)
+}
@@ -1,9 +1,10 @@
 use std::ffi::OsStr;
+use std::ffi::OsString;
 use std::path::Path;
 
+use crate::config::HookOnFailure;
 use crate::error::CliError;
 use crate::ops::cmd;
-use crate::ops::git;
 use crate::ops::replace::{Template, NOW};
 use crate::steps::plan;
 
@@ -46,7 +47,6 @@ pub struct HookStep {
 
 impl HookStep {
     pub fn run(&self) -> Result<(), CliError> {
-        git::git_version()?;
         let mut index = crate::ops::index::CratesIoIndex::new();
 
         if self.dry_run {
@@ -54,15 +54,11 @@ impl HookStep {
                 crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
         }
 
-        let ws_meta = self
-            .manifest
-            .metadata()
-            // When evaluating dependency ordering, we need to consider optional dependencies
-            .features(cargo_metadata::CargoOpt::AllFeatures)
-            .exec()?;
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
         let config = self.to_config();
         let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
         let mut pkgs = plan::load(&config, &ws_meta)?;
+        super::configure_index(&mut index, &ws_config, pkgs.values());
 
         let (_selected_pkgs, excluded_pkgs) = self.workspace.partition_packages(&ws_meta);
         for excluded_pkg in excluded_pkgs {
@@ -102,7 +98,7 @@ impl HookStep {
             }
 
             pkg.config.pre_release_replacements = Some(vec![]);
-            pkg.config.release = Some(false);
+            pkg.config.release = Some(false.into());
         }
 
         let pkgs = plan::plan(pkgs)?;
@@ -168,31 +164,30 @@ pub fn hook(
     pkg: &plan::PackageRelease,
     dry_run: bool,
 ) -> Result<(), CliError> {
-    if let Some(pre_rel_hook) = pkg.config.pre_release_hook() {
-        let cwd = &pkg.package_root;
+    if let Some(pre_rel_hooks) = pkg.config.pre_release_hook() {
         let crate_name = pkg.meta.name.as_str();
         let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
         let prev_version_var = pkg.initial_version.bare_version_string.as_str();
         let prev_metadata_var = pkg.initial_version.full_version.build.as_str();
         let version_var = version.bare_version_string.as_str();
         let metadata_var = version.full_version.build.as_str();
+        let next_version = pkg.next_version();
         let template = Template {
             prev_version: Some(prev_version_var),
             prev_metadata: Some(prev_metadata_var),
             version: Some(version_var),
             metadata: Some(metadata_var),
             crate_name: Some(crate_name),
+            package_path: Some(pkg.package_path.as_str()),
+            manifest_dir: pkg.package_root.to_str(),
+            prev_tag_name: pkg.prior_tag.as_deref(),
+            next_version: Some(next_version.as_str()),
             date: Some(NOW.as_str()),
             tag_name: pkg.planned_tag.as_deref(),
             ..Default::default()
         };
-        let pre_rel_hook = pre_rel_hook
-            .args()
-            .into_iter()
-            .map(|arg| template.render(arg))
-            .collect::<Vec<_>>();
-        log::debug!("calling pre-release hook: {:?}", pre_rel_hook);
-        let envs = maplit::btreemap! {
+        let hook_env = hook_env(pkg)?;
+        let mut envs = maplit::btreemap! {
             OsStr::new("PREV_VERSION") => prev_version_var.as_ref(),
             OsStr::new("PREV_METADATA") => prev_metadata_var.as_ref(),
             OsStr::new("NEW_VERSION") => version_var.as_ref(),
@@ -201,17 +196,205 @@ pub fn hook(
             OsStr::new("CRATE_NAME") => OsStr::new(crate_name),
             OsStr::new("WORKSPACE_ROOT") => ws_meta.workspace_root.as_os_str(),
             OsStr::new("CRATE_ROOT") => pkg.manifest_path.parent().unwrap_or_else(|| Path::new(".")).as_os_str(),
+            OsStr::new("PACKAGE_PATH") => OsStr::new(pkg.package_path.as_str()),
         };
-        // we use dry_run environmental variable to run the script
-        // so here we set dry_run=false and always execute the command.
-        if !cmd::call_with_env(pre_rel_hook, envs, cwd, false)? {
-            let _ = crate::ops::shell::error(format!(
-                "release of {} aborted by non-zero return of prerelease hook.",
-                crate_name
+        for (key, value) in &hook_env {
+            envs.entry(OsStr::new(key.as_str()))
+                .or_insert(value.as_os_str());
+        }
+
+        for (index, pre_rel_hook) in pre_rel_hooks.hooks().into_iter().enumerate() {
+            if dry_run && !pre_rel_hook.run_in_dry_run() {
+                let _ = crate::ops::shell::note(format!(
+                    "skipping prerelease hook `{}` in dry-run",
+                    pre_rel_hook.command.args().join(" ")
+                ));
+                continue;
+            }
+            let cwd = pre_rel_hook.cwd(ws_meta.workspace_root.as_std_path(), &pkg.package_root);
+            let command = pre_rel_hook
+                .argv()
+                .into_iter()
+                .map(|arg| template.render(arg))
+                .collect::<Vec<_>>();
+            let on_failure = pre_rel_hook
+                .on_failure
+                .unwrap_or_else(|| pkg.config.hook_on_failure());
+            log::debug!("calling pre-release hook: {:?}", command);
+            let log_path = pkg.config.hook_log_dir().map(|dir| {
+                ws_meta.workspace_root.as_std_path().join(dir).join(format!(
+                    "{}-{}.log",
+                    crate_name,
+                    index + 1
+                ))
+            });
+            if !run_hook(pkg, &command, &envs, &cwd, on_failure, log_path.as_deref())? {
+                let command = command.join(" ");
+                if on_failure == HookOnFailure::Warn {
+                    let _ = crate::ops::shell::warn(format!(
+                        "prerelease hook `{}` for {} failed, continuing",
+                        command, crate_name
+                    ));
+                } else {
+                    let _ = crate::ops::shell::error(format!(
+                        "release of {} aborted by non-zero return of prerelease hook `{}`.",
+                        crate_name, command
+                    ));
+                    return Err(101.into());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Run one hook command, retrying per `on_failure`
+fn run_hook(
+    pkg: &plan::PackageRelease,
+    command: &[String],
+    envs: &std::collections::BTreeMap<&OsStr, &OsStr>,
+    cwd: &Path,
+    on_failure: HookOnFailure,
+    log_path: Option<&Path>,
+) -> Result<bool, CliError> {
+    let capture = pkg.config.capture_hook_output() || log_path.is_some();
+    let timeout = pkg.config.hook_timeout();
+    let attempts = match on_failure {
+        HookOnFailure::Retry(retries) => retries + 1,
+        HookOnFailure::Abort | HookOnFailure::Warn => 1,
+    };
+    for attempt in 1..=attempts {
+        if 1 < attempt {
+            let _ = crate::ops::shell::warn(format!(
+                "retrying prerelease hook `{}` ({}/{})",
+                command.join(" "),
+                attempt - 1,
+                attempts - 1
             ));
-            return Err(101.into());
+        }
+        let success = if capture {
+            let captured = cmd::call_with_env_captured(
+                command.iter().cloned(),
+                envs.clone(),
+                pkg.config.hook_clean_env(),
+                timeout,
+                cwd,
+            )?;
+            report_output(pkg, command, &captured, log_path)?;
+            captured.success
+        } else {
+            // we use dry_run environmental variable to run the script
+            // so here we set dry_run=false and always execute the command.
+            cmd::call_with_env(
+                command.iter().cloned(),
+                envs.clone(),
+                pkg.config.hook_clean_env(),
+                timeout,
+                cwd,
+                false,
+            )?
+        };
+        if success {
+            return Ok(true);
         }
     }
+    Ok(false)
+}
+
+/// Show captured hook output as its own section and write it to `log_path`
+fn report_output(
+    pkg: &plan::PackageRelease,
+    command: &[String],
+    captured: &cmd::Captured,
+    log_path: Option<&Path>,
+) -> Result<(), CliError> {
+    let command = command.join(" ");
+    let stdout = String::from_utf8_lossy(&captured.stdout);
+    let stderr = String::from_utf8_lossy(&captured.stderr);
+
+    let _ = crate::ops::shell::status(
+        "Hook",
+        format!(
+            "`{}` for {} {}",
+            command,
+            pkg.meta.name,
+            if captured.success {
+                "succeeded"
+            } else {
+                "failed"
+            }
+        ),
+    );
+    for (name, output) in [("stdout", &stdout), ("stderr", &stderr)] {
+        if output.trim().is_empty() {
+            continue;
+        }
+        let indented = output
+            .lines()
+            .map(|l| format!("  | {l}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let _ = crate::ops::shell::note(format!("{name}:\n{indented}"));
+    }
+
+    if let Some(log_path) = log_path {
+        if let Some(parent) = log_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let log = format!(
+            "$ {command}\nsuccess: {}\n\n--- stdout ---\n{stdout}\n--- stderr ---\n{stderr}",
+            captured.success
+        );
+        std::fs::write(log_path, log)?;
+        log::debug!("wrote hook output to {}", log_path.display());
+    }
 
     Ok(())
 }
+
+/// Variables a hook needs to run at all, kept with `hook-clean-env`
+const ESSENTIAL_ENV: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "TMPDIR",
+    "TEMP",
+    "TMP",
+    "SYSTEMROOT",
+    "USERPROFILE",
+    "CARGO",
+    "CARGO_HOME",
+    "RUSTUP_HOME",
+    "RUSTUP_TOOLCHAIN",
+];
+
+/// The variables `hook-env` (and `hook-clean-env`) add to the hook's environment
+fn hook_env(pkg: &plan::PackageRelease) -> Result<Vec<(String, OsString)>, CliError> {
+    let mut env = Vec::new();
+    for (key, value) in pkg.config.hook_env() {
+        let value = if let Some(name) = value.strip_prefix("env:") {
+            std::env::var_os(name).ok_or_else(|| {
+                anyhow::format_err!(
+                    "`hook-env` for {} passes through `{}` but it is not set",
+                    key,
+                    name
+                )
+            })?
+        } else {
+            OsString::from(value)
+        };
+        env.push((key.to_owned(), value));
+    }
+    if pkg.config.hook_clean_env() {
+        for key in ESSENTIAL_ENV {
+            if env.iter().any(|(k, _)| k == key) {
+                continue;
+            }
+            if let Some(value) = std::env::var_os(key) {
+                env.push((key.to_string(), value));
+            }
+        }
+    }
+    Ok(env)
+}
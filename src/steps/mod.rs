@@ -1,16 +1,25 @@
 use std::str::FromStr;
 
+pub mod announcement;
+pub mod approval;
 pub mod changes;
 pub mod commit;
 pub mod config;
+pub mod delete_tag;
+pub mod fix_config;
 pub mod hook;
+pub mod new_fragment;
+pub mod notify;
 pub mod owner;
 pub mod plan;
+pub mod promote;
 pub mod publish;
 pub mod push;
 pub mod release;
 pub mod replace;
+pub mod status;
 pub mod tag;
+pub mod verify_tags;
 pub mod version;
 
 use crate::error::CargoResult;
@@ -20,9 +29,30 @@ pub fn verify_git_is_clean(
     path: &std::path::Path,
     dry_run: bool,
     level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    report_dirty(crate::ops::git::is_dirty(path)?, dry_run, level)
+}
+
+/// Like [`verify_git_is_clean`], for callers that also run [`verify_submodules`]
+pub fn verify_git_is_clean_except_submodules(
+    path: &std::path::Path,
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    report_dirty(
+        crate::ops::git::is_dirty_except_submodules(path)?,
+        dry_run,
+        level,
+    )
+}
+
+fn report_dirty(
+    dirty: Option<Vec<String>>,
+    dry_run: bool,
+    level: log::Level,
 ) -> Result<bool, crate::error::CliError> {
     let mut success = true;
-    if let Some(dirty) = crate::ops::git::is_dirty(path)? {
+    if let Some(dirty) = dirty {
         let _ = crate::ops::shell::log(
             level,
             format!(
@@ -40,6 +70,37 @@ pub fn verify_git_is_clean(
     Ok(success)
 }
 
+pub fn verify_submodules(
+    path: &std::path::Path,
+    ws_config: &crate::config::Config,
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+    if !ws_config.check_submodules() {
+        return Ok(success);
+    }
+
+    let problems = crate::ops::git::submodule_problems(path)?;
+    if !problems.is_empty() {
+        let _ = crate::ops::shell::log(
+            level,
+            format!(
+                "submodules differ from what is committed, please resolve before release:\n  {}",
+                problems.join("\n  ")
+            ),
+        );
+        let _ = crate::ops::shell::note("to release anyway, set `check-submodules = false`");
+        if level == log::Level::Error {
+            success = false;
+            if !dry_run {
+                return Err(101.into());
+            }
+        }
+    }
+    Ok(success)
+}
+
 pub fn verify_tags_missing(
     pkgs: &[plan::PackageRelease],
     dry_run: bool,
@@ -74,6 +135,111 @@ pub fn verify_tags_missing(
     Ok(success)
 }
 
+pub fn verify_umbrella_tag_missing(
+    ws_config: &crate::config::Config,
+    ws_meta: &cargo_metadata::Metadata,
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    if let Some(tag_name) = tag::planned_umbrella_tag(ws_config) {
+        if crate::ops::git::tag_exists(ws_meta.workspace_root.as_std_path(), &tag_name)? {
+            let _ = crate::ops::shell::log(
+                level,
+                format!("tag `{}` already exists (for the workspace)", tag_name),
+            );
+            if level == log::Level::Error {
+                success = false;
+                if !dry_run {
+                    return Err(101.into());
+                }
+            }
+        }
+    }
+
+    Ok(success)
+}
+
+/// Check `HEAD` is the previous release commit and that it and its tags can be rewritten
+pub fn verify_amendable(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &crate::config::Config,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let root = ws_meta.workspace_root.as_std_path();
+    let git_remote = ws_config.push_remote();
+    let branch = crate::ops::git::current_branch(root)?;
+    let mut invalid = false;
+
+    // Recognize the release commit by its tags, or else by its message
+    let mut tagged = false;
+    for tag_name in amended_tags(ws_config, pkgs) {
+        if crate::ops::git::tag_exists(root, &tag_name)? {
+            tagged |= crate::ops::git::is_head_at_tag(root, &tag_name)?;
+        }
+    }
+    if !tagged {
+        let subject = crate::ops::git::head_subject(root)?;
+        let subjects = commit::release_commit_subjects(ws_config, pkgs)?;
+        if !subjects.contains(&subject) {
+            let _ = crate::ops::shell::log(
+                level,
+                format!(
+                    "cannot amend HEAD, {:?} isn't the release commit, expected {:?}",
+                    subject, subjects[0]
+                ),
+            );
+            invalid = true;
+        }
+    }
+
+    if crate::ops::git::is_local_unchanged(root, git_remote, &branch)? {
+        let _ = crate::ops::shell::log(
+            level,
+            format!("cannot amend HEAD, it was pushed to {}", git_remote),
+        );
+        invalid = true;
+    }
+    for tag_name in amended_tags(ws_config, pkgs) {
+        if crate::ops::git::tag_exists(root, &tag_name)?
+            && crate::ops::git::remote_tag_exists(root, git_remote, &tag_name)?
+        {
+            let _ = crate::ops::shell::log(
+                level,
+                format!(
+                    "cannot move tag `{}`, it was pushed to {}",
+                    tag_name, git_remote
+                ),
+            );
+            invalid = true;
+        }
+    }
+    if invalid && level == log::Level::Error {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+/// Tags that `--amend` moves to the amended commit
+pub fn amended_tags(
+    ws_config: &crate::config::Config,
+    pkgs: &[plan::PackageRelease],
+) -> std::collections::BTreeSet<String> {
+    pkgs.iter()
+        .filter_map(|pkg| pkg.planned_tag.clone())
+        .chain(tag::planned_umbrella_tag(ws_config))
+        .collect()
+}
+
 pub fn verify_tags_exist(
     pkgs: &[plan::PackageRelease],
     dry_run: bool,
@@ -108,6 +274,135 @@ pub fn verify_tags_exist(
     Ok(success)
 }
 
+/// Check `Cargo.lock` changes will make it into the release commit
+pub fn verify_lock_committed(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &crate::config::Config,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    if ws_config.update_lockfile() == crate::config::UpdateLockfile::None
+        || !pkgs.iter().any(|pkg| pkg.planned_version.is_some())
+    {
+        return Ok(success);
+    }
+
+    // The release commit only picks up changes to tracked files
+    let root = ws_meta.workspace_root.as_std_path();
+    let lock_path = root.join("Cargo.lock");
+    if crate::ops::git::is_untracked(root, &lock_path)? {
+        let _ = crate::ops::shell::log(
+            level,
+            format!(
+                "{} will be updated but isn't tracked, so it wouldn't be committed with the release; commit or ignore it",
+                lock_path.display()
+            ),
+        );
+        if level == log::Level::Error {
+            success = false;
+            if !dry_run {
+                return Err(101.into());
+            }
+        }
+    }
+
+    Ok(success)
+}
+
+/// Check `Cargo.lock` is consistent with the manifests when publishing with `--locked`
+///
+/// Run before the versions are bumped, so accounts for `update-lockfile` updating it with them.
+pub fn verify_lock_current(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &crate::config::Config,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    if !pkgs
+        .iter()
+        .any(|pkg| pkg.config.publish() && pkg.config.publish_locked())
+    {
+        return Ok(success);
+    }
+
+    let bumped = pkgs.iter().any(|pkg| pkg.planned_version.is_some());
+    let updated = ws_config.update_lockfile() != crate::config::UpdateLockfile::None;
+    let manifest_path = ws_meta.workspace_root.as_std_path().join("Cargo.toml");
+    let current = match (bumped, updated) {
+        // Re-resolved along with the new versions
+        (true, true) => true,
+        // Still lists the old versions
+        (true, false) => false,
+        (false, _) => crate::ops::cargo::lock_is_current(&manifest_path)?,
+    };
+    if !current {
+        let _ = crate::ops::shell::log(
+            level,
+            format!(
+                "{} is out of date with the manifests, publishing with `--locked` would fail; update and commit it",
+                ws_meta.workspace_root.as_std_path().join("Cargo.lock").display()
+            ),
+        );
+        if level == log::Level::Error {
+            success = false;
+            if !dry_run {
+                return Err(101.into());
+            }
+        }
+    }
+
+    Ok(success)
+}
+
+/// Bring `Cargo.lock` in line with the new versions, according to `update-lockfile`
+pub fn update_lock(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &crate::config::Config,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> CargoResult<()> {
+    let policy = ws_config.update_lockfile();
+    if policy == crate::config::UpdateLockfile::None {
+        log::debug!("leaving lock file as-is");
+        return Ok(());
+    }
+
+    log::debug!("updating lock file");
+    let workspace_path = ws_meta.workspace_root.as_std_path().join("Cargo.toml");
+    if dry_run {
+        let lock_path = ws_meta.workspace_root.as_std_path().join("Cargo.lock");
+        if lock_path.exists() {
+            crate::ops::cargo::preview_lock(
+                &lock_path,
+                pkgs.iter().filter_map(|pkg| {
+                    pkg.planned_version.as_ref().map(|version| {
+                        (
+                            pkg.meta.name.as_str(),
+                            pkg.initial_version.full_version_string.as_str(),
+                            version.full_version_string.as_str(),
+                        )
+                    })
+                }),
+            )?;
+        }
+    } else if policy == crate::config::UpdateLockfile::Package {
+        crate::ops::cargo::update_lock_packages(
+            &workspace_path,
+            pkgs.iter().map(|pkg| pkg.meta.name.as_str()),
+        )?;
+    } else {
+        crate::ops::cargo::update_lock(&workspace_path)?;
+    }
+
+    Ok(())
+}
+
 pub fn verify_git_branch(
     path: &std::path::Path,
     ws_config: &crate::config::Config,
@@ -146,6 +441,86 @@ pub fn verify_git_branch(
     Ok(success)
 }
 
+pub fn verify_backport(
+    path: &std::path::Path,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let branch = crate::ops::git::current_branch(path)?;
+    let line = version_line(&branch);
+    let mut invalid = false;
+    if line.is_none() {
+        let _ = crate::ops::shell::log(
+            level,
+            format!(
+                "cannot backport from branch {:?}, expected a version line like `1.x` or `1.2.x`",
+                branch
+            ),
+        );
+        invalid = true;
+    }
+    for pkg in pkgs {
+        let crate_name = pkg.meta.name.as_str();
+        let initial = &pkg.initial_version.full_version;
+        let version = &pkg
+            .planned_version
+            .as_ref()
+            .unwrap_or(&pkg.initial_version)
+            .full_version;
+        if version.major != initial.major || version.minor != initial.minor {
+            let _ = crate::ops::shell::log(
+                level,
+                format!(
+                    "cannot backport {} {}, only patch releases of {}.{} are allowed",
+                    crate_name, version, initial.major, initial.minor
+                ),
+            );
+            invalid = true;
+        }
+        if let Some(line) = line.as_deref() {
+            let parts = [version.major, version.minor];
+            if !parts.starts_with(line) {
+                let _ = crate::ops::shell::log(
+                    level,
+                    format!(
+                        "cannot backport {} {} from branch {:?}",
+                        crate_name, version, branch
+                    ),
+                );
+                invalid = true;
+            }
+        }
+    }
+    if invalid && level == log::Level::Error {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+/// The `major[.minor]` a maintenance branch like `1.x`, `v1.2`, or `release/1.2.x` is for
+fn version_line(branch: &str) -> Option<Vec<u64>> {
+    let name = branch.rsplit('/').next().unwrap_or(branch);
+    let name = name.strip_prefix('v').unwrap_or(name);
+    let mut line = Vec::new();
+    for part in name.split('.') {
+        if part == "x" || part == "*" {
+            break;
+        }
+        line.push(part.parse::<u64>().ok()?);
+    }
+    if line.is_empty() || 2 < line.len() {
+        return None;
+    }
+    Some(line)
+}
+
 pub fn verify_if_behind(
     path: &std::path::Path,
     ws_config: &crate::config::Config,
@@ -178,93 +553,924 @@ pub fn verify_if_behind(
     Ok(success)
 }
 
-pub fn verify_monotonically_increasing(
+/// Refuse to release when `push-remote` isn't one of `allowed-push-remotes`, like from a fork
+pub fn verify_push_remote_allowed(
+    path: &std::path::Path,
+    ws_config: &crate::config::Config,
+    dry_run: bool,
+) -> Result<bool, crate::error::CliError> {
+    use itertools::Itertools;
+
+    let mut success = true;
+
+    let allowed = ws_config.allowed_push_remotes();
+    if allowed.is_empty() {
+        return Ok(success);
+    }
+
+    let mut patterns = globset::GlobSetBuilder::new();
+    for pattern in allowed {
+        patterns.add(globset::Glob::new(pattern)?);
+    }
+    let patterns = patterns.build()?;
+
+    for git_remote in ws_config.push_remotes() {
+        let url = crate::ops::git::remote_url(path, git_remote)?;
+        let url = if let Some(url) = url {
+            url
+        } else {
+            // `push-remote` may be a URL rather than the name of a remote
+            git_remote.to_owned()
+        };
+        // Rehearsals push to their own remote
+        if ws_config.rehearsal_remote() == Some(url.as_str()) {
+            continue;
+        }
+
+        if !patterns.is_match(&url) {
+            let _ = crate::ops::shell::error(format!(
+                "cannot release to {} ({}), `allowed-push-remotes` only allows {}",
+                git_remote,
+                url,
+                allowed.iter().join(", ")
+            ));
+            success = false;
+        }
+    }
+    if !success && !dry_run {
+        return Err(101.into());
+    }
+
+    Ok(success)
+}
+
+/// Fail before anything is published when the forge would reject pushing the release branch
+pub fn verify_branch_protection(
+    path: &std::path::Path,
+    ws_config: &crate::config::Config,
+    dry_run: bool,
+) -> Result<bool, crate::error::CliError> {
+    use itertools::Itertools;
+
+    let mut success = true;
+
+    if !ws_config.push() || !ws_config.check_branch_protection() {
+        return Ok(success);
+    }
+    // Only the tags are pushed, the branch is updated through a pull request
+    if ws_config.push_strategy() == crate::config::PushStrategy::TagsOnly {
+        return Ok(success);
+    }
+
+    let git_remote = ws_config.push_remote();
+    let url = crate::ops::git::remote_url(path, git_remote)?;
+    let repository_url =
+        if let Some(repository_url) = url.as_deref().and_then(crate::ops::forge::repository_url) {
+            repository_url
+        } else {
+            log::debug!("not checking branch protection, `{git_remote}` isn't a recognized remote");
+            return Ok(success);
+        };
+    let branch = crate::ops::git::current_branch(path)?;
+    let restrictions = match crate::ops::forge::push_restrictions(&repository_url, &branch) {
+        Ok(Some(restrictions)) => restrictions,
+        Ok(None) => return Ok(success),
+        Err(err) => {
+            // Don't block releases on the forge being unreachable, pushing will still tell
+            let _ = crate::ops::shell::warn(format!(
+                "could not check branch protection for {branch}: {err:#}"
+            ));
+            return Ok(success);
+        }
+    };
+    if !restrictions.is_empty() {
+        let _ = crate::ops::shell::error(format!(
+            "cannot push to {} on {} ({})",
+            branch,
+            repository_url,
+            restrictions.iter().join(", ")
+        ));
+        let _ = crate::ops::shell::note(
+            "release from a branch and merge it through a pull request, or pass `--no-push` and push the release yourself",
+        );
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+/// Make sure commits and tags can be signed before anything is changed
+pub fn verify_signing(
+    path: &std::path::Path,
+    ws_config: &crate::config::Config,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let signs = ws_config.sign_commit()
+        || ws_config.sign_tag()
+        || pkgs
+            .iter()
+            .any(|pkg| pkg.config.sign_commit() || (pkg.config.sign_tag() && pkg.config.tag()));
+    if !signs {
+        return Ok(success);
+    }
+
+    let _ = crate::ops::shell::status("Checking", "that commits and tags can be signed");
+    if let Err(err) = crate::ops::git::test_signing(path)? {
+        let _ = crate::ops::shell::error(format!("signing failed: {err}"));
+        let _ = crate::ops::shell::note(
+            "check `user.signingkey` and that your agent or smartcard is unlocked",
+        );
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+pub fn verify_monotonically_increasing(
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let mut downgrades_present = false;
+    for pkg in pkgs {
+        if let Some(version) = pkg.planned_version.as_ref() {
+            if version.full_version < pkg.initial_version.full_version {
+                let crate_name = pkg.meta.name.as_str();
+                let _ = crate::ops::shell::log(
+                    level,
+                    format!(
+                        "cannot downgrade {} from {} to {}",
+                        crate_name, version.full_version, pkg.initial_version.full_version
+                    ),
+                );
+                downgrades_present = true;
+            }
+        }
+    }
+    if downgrades_present && level == log::Level::Error {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+pub fn verify_registry_version(
+    pkgs: &[plan::PackageRelease],
+    index: &mut crate::ops::index::CratesIoIndex,
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let mut downgrades_present = false;
+    let mut republish_present = false;
+    for pkg in pkgs {
+        if !pkg.config.publish() {
+            continue;
+        }
+        let crate_name = pkg.meta.name.as_str();
+        let version = &pkg
+            .planned_version
+            .as_ref()
+            .unwrap_or(&pkg.initial_version)
+            .full_version;
+        let max_version = pkg
+            .config
+            .max_version()
+            .map(semver::Version::parse)
+            .transpose()
+            .map_err(|err| anyhow::format_err!("invalid `max-version` for {crate_name}: {err}"))?;
+
+        if let Some(max_version) = &max_version {
+            if max_version <= version {
+                let _ = crate::ops::shell::log(
+                    level,
+                    format!(
+                        "cannot release {} {}, `max-version` is {}",
+                        crate_name, version, max_version
+                    ),
+                );
+                downgrades_present = true;
+                continue;
+            }
+        }
+
+        let krate = match index.krate(pkg.config.registry(), crate_name) {
+            Ok(krate) => krate,
+            // Let dry runs work offline, publishing will catch it
+            Err(err) if dry_run => {
+                let _ = crate::ops::shell::warn(format!(
+                    "could not check published versions of {crate_name}: {err}"
+                ));
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        // Yanked versions can't be published again either
+        let published = krate
+            .map(|ik| {
+                ik.versions
+                    .iter()
+                    .filter_map(|iv| {
+                        let v = semver::Version::parse(&iv.version).ok()?;
+                        Some((v, iv.yanked))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        // Exact matches are reported as double publishes, build metadata doesn't make a version new
+        let existing = published.iter().find(|(v, _)| {
+            v != version
+                && v.major == version.major
+                && v.minor == version.minor
+                && v.patch == version.patch
+                && v.pre == version.pre
+        });
+        if published.iter().any(|(v, _)| v == version) {
+            continue;
+        }
+        if let Some((existing, yanked)) = existing {
+            // Unlike a downgrade, a backport can't allow this
+            let _ = crate::ops::shell::error(format!(
+                "cannot release {} {}, {} is already published{}",
+                crate_name,
+                version,
+                existing,
+                if *yanked { " (yanked)" } else { "" }
+            ));
+            republish_present = true;
+            continue;
+        }
+        let latest = published
+            .into_iter()
+            .filter(|(_, yanked)| !yanked)
+            .map(|(v, _)| v)
+            .filter(|v| max_version.as_ref().map(|max| v < max).unwrap_or(true))
+            .max();
+        if let Some(latest) = latest {
+            if version < &latest {
+                let _ = crate::ops::shell::log(
+                    level,
+                    format!(
+                        "cannot release {} {}, {} is already published",
+                        crate_name, version, latest
+                    ),
+                );
+                downgrades_present = true;
+            }
+        }
+    }
+    if republish_present || (downgrades_present && level == log::Level::Error) {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+pub fn verify_rust_version(
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let mut outdated = false;
+    let mut stable = None;
+    for pkg in pkgs {
+        if pkg.config.update_msrv() {
+            continue;
+        }
+        if let Some(required) = required_rust_version(pkg, &mut stable)? {
+            let crate_name = pkg.meta.name.as_str();
+            let current = pkg
+                .meta
+                .rust_version
+                .as_ref()
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "unset".to_owned());
+            let _ = crate::ops::shell::log(
+                level,
+                format!(
+                    "{}'s `rust-version` ({}) is older than {} required by `msrv-policy`",
+                    crate_name, current, required
+                ),
+            );
+            outdated = true;
+        }
+    }
+    if outdated && level == log::Level::Error {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+/// Raise `rust-version` to what `msrv-policy` requires, noting it in the changelog
+pub fn update_rust_version(
+    ws_meta: &cargo_metadata::Metadata,
+    pkg: &plan::PackageRelease,
+    dry_run: bool,
+) -> Result<(), crate::error::CliError> {
+    if !pkg.config.update_msrv() {
+        return Ok(());
+    }
+    let required = if let Some(required) = required_rust_version(pkg, &mut None)? {
+        required
+    } else {
+        return Ok(());
+    };
+
+    let crate_name = pkg.meta.name.as_str();
+    let rust_version = format!("{}.{}", required.major, required.minor);
+    let _ = crate::ops::shell::status(
+        "Upgrading",
+        format!("{} rust-version to {}", crate_name, rust_version),
+    );
+    crate::ops::cargo::set_rust_version(
+        &pkg.manifest_path,
+        &ws_meta.workspace_root.as_std_path().join("Cargo.toml"),
+        &rust_version,
+        dry_run,
+    )?;
+
+    let path = pkg.package_root.join(pkg.config.changelog_path());
+    let changelog = if let Ok(changelog) = std::fs::read_to_string(&path) {
+        changelog
+    } else {
+        log::debug!("no changelog to note the MSRV of {} in", crate_name);
+        return Ok(());
+    };
+    let note = format!("- Bumped MSRV to {}", rust_version);
+    match crate::ops::changelog::add_unreleased_note(&changelog, &note) {
+        Ok(updated) => {
+            if dry_run {
+                log::debug!(
+                    "change:\n{}",
+                    crate::ops::diff::unified_diff(&changelog, &updated, &path, "updated")
+                );
+            } else {
+                std::fs::write(&path, updated)?;
+            }
+        }
+        Err(err) => {
+            log::debug!("not noting the MSRV in {}: {}", path.display(), err);
+        }
+    }
+
+    Ok(())
+}
+
+/// The `rust-version` `msrv-policy` requires if `pkg` is behind it
+///
+/// `stable` caches the stable Rust version between packages.
+fn required_rust_version(
+    pkg: &plan::PackageRelease,
+    stable: &mut Option<semver::Version>,
+) -> CargoResult<Option<semver::Version>> {
+    let policy = if let Some(policy) = pkg.config.msrv_policy() {
+        policy
+    } else {
+        return Ok(None);
+    };
+    let lag = policy
+        .trim()
+        .strip_prefix('N')
+        .map(|lag| lag.trim())
+        .and_then(|lag| {
+            if lag.is_empty() {
+                Some(0)
+            } else {
+                lag.strip_prefix('-')?.trim().parse::<u64>().ok()
+            }
+        })
+        .ok_or_else(|| {
+            anyhow::format_err!(
+                "invalid `msrv-policy` {:?} for {}, expected `N-<minor versions>`",
+                policy,
+                pkg.meta.name
+            )
+        })?;
+
+    if stable.is_none() {
+        *stable = Some(crate::ops::cargo::stable_rust_version()?);
+    }
+    let stable = stable.as_ref().unwrap();
+    let required = semver::Version::new(stable.major, stable.minor.saturating_sub(lag), 0);
+
+    let current = pkg.meta.rust_version.as_ref();
+    if current.map(|current| current < &required).unwrap_or(true) {
+        Ok(Some(required))
+    } else {
+        Ok(None)
+    }
+}
+
+pub fn verify_changelog_updated(
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let mut stale = false;
+    for pkg in pkgs {
+        if !pkg.config.require_changelog_update() || pkg.first_release {
+            continue;
+        }
+        let crate_name = pkg.meta.name.as_str();
+        let changelog = pkg.package_root.join(pkg.config.changelog_path());
+        if !changelog.exists() {
+            let _ = crate::ops::shell::log(
+                level,
+                format!("{} has no changelog at {}", crate_name, changelog.display()),
+            );
+            stale = true;
+            continue;
+        }
+        let prior_tag_name = if let Some(prior_tag_name) = pkg.prior_tag.as_deref() {
+            prior_tag_name
+        } else {
+            log::debug!("not checking changelog for {} (no tag found)", crate_name);
+            continue;
+        };
+        let changed = crate::ops::git::changed_files(&pkg.package_root, prior_tag_name)?;
+        if let Some(changed) = changed {
+            if !changed.contains(&changelog) {
+                let _ = crate::ops::shell::log(
+                    level,
+                    format!(
+                        "{} has not been updated for {} since {}",
+                        changelog.display(),
+                        crate_name,
+                        prior_tag_name
+                    ),
+                );
+                stale = true;
+            }
+        }
+    }
+    if stale && level == log::Level::Error {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+/// Check READMEs exist without leftover placeholders, and optionally that doc links resolve
+pub fn verify_docs(
+    ws_meta: &cargo_metadata::Metadata,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    static PLACEHOLDER: once_cell::sync::Lazy<regex::Regex> =
+        once_cell::sync::Lazy::new(|| regex::Regex::new(r"\{\{\s*[a-z_]+\s*\}\}").unwrap());
+
+    let mut success = true;
+    let mut invalid = false;
+
+    for pkg in pkgs {
+        if !pkg.config.publish() {
+            continue;
+        }
+        let crate_name = pkg.meta.name.as_str();
+
+        if pkg.config.verify_readme() {
+            if let Some(readme) = pkg.meta.readme.as_ref() {
+                let path = pkg.package_root.join(readme);
+                match std::fs::read_to_string(&path) {
+                    Ok(content) => {
+                        let placeholders: Vec<_> = PLACEHOLDER
+                            .find_iter(&content)
+                            .map(|m| m.as_str())
+                            .collect();
+                        if !placeholders.is_empty() {
+                            invalid = true;
+                            let _ = crate::ops::shell::log(
+                                level,
+                                format!(
+                                    "{} has unreplaced placeholders: {}",
+                                    path.display(),
+                                    placeholders.join(", ")
+                                ),
+                            );
+                        }
+                    }
+                    Err(_) => {
+                        invalid = true;
+                        let _ = crate::ops::shell::log(
+                            level,
+                            format!("{}'s readme {} does not exist", crate_name, path.display()),
+                        );
+                    }
+                }
+            }
+        }
+
+        if pkg.config.verify_doc_links() {
+            let _ = crate::ops::shell::status("Documenting", crate_name);
+            let pkgid = (1 < ws_meta.workspace_members.len()).then_some(crate_name);
+            if !crate::ops::cargo::check_doc_links(&pkg.manifest_path, pkgid)? {
+                invalid = true;
+                let _ =
+                    crate::ops::shell::log(level, format!("{} has broken doc links", crate_name));
+            }
+        }
+    }
+
+    if invalid && level == log::Level::Error {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+pub fn verify_changelog_fragments(
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let mut missing_fragments = false;
+    for pkg in pkgs {
+        let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+        let fragments = if let Some(fragments) = pkg.config.changelog_fragments() {
+            fragments
+        } else {
+            continue;
+        };
+        if version.is_prerelease() {
+            continue;
+        }
+        let crate_name = pkg.meta.name.as_str();
+        let dir = pkg.package_root.join(fragments);
+        match crate::ops::fragment::load(&dir) {
+            Ok(fragments) if !fragments.is_empty() => {}
+            Ok(_) => {
+                let _ = crate::ops::shell::log(
+                    level,
+                    format!(
+                        "{} has no changelog fragments for {}",
+                        dir.display(),
+                        crate_name
+                    ),
+                );
+                missing_fragments = true;
+            }
+            Err(err) => {
+                let _ = crate::ops::shell::log(level, err.to_string());
+                missing_fragments = true;
+            }
+        }
+    }
+    if missing_fragments && level == log::Level::Error {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+pub fn verify_unreleased_notes(
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let mut missing_notes = false;
+    for pkg in pkgs {
+        let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+        if !pkg.config.keep_a_changelog() || version.is_prerelease() || pkg.first_release {
+            continue;
+        }
+        if pkg.config.changelog_fragments().is_some() {
+            // Checked by `verify_changelog_fragments`
+            continue;
+        }
+        let crate_name = pkg.meta.name.as_str();
+        let path = pkg.package_root.join(pkg.config.changelog_path());
+        let changelog = std::fs::read_to_string(&path).unwrap_or_default();
+        match crate::ops::changelog::unreleased_notes(&changelog) {
+            Some(notes) if !notes.is_empty() => {}
+            Some(_) => {
+                let _ = crate::ops::shell::log(
+                    level,
+                    format!(
+                        "{} has no unreleased changes listed for {}",
+                        path.display(),
+                        crate_name
+                    ),
+                );
+                missing_notes = true;
+            }
+            None => {
+                let _ = crate::ops::shell::log(
+                    level,
+                    format!(
+                        "{} has no `## [Unreleased]` section for {}",
+                        path.display(),
+                        crate_name
+                    ),
+                );
+                missing_notes = true;
+            }
+        }
+    }
+    if missing_notes && level == log::Level::Error {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+pub fn verify_first_release(
+    pkgs: &[plan::PackageRelease],
+    allow_first_release: bool,
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    if allow_first_release {
+        return Ok(success);
+    }
+
+    let mut first_release = false;
+    for pkg in pkgs {
+        if pkg.first_release {
+            let crate_name = pkg.meta.name.as_str();
+            let _ = crate::ops::shell::log(
+                level,
+                format!(
+                    "{} has never been released, pass `--allow-first-release` to release it",
+                    crate_name
+                ),
+            );
+            first_release = true;
+        }
+    }
+    if first_release && level == log::Level::Error {
+        success = false;
+        if !dry_run {
+            return Err(101.into());
+        }
+    }
+
+    Ok(success)
+}
+
+/// Check that never-published crates don't collide with, or look like, existing crates
+pub fn verify_new_names(
     pkgs: &[plan::PackageRelease],
+    index: &mut crate::ops::index::CratesIoIndex,
+    allow_new_name: bool,
     dry_run: bool,
     level: log::Level,
 ) -> Result<bool, crate::error::CliError> {
     let mut success = true;
 
-    let mut downgrades_present = false;
     for pkg in pkgs {
-        if let Some(version) = pkg.planned_version.as_ref() {
-            if version.full_version < pkg.initial_version.full_version {
-                let crate_name = pkg.meta.name.as_str();
+        if !pkg.first_release || !pkg.config.publish() {
+            continue;
+        }
+        let registry = pkg.config.registry();
+        let crate_name = pkg.meta.name.as_str();
+
+        // Registries treat `-` and `_` as the same, so these are taken
+        let normalized = crate_name.replace('_', "-");
+        let swapped = crate_name.replace('-', "_");
+        for taken in [normalized, swapped] {
+            if taken != crate_name && index.has_krate(registry, &taken)? {
+                success = false;
                 let _ = crate::ops::shell::log(
-                    level,
+                    log::Level::Error,
                     format!(
-                        "cannot downgrade {} from {} to {}",
-                        crate_name, version.full_version, pkg.initial_version.full_version
+                        "{} is unavailable, `{}` is already published",
+                        crate_name, taken
                     ),
                 );
-                downgrades_present = true;
             }
         }
-    }
-    if downgrades_present && level == log::Level::Error {
-        success = false;
-        if !dry_run {
-            return Err(101.into());
+
+        if allow_new_name {
+            continue;
+        }
+        let candidates = confusable_names(crate_name);
+        // Fetched together, rather than one request per candidate, for sparse indexes
+        let names = candidates.iter().map(String::as_str).collect::<Vec<_>>();
+        if let Err(err) = index.prefetch(registry, &names) {
+            log::debug!("failed to prefetch index entries: {err}");
+        }
+        let mut similar = Vec::new();
+        for candidate in candidates {
+            if index.has_krate(registry, &candidate)? {
+                similar.push(candidate);
+            }
+        }
+        if !similar.is_empty() {
+            if level == log::Level::Error {
+                success = false;
+            }
+            let _ = crate::ops::shell::log(
+                level,
+                format!(
+                    "{} resembles the existing {}, pass `--allow-new-name` if that is intended",
+                    crate_name,
+                    similar.join(", ")
+                ),
+            );
         }
     }
 
+    if !success && !dry_run {
+        return Err(101.into());
+    }
+
     Ok(success)
 }
 
-pub fn verify_rate_limit(
+/// Names a reader could mistake for `name`, most likely first
+///
+/// Capped at [`MAX_CONFUSABLE_NAMES`] as each is looked up in the index.
+fn confusable_names(name: &str) -> Vec<String> {
+    const HOMOGLYPHS: &[(&str, &str)] =
+        &[("0", "o"), ("1", "l"), ("l", "i"), ("rn", "m"), ("vv", "w")];
+
+    let mut names = vec![name.replace(['-', '_'], "")];
+    for (a, b) in HOMOGLYPHS {
+        names.push(name.replace(a, b));
+        names.push(name.replace(b, a));
+    }
+    let chars: Vec<char> = name.chars().collect();
+    for i in 1..chars.len() {
+        let mut transposed = chars.clone();
+        transposed.swap(i - 1, i);
+        names.push(transposed.into_iter().collect());
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    names
+        .into_iter()
+        .filter(|n| n != name && !n.is_empty() && !n.starts_with(['-', '_']))
+        .filter(|n| seen.insert(n.clone()))
+        .take(MAX_CONFUSABLE_NAMES)
+        .collect()
+}
+
+/// Most names [`confusable_names`] suggests, bounding the index lookups for a new crate
+const MAX_CONFUSABLE_NAMES: usize = 16;
+
+/// Check the registries being published to aren't reporting an incident
+pub fn verify_registry_health(
+    ws_config: &crate::config::Config,
     pkgs: &[plan::PackageRelease],
-    index: &mut crate::ops::index::CratesIoIndex,
     dry_run: bool,
-    level: log::Level,
 ) -> Result<bool, crate::error::CliError> {
+    let level = match ws_config.registry_health() {
+        crate::config::LintLevel::Allow => return Ok(true),
+        crate::config::LintLevel::Warn => log::Level::Warn,
+        crate::config::LintLevel::Error => log::Level::Error,
+    };
     let mut success = true;
 
+    let status_urls = pkgs
+        .iter()
+        .filter(|pkg| pkg.config.publish())
+        .filter_map(|pkg| Some((pkg.config.registry(), pkg.config.registry_status_url()?)))
+        .collect::<std::collections::BTreeMap<_, _>>();
+    for (registry, url) in status_urls {
+        if let Some(problem) = crate::ops::registry_status::problem(url)? {
+            let _ = crate::ops::shell::log(
+                level,
+                format!(
+                    "{} may not be accepting releases: {}",
+                    registry.unwrap_or("crates.io"),
+                    problem
+                ),
+            );
+            if level == log::Level::Error {
+                success = false;
+                if !dry_run {
+                    return Err(101.into());
+                }
+            }
+        }
+    }
+
+    Ok(success)
+}
+
+/// Warn about pauses publishing will take to stay within registry rate limits
+///
+/// Publishes from earlier runs, recorded in the publish log, count against the allowance.
+pub fn report_rate_limit(
+    pkgs: &[plan::PackageRelease],
+    index: &mut crate::ops::index::CratesIoIndex,
+) -> Result<(), crate::error::CliError> {
     // "It's not particularly secret, we just don't publish it other than in the code because
     // it's subject to change. The responses from the rate limited requests on when to try
     // again contain the most accurate information."
-    let mut new = 0;
-    let mut existing = 0;
+    let mut counts = std::collections::BTreeMap::new();
     for pkg in pkgs {
-        // Note: these rate limits are only known for default registry
-        if pkg.config.registry().is_none() && pkg.config.publish() {
+        // Note: rate limits are only known for the default registry, unless configured
+        let limit = if let Some(limit) = pkg.config.rate_limit() {
+            limit
+        } else {
+            continue;
+        };
+        if pkg.config.publish() {
+            let registry = pkg.config.registry();
             let crate_name = pkg.meta.name.as_str();
-            if index.has_krate(None, crate_name)? {
-                existing += 1;
+            let (_, new, existing) = counts.entry(registry).or_insert((limit, 0, 0));
+            if index.has_krate(registry, crate_name)? {
+                *existing += 1;
             } else {
-                new += 1;
+                *new += 1;
             }
         }
     }
 
-    if 5 < new {
-        // "The rate limit for creating new crates is 1 crate every 10 minutes, with a burst of 5 crates."
-        success = false;
-        let _ = crate::ops::shell::log(
-            level,
-            format!(
-                "attempting to publish {} new crates which is above the crates.io rate limit",
-                new
-            ),
-        );
+    let publish_log = crate::ops::rate_limit::PublishLog::load();
+    for (registry, (limit, new, existing)) in counts {
+        let wait = publish_log
+            .wait_for(registry, &limit, true, new)
+            .max(publish_log.wait_for(registry, &limit, false, existing));
+        if !wait.is_zero() {
+            let _ = crate::ops::shell::warn(format!(
+                "{} new and {} existing crates are above the {} rate limit, pausing about {}",
+                new,
+                existing,
+                registry.unwrap_or("crates.io"),
+                crate::ops::rate_limit::format_duration(wait)
+            ));
+        }
     }
 
-    if 30 < existing {
-        // "The rate limit for new versions of existing crates is 1 per minute, with a burst of 30 crates, so when releasing new versions of these crates, you shouldn't hit the limit."
-        success = false;
-        let _ = crate::ops::shell::log(
-            level,
-            format!(
-                "attempting to publish {} existing crates which is above the crates.io rate limit",
-                existing
-            ),
-        );
-    }
+    Ok(())
+}
 
-    if !success && level == log::Level::Error && !dry_run {
-        return Err(101.into());
+/// Set up `index`'s client and let it look up crates in the registries `pkgs` publish to
+pub fn configure_index<'p>(
+    index: &mut crate::ops::index::CratesIoIndex,
+    ws_config: &crate::config::Config,
+    pkgs: impl IntoIterator<Item = &'p plan::PackageRelease>,
+) {
+    let options = crate::ops::http::ClientOptions::from_config(ws_config);
+    crate::ops::http::set_default_options(options.clone());
+    index.set_client_options(options);
+    index.set_api_fallback(ws_config.index_api_fallback());
+    index.set_cache_max_age(ws_config.index_cache_max_age());
+    let mut names = std::collections::BTreeMap::<_, Vec<_>>::new();
+    for pkg in pkgs {
+        if let Some(registry_config) = pkg.config.registry_config() {
+            index.add_registry(pkg.config.registry(), registry_config);
+        }
+        if pkg.config.publish() {
+            names
+                .entry(pkg.config.registry())
+                .or_default()
+                .push(pkg.meta.name.as_str());
+        }
+    }
+    // Warm the cache so the per-package checks that follow don't each wait on the network
+    for (registry, names) in names {
+        if let Err(err) = index.prefetch(registry, &names) {
+            log::debug!("failed to prefetch index entries: {err}");
+        }
     }
-
-    Ok(success)
 }
 
 pub fn verify_metadata(
@@ -312,6 +1518,59 @@ pub fn verify_metadata(
             missing.push("documentation || homepage || repository");
         }
 
+        // Project rules
+        let lints = pkg.config.manifest_lints();
+        let mut lacking = Vec::new();
+        let optional_fields = [
+            ("readme", lints.readme, pkg.meta.readme.is_none()),
+            (
+                "repository",
+                lints.repository,
+                pkg.meta
+                    .repository
+                    .as_deref()
+                    .unwrap_or_default()
+                    .is_empty(),
+            ),
+            (
+                "homepage",
+                lints.homepage,
+                pkg.meta.homepage.as_deref().unwrap_or_default().is_empty(),
+            ),
+            (
+                "documentation",
+                lints.documentation,
+                pkg.meta
+                    .documentation
+                    .as_deref()
+                    .unwrap_or_default()
+                    .is_empty(),
+            ),
+            ("keywords", lints.keywords, pkg.meta.keywords.is_empty()),
+            (
+                "categories",
+                lints.categories,
+                pkg.meta.categories.is_empty(),
+            ),
+        ];
+        for (field, lint, is_missing) in optional_fields {
+            if !is_missing {
+                continue;
+            }
+            match lint.unwrap_or_default() {
+                crate::config::LintLevel::Allow => {}
+                crate::config::LintLevel::Warn => lacking.push(field),
+                crate::config::LintLevel::Error => missing.push(field),
+            }
+        }
+        if !lacking.is_empty() {
+            let _ = crate::ops::shell::warn(format!(
+                "{} is missing the following recommended fields:\n  {}",
+                pkg.meta.name,
+                lacking.join("\n  ")
+            ));
+        }
+
         if !missing.is_empty() {
             let _ = crate::ops::shell::log(
                 level,
@@ -332,14 +1591,294 @@ pub fn verify_metadata(
     Ok(success)
 }
 
+/// Check that published crates don't rely on unreleased changes in workspace dependencies
+pub fn verify_unreleased_dependencies(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &crate::config::Config,
+    selected_pkgs: &[plan::PackageRelease],
+    excluded_pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<bool, crate::error::CliError> {
+    let level = match ws_config.unreleased_dependencies() {
+        crate::config::LintLevel::Allow => return Ok(true),
+        crate::config::LintLevel::Warn => log::Level::Warn,
+        crate::config::LintLevel::Error => log::Level::Error,
+    };
+    let mut success = true;
+
+    let excluded = excluded_pkgs
+        .iter()
+        .map(|p| (p.meta.name.as_str(), p))
+        .collect::<std::collections::HashMap<_, _>>();
+    for pkg in selected_pkgs {
+        if !pkg.config.publish() {
+            continue;
+        }
+
+        for dep in &pkg.meta.dependencies {
+            if dep.kind == cargo_metadata::DependencyKind::Development || dep.path.is_none() {
+                continue;
+            }
+            let dep_pkg = if let Some(dep_pkg) = excluded.get(dep.name.as_str()) {
+                dep_pkg
+            } else {
+                continue;
+            };
+            if !dep_pkg.config.publish() {
+                continue;
+            }
+            let prior_tag = if let Some(prior_tag) = dep_pkg.prior_tag.as_deref() {
+                prior_tag
+            } else {
+                continue;
+            };
+            let changed = version::changed_since(ws_meta, dep_pkg, prior_tag).unwrap_or_default();
+            if !changed.is_empty() {
+                let _ = crate::ops::shell::log(
+                    level,
+                    format!(
+                        "{} depends on {} which has unreleased changes since {}; include it in the release",
+                        pkg.meta.name, dep.name, prior_tag
+                    ),
+                );
+                if level == log::Level::Error {
+                    success = false;
+                }
+            }
+        }
+    }
+
+    if !success && !dry_run {
+        return Err(101.into());
+    }
+
+    Ok(success)
+}
+
+pub fn verify_dependency_reqs(
+    selected_pkgs: &[plan::PackageRelease],
+    excluded_pkgs: &[plan::PackageRelease],
+    index: &mut crate::ops::index::CratesIoIndex,
+    dry_run: bool,
+    level: log::Level,
+) -> Result<bool, crate::error::CliError> {
+    let mut success = true;
+
+    let ws_pkgs = selected_pkgs
+        .iter()
+        .map(|p| (true, p))
+        .chain(excluded_pkgs.iter().map(|p| (false, p)))
+        .collect::<Vec<_>>();
+    for pkg in selected_pkgs {
+        if !pkg.config.publish() {
+            continue;
+        }
+
+        for dep in &pkg.meta.dependencies {
+            if dep.kind == cargo_metadata::DependencyKind::Development {
+                // Not needed to build the published package
+                continue;
+            }
+            // `dep.rename` is only the key it is used under, match the package it points to
+            let dep_path = dep.path.as_ref().map(|p| p.as_std_path());
+            let (dep_selected, dep_pkg) = if let Some((dep_selected, dep_pkg)) =
+                ws_pkgs.iter().find(|(_, p)| {
+                    dep_path.map_or(p.meta.name == dep.name, |path| path == p.package_root)
+                }) {
+                (*dep_selected, *dep_pkg)
+            } else {
+                continue;
+            };
+            let dep_name = dep_pkg.meta.name.as_str();
+            let registry = dep_pkg.config.registry();
+            if !index.can_look_up(registry) {
+                log::trace!(
+                    "cannot check requirement on {} in `{:?}` without its `index` or `api`",
+                    dep_name,
+                    registry
+                );
+                continue;
+            }
+
+            let existing_req = dep.req.to_string();
+            let req = if let Some(version) = dep_pkg.planned_version.as_ref() {
+                crate::ops::cargo::upgraded_req(
+                    &existing_req,
+                    &version.full_version,
+                    dep_pkg.config.dependent_version_for(&dep_pkg.meta.name),
+                    dep_pkg.config.dependent_prerelease(),
+                )
+                .unwrap_or(existing_req)
+            } else {
+                existing_req
+            };
+            let req = semver::VersionReq::parse(&req)?;
+
+            let mut available = match index.krate_versions(registry, dep_name) {
+                Ok(available) => available.unwrap_or_default(),
+                Err(err) => {
+                    let _ = crate::ops::shell::log(
+                        level,
+                        format!(
+                            "cannot check {}'s requirement on {}: {:#}",
+                            pkg.meta.name, dep_name, err
+                        ),
+                    );
+                    if level == log::Level::Error {
+                        success = false;
+                    }
+                    continue;
+                }
+            };
+            if dep_selected && dep_pkg.config.publish() {
+                let version = dep_pkg
+                    .planned_version
+                    .as_ref()
+                    .unwrap_or(&dep_pkg.initial_version);
+                available.push(version.full_version.clone());
+            }
+            if !available.iter().any(|v| req.matches(v)) {
+                let _ = crate::ops::shell::log(
+                    level,
+                    format!(
+                        "{} requires {} {} which will not be available on the registry",
+                        pkg.meta.name, dep_name, req
+                    ),
+                );
+                if level == log::Level::Error {
+                    success = false;
+                }
+            }
+        }
+    }
+
+    if !success && level == log::Level::Error && !dry_run {
+        return Err(101.into());
+    }
+
+    Ok(success)
+}
+
+/// Release the dependents whose requirements on a released package will be updated, bumping them
+/// by `level`, until no more are affected
+///
+/// Only packages in `releasable` are considered, which are restored to that `release` config.
+/// Returns whether any were added.
+pub fn bump_dependents(
+    pkgs: &mut indexmap::IndexMap<cargo_metadata::PackageId, plan::PackageRelease>,
+    releasable: &std::collections::BTreeMap<
+        cargo_metadata::PackageId,
+        Option<crate::config::ReleaseField>,
+    >,
+    level: BumpLevel,
+) -> CargoResult<bool> {
+    let is_released =
+        |pkg: &plan::PackageRelease| pkg.config.release() && pkg.planned_version.is_some();
+
+    let mut added = false;
+    loop {
+        let mut affected = std::collections::BTreeMap::new();
+        for pkg in pkgs.values().filter(|pkg| is_released(pkg)) {
+            let version = pkg
+                .planned_version
+                .as_ref()
+                .expect("checked by `is_released`");
+            for dep in &pkg.dependents {
+                if !releasable.contains_key(&dep.pkg.id) || dep.req == semver::VersionReq::STAR {
+                    continue;
+                }
+                if pkgs.get(&dep.pkg.id).map(is_released).unwrap_or(true) {
+                    continue;
+                }
+                let updated = crate::ops::cargo::upgraded_req(
+                    &dep.req.to_string(),
+                    &version.full_version,
+                    pkg.config.dependent_version_for(&pkg.meta.name),
+                    pkg.config.dependent_prerelease(),
+                )
+                .is_some();
+                if updated {
+                    affected
+                        .entry(dep.pkg.id.clone())
+                        .or_insert_with(|| pkg.meta.name.clone());
+                }
+            }
+        }
+        if affected.is_empty() {
+            break;
+        }
+
+        for (id, dependency) in affected {
+            let pkg = &mut pkgs[&id];
+            pkg.config.release = releasable[&id];
+            pkg.bump(&TargetVersion::Relative(level), None)?;
+            if pkg.planned_version.is_none() {
+                anyhow::bail!("`{}` could not be bumped by {}", pkg.meta.name, level);
+            }
+            let _ = crate::ops::shell::status(
+                "Including",
+                format!(
+                    "{} for its updated requirement on {}",
+                    pkg.meta.name, dependency
+                ),
+            );
+            added = true;
+        }
+    }
+
+    Ok(added)
+}
+
 pub fn warn_changed(
     ws_meta: &cargo_metadata::Metadata,
     pkgs: &[plan::PackageRelease],
 ) -> Result<(), crate::error::CliError> {
     let mut changed_pkgs = std::collections::HashSet::new();
+    let mut index = crate::ops::index::CratesIoIndex::new();
     for pkg in pkgs {
         let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
         let crate_name = pkg.meta.name.as_str();
+        if pkg.config.change_detection() == crate::config::ChangeDetection::Content {
+            if let Some(registry_config) = pkg.config.registry_config() {
+                index.add_registry(pkg.config.registry(), registry_config);
+            }
+            match version::content_changed(ws_meta, &mut index, pkg) {
+                Ok(Some(changed)) => {
+                    if !changed.is_empty() || changed_pkgs.contains(&pkg.meta.id) {
+                        log::debug!(
+                            "Package content of {} differs from the published v{}: {:#?}",
+                            crate_name,
+                            pkg.initial_version.full_version_string,
+                            changed
+                        );
+                        changed_pkgs.insert(&pkg.meta.id);
+                        changed_pkgs.extend(pkg.dependents.iter().map(|d| &d.pkg.id));
+                    } else {
+                        let _ = crate::ops::shell::warn(format!(
+                            "updating {} to {} despite matching the published v{}",
+                            crate_name,
+                            version.full_version_string,
+                            pkg.initial_version.full_version_string
+                        ));
+                    }
+                    continue;
+                }
+                Ok(None) => {
+                    log::debug!(
+                        "cannot compare {} to v{} which is unpublished, falling back to git",
+                        crate_name,
+                        pkg.initial_version.full_version_string
+                    );
+                }
+                Err(err) => {
+                    log::debug!(
+                        "cannot compare {} to the registry, falling back to git: {}",
+                        crate_name,
+                        err
+                    );
+                }
+            }
+        }
         if let Some(prior_tag_name) = &pkg.prior_tag {
             if let Some(changed) = version::changed_since(ws_meta, pkg, prior_tag_name) {
                 if !changed.is_empty() {
@@ -445,6 +1984,62 @@ pub fn consolidate_commits(
     Ok(consolidate_commits.expect("at least one package"))
 }
 
+/// Render `{{contributors}}` for `pkg`, if `template` uses it
+pub fn contributors(
+    pkg: &plan::PackageRelease,
+    template: &str,
+) -> Result<Option<String>, crate::error::CliError> {
+    if !crate::ops::replace::Template::uses(template, "contributors") {
+        return Ok(None);
+    }
+
+    let authors = crate::ops::git::authors(&pkg.package_root, pkg.prior_tag.as_deref())?;
+    Ok(Some(render_authors(
+        &authors,
+        pkg.config.contributor_handles(),
+    )))
+}
+
+fn render_authors(authors: &[(String, String)], handles: bool) -> String {
+    let contributors = authors
+        .iter()
+        .map(|(name, email)| {
+            let handle = handles.then(|| github_handle(email)).flatten();
+            if let Some(handle) = handle {
+                format!("{} (@{})", name, handle)
+            } else {
+                name.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+    contributors.join(", ")
+}
+
+/// Render `{{contributors}}` for the whole workspace since `since`, if `template` uses it
+pub fn workspace_contributors(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &crate::config::Config,
+    since: Option<&str>,
+    template: &str,
+) -> Result<Option<String>, crate::error::CliError> {
+    if !crate::ops::replace::Template::uses(template, "contributors") {
+        return Ok(None);
+    }
+
+    let authors = crate::ops::git::authors(ws_meta.workspace_root.as_std_path(), since)?;
+    Ok(Some(render_authors(
+        &authors,
+        ws_config.contributor_handles(),
+    )))
+}
+
+/// Recover the handle from a GitHub noreply address, like `123+octocat@users.noreply.github.com`
+fn github_handle(email: &str) -> Option<&str> {
+    let user = email.strip_suffix("@users.noreply.github.com")?;
+    let handle = user.split_once('+').map(|(_, h)| h).unwrap_or(user);
+    (!handle.is_empty()).then_some(handle)
+}
+
 pub fn confirm(
     step: &str,
     pkgs: &[plan::PackageRelease],
@@ -485,6 +2080,34 @@ pub fn confirm(
     Ok(())
 }
 
+/// Align `rows` into columns under `header`
+pub fn render_table<R: AsRef<[String]>>(header: &[&str], rows: &[R]) -> String {
+    let mut widths = header.iter().map(|h| h.len()).collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row.as_ref().iter()) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut output = String::new();
+    let mut write_row = |cells: &[&str]| {
+        let line = cells
+            .iter()
+            .zip(widths.iter().copied())
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        output.push_str(line.trim_end());
+        output.push('\n');
+    };
+    write_row(header);
+    for row in rows {
+        let cells = row.as_ref().iter().map(|c| c.as_str()).collect::<Vec<_>>();
+        write_row(&cells);
+    }
+    output
+}
+
 pub fn finish(failed: bool, dry_run: bool) -> Result<(), crate::error::CliError> {
     if dry_run {
         if failed {
@@ -512,11 +2135,14 @@ impl TargetVersion {
         &self,
         current: &semver::Version,
         metadata: Option<&str>,
+        zero_ver_breaking: crate::config::ZeroVerBreaking,
     ) -> CargoResult<Option<plan::Version>> {
         match self {
             TargetVersion::Relative(bump_level) => {
                 let mut potential_version = current.to_owned();
-                bump_level.bump_version(&mut potential_version, metadata)?;
+                bump_level
+                    .resolve(current, zero_ver_breaking)
+                    .bump_version(&mut potential_version, metadata)?;
                 if potential_version != *current {
                     let full_version = potential_version;
                     let version = plan::Version::from(full_version);
@@ -625,6 +2251,8 @@ pub enum BumpLevel {
     Minor,
     /// Increase the patch version (x.y.z)
     Patch,
+    /// Increase the version for a breaking change (major, or minor below 1.0)
+    Breaking,
     /// Remove the pre-version (x.y.z)
     Release,
     /// Increase the rc pre-version (x.y.z-rc.M)
@@ -662,6 +2290,22 @@ impl FromStr for BumpLevel {
 }
 
 impl BumpLevel {
+    /// Resolve `breaking` to the level that is breaking for `version`
+    pub fn resolve(
+        self,
+        version: &semver::Version,
+        zero_ver_breaking: crate::config::ZeroVerBreaking,
+    ) -> Self {
+        match self {
+            BumpLevel::Breaking if version.major == 0 => match zero_ver_breaking {
+                crate::config::ZeroVerBreaking::Minor => BumpLevel::Minor,
+                crate::config::ZeroVerBreaking::Major => BumpLevel::Major,
+            },
+            BumpLevel::Breaking => BumpLevel::Major,
+            level => level,
+        }
+    }
+
     pub fn bump_version(
         self,
         version: &mut semver::Version,
@@ -674,6 +2318,10 @@ impl BumpLevel {
             BumpLevel::Minor => {
                 version.increment_minor();
             }
+            BumpLevel::Breaking => {
+                let level = self.resolve(version, Default::default());
+                return level.bump_version(version, metadata);
+            }
             BumpLevel::Patch => {
                 if !version.is_prerelease() {
                     version.increment_patch();
@@ -704,3 +2352,26 @@ impl BumpLevel {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn confusable_names_most_likely_first() {
+        let names = confusable_names("cargo-rn1");
+        assert_eq!(names[0], "cargorn1");
+        assert!(names.contains(&"cargo-rnl".to_owned()));
+        assert!(names.contains(&"cargo-m1".to_owned()));
+        assert!(names.contains(&"acrgo-rn1".to_owned()));
+        assert!(!names.contains(&"cargo-rn1".to_owned()));
+    }
+
+    #[test]
+    fn confusable_names_capped() {
+        let name = "a-very-long-crate-name-with-many-transpositions";
+        let names = confusable_names(name);
+        assert_eq!(names.len(), MAX_CONFUSABLE_NAMES);
+        assert_eq!(names[0], name.replace('-', ""));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::error::CliError;
+use crate::steps::plan;
+
+/// Add a changelog fragment for a change
+#[derive(Debug, Clone, clap::Args)]
+pub struct NewFragmentStep {
+    /// What changed, for the changelog entry
+    #[arg(value_name = "MESSAGE")]
+    message: Option<String>,
+
+    /// Kind of change
+    #[arg(
+        long = "type",
+        value_name = "TYPE",
+        value_parser = clap::builder::PossibleValuesParser::new(crate::ops::fragment::TYPES.iter().copied()),
+    )]
+    kind: String,
+
+    /// Issue or pull request number to link to
+    #[arg(long, value_name = "NUMBER")]
+    issue: Option<u64>,
+
+    /// Short name for the change, derived from MESSAGE by default
+    #[arg(long)]
+    slug: Option<String>,
+
+    #[command(flatten)]
+    manifest: clap_cargo::Manifest,
+
+    #[command(flatten)]
+    workspace: clap_cargo::Workspace,
+
+    /// Custom config file
+    #[arg(short, long = "config", value_name = "PATH")]
+    custom_config: Option<std::path::PathBuf>,
+
+    /// Ignore implicit configuration files.
+    #[arg(long)]
+    isolated: bool,
+}
+
+impl NewFragmentStep {
+    pub fn run(&self) -> Result<(), CliError> {
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
+        let config = self.to_config();
+        let mut pkgs = plan::load(&config, &ws_meta)?;
+
+        let (selected_pkgs, _excluded_pkgs) = self.workspace.partition_packages(&ws_meta);
+        let selected_pkgs = selected_pkgs
+            .into_iter()
+            .filter_map(|p| pkgs.remove(&p.id))
+            .filter(|p| p.config.changelog_fragments().is_some())
+            .collect::<Vec<_>>();
+        let pkg = match selected_pkgs.as_slice() {
+            [pkg] => pkg,
+            [] => {
+                let _ = crate::ops::shell::error(
+                    "no selected packages have `changelog-fragments` configured",
+                );
+                return Err(2.into());
+            }
+            _ => {
+                let _ = crate::ops::shell::error(
+                    "multiple packages selected, pick one with `--package`",
+                );
+                return Err(2.into());
+            }
+        };
+        let dir = pkg.package_root.join(
+            pkg.config
+                .changelog_fragments()
+                .expect("only packages with fragments are selected"),
+        );
+
+        let slug = self
+            .slug
+            .as_deref()
+            .map(crate::ops::fragment::slugify)
+            .or_else(|| {
+                let message = self.message.as_deref()?;
+                let words = message.split_whitespace().take(5).collect::<Vec<_>>();
+                Some(crate::ops::fragment::slugify(&words.join(" ")))
+            })
+            .filter(|slug| !slug.is_empty());
+        let name = crate::ops::fragment::file_name(self.issue, slug.as_deref(), &self.kind);
+        let path = dir.join(name);
+        if path.exists() {
+            let _ = crate::ops::shell::error(format!("{} already exists", path.display()));
+            return Err(2.into());
+        }
+
+        let mut content = self
+            .message
+            .clone()
+            .unwrap_or_else(|| "Describe the change for users".to_owned());
+        content.push('\n');
+
+        let _ = crate::ops::shell::status("Creating", path.display());
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(&path, content)?;
+
+        Ok(())
+    }
+
+    fn to_config(&self) -> crate::config::ConfigArgs {
+        crate::config::ConfigArgs {
+            custom_config: self.custom_config.clone(),
+            isolated: self.isolated,
+            ..Default::default()
+        }
+    }
+}
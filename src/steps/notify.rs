@@ -0,0 +1,181 @@
+use crate::error::CliError;
+use crate::ops::replace::{Template, NOW};
+use crate::steps::plan;
+
+/// Announce the release through each `[notify]` channel
+///
+/// The release already happened, so failing to announce it is only reported.
+pub fn announce(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &crate::config::Config,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let notify = if let Some(notify) = ws_config.notify() {
+        notify
+    } else {
+        return Ok(());
+    };
+
+    #[cfg(feature = "notify")]
+    {
+        send(ws_meta, ws_config, notify, pkgs, dry_run)
+    }
+    #[cfg(not(feature = "notify"))]
+    {
+        let _ = (ws_meta, pkgs, dry_run, notify);
+        let _ = crate::ops::shell::warn(
+            "not announcing the release, `[notify]` needs cargo-release built with the `notify` feature",
+        );
+        Ok(())
+    }
+}
+
+#[cfg(feature = "notify")]
+fn send(
+    ws_meta: &cargo_metadata::Metadata,
+    ws_config: &crate::config::Config,
+    notify: &crate::config::Notify,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let releases = pkgs
+        .iter()
+        .map(|pkg| {
+            let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+            format!("{} {}", pkg.meta.name, version.bare_version_string)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let changelog = changelog(ws_meta, pkgs);
+    let version = super::find_shared_versions(pkgs)?;
+    let crate_name = match pkgs {
+        [pkg] => Some(pkg.meta.name.as_str()),
+        _ => None,
+    };
+    let version = version.as_ref().or(match pkgs {
+        [pkg] => Some(pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version)),
+        _ => None,
+    });
+    let tag_name = super::tag::planned_umbrella_tag(ws_config);
+    let next_version = match pkgs {
+        [pkg] => Some(pkg.next_version()),
+        _ => version.map(|v| v.next_version(ws_config.dev_version_ext())),
+    };
+    let prev_tag_name = match pkgs {
+        [pkg] => pkg.prior_tag.as_deref(),
+        _ => None,
+    };
+    let template = Template {
+        version: version.map(|v| v.bare_version_string.as_str()),
+        metadata: version.map(|v| v.full_version.build.as_str()),
+        next_version: next_version.as_deref(),
+        crate_name,
+        tag_name: tag_name.as_deref(),
+        prev_tag_name,
+        date: Some(NOW.as_str()),
+        releases: Some(releases.as_str()),
+        changelog: Some(changelog.as_str()),
+        ..Default::default()
+    };
+
+    if let Some(email) = notify.email.as_ref() {
+        let subject = template.render(email.subject());
+        let body = template.render(email.body());
+        let _ = crate::ops::shell::status(
+            "Announcing",
+            format!("{} to {}", subject, email.to().join(", ")),
+        );
+        if dry_run {
+            log::debug!("email body:\n{body}");
+        } else if let Err(err) = crate::ops::notify::send_email(email, &subject, &body) {
+            let _ = crate::ops::shell::warn(format!("failed to send announcement email: {err:#}"));
+        }
+    }
+
+    if let Some(matrix) = notify.matrix.as_ref() {
+        let body = template.render(matrix.body());
+        let _ = crate::ops::shell::status(
+            "Announcing",
+            format!(
+                "{} in {}",
+                releases,
+                matrix.room_id.as_deref().unwrap_or_default()
+            ),
+        );
+        if dry_run {
+            log::debug!("matrix message:\n{body}");
+        } else if let Err(err) = crate::ops::notify::send_matrix(matrix, &body) {
+            let _ =
+                crate::ops::shell::warn(format!("failed to post announcement to matrix: {err:#}"));
+        }
+    }
+
+    Ok(())
+}
+
+/// The changelog entries for each release, headed by the crate when there are several
+///
+/// Without changelog entries, a release's notes come from `commit-notes-template`.
+pub fn changelog(ws_meta: &cargo_metadata::Metadata, pkgs: &[plan::PackageRelease]) -> String {
+    let mut sections = Vec::new();
+    for pkg in pkgs {
+        let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+        let path = pkg.package_root.join(pkg.config.changelog_path());
+        let notes = std::fs::read_to_string(&path).ok().and_then(|changelog| {
+            crate::ops::changelog::release_notes(&changelog, &version.bare_version_string)
+                .map(ToOwned::to_owned)
+        });
+        let notes = if let Some(notes) = notes.or_else(|| commit_notes(ws_meta, pkg)) {
+            notes
+        } else {
+            continue;
+        };
+        if pkgs.len() == 1 {
+            sections.push(notes);
+        } else {
+            sections.push(format!(
+                "{} {}\n\n{}",
+                pkg.meta.name, version.bare_version_string, notes
+            ));
+        }
+    }
+    sections.join("\n\n")
+}
+
+/// `commit-notes-template` rendered for the commits since `pkg`'s prior tag
+fn commit_notes(ws_meta: &cargo_metadata::Metadata, pkg: &plan::PackageRelease) -> Option<String> {
+    let template = pkg.config.commit_notes_template();
+    let prior_tag = pkg.prior_tag.as_deref()?;
+    if template.is_empty() {
+        return None;
+    }
+    let commits = commits(ws_meta, pkg)?;
+
+    let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+    let template_vars = Template {
+        version: Some(version.bare_version_string.as_str()),
+        crate_name: Some(pkg.meta.name.as_str()),
+        prev_tag_name: Some(prior_tag),
+        date: Some(NOW.as_str()),
+        commits: Some(commits.as_str()),
+        ..Default::default()
+    };
+    Some(template_vars.render(template).trim().to_owned())
+}
+
+/// `{{commits}}`: the commits since `pkg`'s prior tag, grouped by their conventional commit type
+pub fn commits(ws_meta: &cargo_metadata::Metadata, pkg: &plan::PackageRelease) -> Option<String> {
+    let prior_tag = pkg.prior_tag.as_deref()?;
+    let commits =
+        match super::changes::package_commits(ws_meta.workspace_root.as_std_path(), pkg, prior_tag)
+        {
+            Ok(commits) => commits,
+            Err(err) => {
+                log::debug!("no commit notes for {}: {err}", pkg.meta.name);
+                return None;
+            }
+        };
+    let commits = super::changes::commit_notes(&commits);
+    (!commits.is_empty()).then_some(commits)
+}
@@ -1,5 +1,6 @@
+use std::io::Write;
+
 use crate::error::CliError;
-use crate::ops::git;
 use crate::steps::plan;
 
 /// Ensure owners are set on specified packages
@@ -23,8 +24,8 @@ pub struct OwnerStep {
     #[arg(long, value_delimiter = ',')]
     allow_branch: Option<Vec<String>>,
 
-    /// Actually perform a release. Dry-run mode is the default
-    #[arg(short = 'x', long)]
+    /// Add missing owners. Dry-run mode, which only audits, is the default
+    #[arg(short = 'x', long, visible_alias = "apply")]
     execute: bool,
 
     #[arg(short = 'n', long, conflicts_with = "execute", hide = true)]
@@ -37,19 +38,12 @@ pub struct OwnerStep {
 
 impl OwnerStep {
     pub fn run(&self) -> Result<(), CliError> {
-        git::git_version()?;
-
         if self.dry_run {
             let _ =
                 crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
         }
 
-        let ws_meta = self
-            .manifest
-            .metadata()
-            // When evaluating dependency ordering, we need to consider optional dependencies
-            .features(cargo_metadata::CargoOpt::AllFeatures)
-            .exec()?;
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
         let config = self.to_config();
         let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
         let mut pkgs = plan::load(&config, &ws_meta)?;
@@ -68,7 +62,7 @@ impl OwnerStep {
 
             pkg.config.publish = Some(false);
             pkg.config.owners = Some(vec![]);
-            pkg.config.release = Some(false);
+            pkg.config.release = Some(false.into());
 
             let crate_name = pkg.meta.name.as_str();
             log::debug!("disabled by user, skipping {}", crate_name,);
@@ -76,17 +70,13 @@ impl OwnerStep {
 
         let mut pkgs = plan::plan(pkgs)?;
 
+        // Packages without `owners` are still audited, to show who has access to them
         for pkg in pkgs.values_mut() {
-            if pkg.config.owners().is_empty() {
-                log::debug!("disabled due to no owners, skipping {}", pkg.meta.name);
-                pkg.config.publish = Some(false);
-                pkg.config.owners = Some(vec![]);
-                pkg.config.release = Some(false);
-            } else if !pkg.config.publish() {
+            if !pkg.config.publish() {
                 log::debug!("disabled due to publish=false, skipping {}", pkg.meta.name);
                 pkg.config.publish = Some(false);
                 pkg.config.owners = Some(vec![]);
-                pkg.config.release = Some(false);
+                pkg.config.release = Some(false.into());
             }
         }
 
@@ -123,10 +113,27 @@ impl OwnerStep {
             log::Level::Warn,
         )?;
 
+        let audits = selected_pkgs
+            .iter()
+            .map(OwnerAudit::load)
+            .collect::<Result<Vec<_>, CliError>>()?;
+        let output = render(&audits);
+        std::io::stdout().write_all(output.as_bytes())?;
+        if audits.iter().all(|a| a.missing().is_empty()) {
+            return super::finish(failed, dry_run);
+        }
+
         // STEP 1: Release Confirmation
         super::confirm("Owner", &selected_pkgs, self.no_confirm, dry_run)?;
 
-        ensure_owners(&selected_pkgs, dry_run)?;
+        for audit in &audits {
+            crate::ops::cargo::add_owners(
+                &audit.name,
+                &audit.missing(),
+                audit.registry.as_deref(),
+                dry_run,
+            )?;
+        }
 
         super::finish(failed, dry_run)
     }
@@ -158,3 +165,118 @@ pub fn ensure_owners(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<(),
 
     Ok(())
 }
+
+/// How a package's registry owners compare to its `owners`
+#[derive(Clone, Debug)]
+pub struct OwnerAudit {
+    pub name: String,
+    pub registry: Option<String>,
+    pub expected: Vec<String>,
+    /// `None` when the registry can't be queried, like for a crate that was never published
+    pub current: Option<std::collections::BTreeSet<String>>,
+}
+
+impl OwnerAudit {
+    pub fn load(pkg: &plan::PackageRelease) -> Result<Self, CliError> {
+        let name = pkg.meta.name.clone();
+        let registry = pkg.config.registry().map(ToOwned::to_owned);
+        let current = match crate::ops::cargo::list_owners(&name, registry.as_deref()) {
+            Ok(current) => Some(current),
+            Err(err) => {
+                let _ =
+                    crate::ops::shell::warn(format!("failed to list owners of {name}: {err:#}"));
+                None
+            }
+        };
+        Ok(Self {
+            name,
+            registry,
+            expected: pkg.config.owners().to_vec(),
+            current,
+        })
+    }
+
+    /// Expected owners not on the registry, all of them if it couldn't be queried
+    pub fn missing(&self) -> Vec<&str> {
+        self.expected
+            .iter()
+            .filter(|login| !matches!(&self.current, Some(current) if current.contains(*login)))
+            .map(|login| login.as_str())
+            .collect()
+    }
+}
+
+fn render(audits: &[OwnerAudit]) -> String {
+    let header = ["PACKAGE", "OWNER", "STATUS"];
+    let mut rows = Vec::new();
+    for audit in audits {
+        for login in &audit.expected {
+            let status = match audit.current.as_ref() {
+                Some(current) if current.contains(login) => "present",
+                Some(_) => "missing",
+                None => "unknown",
+            };
+            rows.push([audit.name.clone(), login.clone(), status.to_owned()]);
+        }
+        for login in audit.current.iter().flatten() {
+            if !audit.expected.contains(login) {
+                rows.push([audit.name.clone(), login.clone(), "unlisted".to_owned()]);
+            }
+        }
+        if audit.expected.is_empty() && audit.current.iter().flatten().next().is_none() {
+            let status = if audit.current.is_some() {
+                "none"
+            } else {
+                "unknown"
+            };
+            rows.push([audit.name.clone(), "-".to_owned(), status.to_owned()]);
+        }
+    }
+
+    super::render_table(&header, &rows)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_matrix() {
+        let audits = vec![
+            OwnerAudit {
+                name: "foo".to_owned(),
+                registry: None,
+                expected: vec!["alice".to_owned(), "github:org:team".to_owned()],
+                current: Some(
+                    ["alice", "bob"]
+                        .into_iter()
+                        .map(ToOwned::to_owned)
+                        .collect(),
+                ),
+            },
+            OwnerAudit {
+                name: "foo-internal".to_owned(),
+                registry: None,
+                expected: vec!["alice".to_owned()],
+                current: None,
+            },
+            OwnerAudit {
+                name: "foo-macros".to_owned(),
+                registry: None,
+                expected: vec![],
+                current: Some(["bob"].into_iter().map(ToOwned::to_owned).collect()),
+            },
+        ];
+        assert_eq!(audits[0].missing(), ["github:org:team"]);
+        let actual = render(&audits);
+        let expected = "\
+PACKAGE       OWNER            STATUS
+foo           alice            present
+foo           github:org:team  missing
+foo           bob              unlisted
+foo-internal  alice            unknown
+foo-macros    bob              unlisted
+";
+        assert_eq!(actual, expected);
+    }
+}
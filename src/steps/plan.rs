@@ -14,7 +14,8 @@ pub fn load(
 ) -> CargoResult<indexmap::IndexMap<cargo_metadata::PackageId, PackageRelease>> {
     let root = git::top_level(ws_meta.workspace_root.as_std_path())?;
 
-    let member_ids = cargo::sort_workspace(ws_meta);
+    let ws_config = config::load_workspace_config(args, ws_meta)?;
+    let member_ids = cargo::sort_workspace(ws_meta, ws_config.publish_order())?;
     member_ids
         .iter()
         .map(|p| PackageRelease::load(args, &root, ws_meta, &ws_meta[p]))
@@ -73,11 +74,77 @@ pub fn plan(
     Ok(pkgs)
 }
 
+/// Stop publishing `skip-unchanged` members of shared versions whose package content is the same
+/// as in their last release
+///
+/// They are still bumped and tagged with the rest of their group.  Members that a published crate
+/// depends on are kept as they would otherwise require a version that was never published.
+pub fn skip_unchanged(
+    pkgs: &mut indexmap::IndexMap<cargo_metadata::PackageId, PackageRelease>,
+) -> CargoResult<()> {
+    let mut unchanged = std::collections::BTreeMap::new();
+    for pkg in pkgs.values() {
+        if !pkg.config.skip_unchanged()
+            || pkg.config.shared_version().is_none()
+            || !pkg.config.release()
+            || !pkg.config.publish()
+            || pkg.planned_version.is_none()
+        {
+            continue;
+        }
+        let prior_tag = if let Some(prior_tag) = pkg.prior_tag.as_deref() {
+            prior_tag
+        } else {
+            continue;
+        };
+        if pkg.content_hash(None)? == pkg.content_hash(Some(prior_tag))? {
+            unchanged.insert(pkg.meta.id.clone(), prior_tag.to_owned());
+        }
+    }
+
+    loop {
+        let required = pkgs
+            .values()
+            .filter(|pkg| unchanged.contains_key(&pkg.meta.id))
+            .filter(|pkg| {
+                pkg.dependents.iter().any(|dep| {
+                    !unchanged.contains_key(&dep.pkg.id)
+                        && pkgs
+                            .get(&dep.pkg.id)
+                            .map(|dep| dep.config.release() && dep.config.publish())
+                            .unwrap_or(false)
+                })
+            })
+            .map(|pkg| pkg.meta.id.clone())
+            .collect::<Vec<_>>();
+        if required.is_empty() {
+            break;
+        }
+        for id in required {
+            log::debug!("{} is unchanged but required by a published dependent", id);
+            unchanged.remove(&id);
+        }
+    }
+
+    for (id, prior_tag) in unchanged {
+        let pkg = &mut pkgs[&id];
+        let _ = crate::ops::shell::note(format!(
+            "{} is unchanged since {}, bumping without publishing",
+            pkg.meta.name, prior_tag
+        ));
+        pkg.config.release = Some(config::ReleaseField::Mode(config::ReleaseMode::TagOnly));
+    }
+
+    Ok(())
+}
+
 #[derive(Debug)]
 pub struct PackageRelease {
     pub meta: cargo_metadata::Package,
     pub manifest_path: PathBuf,
     pub package_root: PathBuf,
+    /// `package_root` relative to the workspace root, with `/` separators
+    pub package_path: String,
     pub is_root: bool,
     pub config: config::Config,
 
@@ -93,6 +160,8 @@ pub struct PackageRelease {
     pub planned_tag: Option<String>,
 
     pub ensure_owners: bool,
+    /// Never published nor tagged
+    pub first_release: bool,
 }
 
 impl PackageRelease {
@@ -108,6 +177,10 @@ impl PackageRelease {
             .parent()
             .unwrap_or_else(|| Path::new("."))
             .to_owned();
+        let package_path = package_root
+            .strip_prefix(ws_meta.workspace_root.as_std_path())
+            .map(slash_path)
+            .unwrap_or_else(|_| package_root.display().to_string());
         let config = config::load_package_config(args, ws_meta, pkg_meta)?;
         if !config.release() {
             log::trace!("disabled in config, skipping {}", manifest_path.display());
@@ -153,6 +226,7 @@ impl PackageRelease {
             tag_name,
             tag_prefix,
             name,
+            (package_path.as_str(), package_root.to_str()),
             &initial_version,
             &initial_version,
         );
@@ -162,7 +236,12 @@ impl PackageRelease {
             let tag_name = config.tag_name();
             let tag_prefix = config.tag_prefix(is_root);
             let name = meta.name.as_str();
-            let tag_glob = render_tag_glob(tag_name, tag_prefix, name);
+            let tag_glob = render_tag_glob(
+                tag_name,
+                tag_prefix,
+                name,
+                (package_path.as_str(), package_root.to_str()),
+            );
             match globset::Glob::new(&tag_glob) {
                 Ok(tag_glob) => {
                     let tag_glob = tag_glob.compile_matcher();
@@ -183,6 +262,7 @@ impl PackageRelease {
             meta,
             manifest_path,
             package_root,
+            package_path,
             is_root,
             config,
 
@@ -197,10 +277,49 @@ impl PackageRelease {
             planned_version,
             planned_tag,
             ensure_owners,
+            first_release: false,
         };
         Ok(pkg)
     }
 
+    /// Hash of the files `cargo package` would include, from the working tree or as of `tag`
+    ///
+    /// As of `tag`, files deleted since then are included too, so a deletion is a change.
+    pub fn content_hash(&self, tag: Option<&str>) -> CargoResult<String> {
+        use sha2::Digest as _;
+
+        let mut paths = self.package_content.clone();
+        if let Some(tag) = tag {
+            let deleted = git::files_in_tag(&self.package_root, tag)?
+                .into_iter()
+                .filter(|path| !path.exists());
+            paths.extend(deleted);
+        }
+        paths.sort();
+        paths.dedup();
+        let content = if let Some(tag) = tag {
+            git::files_at_tag(&self.package_root, tag, &paths)?
+        } else {
+            paths.iter().map(|path| std::fs::read(path).ok()).collect()
+        };
+
+        let mut hasher = sha2::Sha256::new();
+        for (path, content) in paths.iter().zip(content) {
+            hasher.update(path.as_os_str().as_encoded_bytes());
+            hasher.update([0]);
+            if let Some(content) = content {
+                hasher.update((content.len() as u64).to_le_bytes());
+                hasher.update(content);
+            }
+            hasher.update([0]);
+        }
+        Ok(hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect())
+    }
+
     pub fn set_prior_tag(&mut self, prior_tag: String) {
         self.prior_tag = Some(prior_tag);
     }
@@ -233,8 +352,11 @@ impl PackageRelease {
                 }
             }
         }
-        self.planned_version =
-            level_or_version.bump(&self.initial_version.full_version, metadata)?;
+        self.planned_version = level_or_version.bump(
+            &self.initial_version.full_version,
+            metadata,
+            self.config.zero_ver_breaking(),
+        )?;
         Ok(())
     }
 
@@ -255,6 +377,7 @@ impl PackageRelease {
                 tag_name,
                 tag_prefix,
                 name,
+                self.location(),
                 &self.initial_version,
                 base,
             ))
@@ -266,12 +389,63 @@ impl PackageRelease {
 
         Ok(())
     }
+
+    /// The tag this package would have been given when releasing `version`
+    pub fn tag_for(&self, version: &Version) -> String {
+        render_tag(
+            self.config.tag_name(),
+            self.config.tag_prefix(self.is_root),
+            self.meta.name.as_str(),
+            self.location(),
+            version,
+            version,
+        )
+    }
+
+    /// Matches any tag this package could have been given
+    pub fn tag_glob(&self) -> String {
+        render_tag_glob(
+            self.config.tag_name(),
+            self.config.tag_prefix(self.is_root),
+            self.meta.name.as_str(),
+            self.location(),
+        )
+    }
+
+    /// The `package_path` and `manifest_dir` template variables
+    pub fn location(&self) -> (&str, Option<&str>) {
+        (self.package_path.as_str(), self.package_root.to_str())
+    }
+
+    /// `{{next_version}}`, see [`Version::next_version`]
+    pub fn next_version(&self) -> String {
+        let version = self
+            .planned_version
+            .as_ref()
+            .unwrap_or(&self.initial_version);
+        version.next_version(self.config.dev_version_ext())
+    }
+}
+
+/// `path` with `/` separators, `.` when empty
+fn slash_path(path: &Path) -> String {
+    let path = path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    if path.is_empty() {
+        ".".to_owned()
+    } else {
+        path
+    }
 }
 
 fn render_tag(
     tag_name: &str,
     tag_prefix: &str,
     name: &str,
+    (package_path, manifest_dir): (&str, Option<&str>),
     prev: &Version,
     base: &Version,
 ) -> String {
@@ -285,6 +459,8 @@ fn render_tag(
         version: Some(version_var),
         metadata: Some(metadata_var),
         crate_name: Some(name),
+        package_path: Some(package_path),
+        manifest_dir,
         ..Default::default()
     };
 
@@ -293,7 +469,12 @@ fn render_tag(
     template.render(tag_name)
 }
 
-fn render_tag_glob(tag_name: &str, tag_prefix: &str, name: &str) -> String {
+fn render_tag_glob(
+    tag_name: &str,
+    tag_prefix: &str,
+    name: &str,
+    (package_path, manifest_dir): (&str, Option<&str>),
+) -> String {
     let initial_version_var = "*";
     let existing_metadata_var = "*";
     let version_var = "*";
@@ -304,6 +485,8 @@ fn render_tag_glob(tag_name: &str, tag_prefix: &str, name: &str) -> String {
         version: Some(version_var),
         metadata: Some(metadata_var),
         crate_name: Some(name),
+        package_path: Some(package_path),
+        manifest_dir,
         ..Default::default()
     };
 
@@ -346,6 +529,28 @@ impl Version {
     pub fn is_prerelease(&self) -> bool {
         self.full_version.is_prerelease()
     }
+
+    /// The development version that work continues as after this release
+    ///
+    /// `dev_version_ext` is added to the pre-release, so it sorts between this release and the
+    /// next one: `1.2.3` is followed by `1.2.4-alpha.0` and `1.2.3-rc.1` by `1.2.3-rc.1.alpha.0`.
+    /// Without it, this is the next patch or, for a pre-release, the final release.
+    pub fn next_version(&self, dev_version_ext: &str) -> String {
+        let mut next = self.bare_version.clone();
+        if next.is_prerelease() {
+            next.pre = if dev_version_ext.is_empty() {
+                semver::Prerelease::EMPTY
+            } else {
+                semver::Prerelease::new(&format!("{}.{}", next.pre, dev_version_ext))
+                    .unwrap_or(semver::Prerelease::EMPTY)
+            };
+        } else {
+            next.increment_patch();
+            next.pre =
+                semver::Prerelease::new(dev_version_ext).unwrap_or(semver::Prerelease::EMPTY);
+        }
+        next.to_string()
+    }
 }
 
 impl From<semver::Version> for Version {
@@ -0,0 +1,220 @@
+use crate::error::CliError;
+use crate::ops::git;
+use crate::steps::plan;
+
+/// Finalize pre-release versions as stable
+#[derive(Debug, Clone, clap::Args)]
+pub struct PromoteStableStep {
+    #[command(flatten)]
+    manifest: clap_cargo::Manifest,
+
+    #[command(flatten)]
+    workspace: clap_cargo::Workspace,
+
+    /// Custom config file
+    #[arg(short, long = "config")]
+    custom_config: Option<std::path::PathBuf>,
+
+    /// Ignore implicit configuration files.
+    #[arg(long)]
+    isolated: bool,
+
+    /// Comma-separated globs of branch names a release can happen from
+    #[arg(long, value_delimiter = ',')]
+    allow_branch: Option<Vec<String>>,
+
+    /// Actually perform a release. Dry-run mode is the default
+    #[arg(short = 'x', long)]
+    execute: bool,
+
+    #[arg(short = 'n', long, conflicts_with = "execute", hide = true)]
+    dry_run: bool,
+
+    /// Skip release confirmation and version preview
+    #[arg(long)]
+    no_confirm: bool,
+}
+
+impl PromoteStableStep {
+    pub fn run(&self) -> Result<(), CliError> {
+        if self.dry_run {
+            let _ =
+                crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
+        }
+
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
+        let config = self.to_config();
+        let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
+        let mut pkgs = plan::load(&config, &ws_meta)?;
+
+        let stable = super::TargetVersion::Relative(super::BumpLevel::Release);
+        for pkg in pkgs.values_mut() {
+            if pkg.config.release() && pkg.initial_version.full_version.is_prerelease() {
+                pkg.bump(&stable, None)?;
+            }
+        }
+
+        let (_selected_pkgs, excluded_pkgs) = self.workspace.partition_packages(&ws_meta);
+        for excluded_pkg in excluded_pkgs {
+            let pkg = if let Some(pkg) = pkgs.get_mut(&excluded_pkg.id) {
+                pkg
+            } else {
+                // Either not in workspace or marked as `release = false`.
+                continue;
+            };
+            if !pkg.config.release() {
+                continue;
+            }
+
+            pkg.planned_version = None;
+            pkg.config.release = Some(false.into());
+        }
+
+        let pkgs = plan::plan(pkgs)?;
+
+        let (selected_pkgs, excluded_pkgs): (Vec<_>, Vec<_>) = pkgs
+            .into_iter()
+            .map(|(_, pkg)| pkg)
+            .partition(|p| p.config.release() && p.planned_version.is_some());
+        if selected_pkgs.is_empty() {
+            let _ = crate::ops::shell::error("no pre-releases selected");
+            return Err(2.into());
+        }
+
+        let dry_run = !self.execute;
+        let mut failed = false;
+
+        // STEP 0: Help the user make the right decisions.
+        failed |= !super::verify_git_is_clean(
+            ws_meta.workspace_root.as_std_path(),
+            dry_run,
+            log::Level::Warn,
+        )?;
+
+        failed |= !super::verify_git_branch(
+            ws_meta.workspace_root.as_std_path(),
+            &ws_config,
+            dry_run,
+            log::Level::Warn,
+        )?;
+
+        failed |= !super::verify_if_behind(
+            ws_meta.workspace_root.as_std_path(),
+            &ws_config,
+            dry_run,
+            log::Level::Warn,
+        )?;
+
+        // STEP 1: Release Confirmation
+        super::confirm("Promote", &selected_pkgs, self.no_confirm, dry_run)?;
+
+        // STEP 2: update current version
+        let update_lock =
+            super::version::update_versions(&ws_meta, &selected_pkgs, &excluded_pkgs, dry_run)?;
+        if update_lock {
+            super::update_lock(&ws_meta, &ws_config, &selected_pkgs, dry_run)?;
+        }
+
+        // STEP 3: carry the pre-releases' notes over to the stable release
+        for pkg in &selected_pkgs {
+            fold_changelog(pkg, dry_run)?;
+        }
+        // Pre-release tags are cleaned up once the stable release is pushed, see
+        // `delete_prerelease_tags`
+
+        super::finish(failed, dry_run)
+    }
+
+    fn to_config(&self) -> crate::config::ConfigArgs {
+        crate::config::ConfigArgs {
+            custom_config: self.custom_config.clone(),
+            isolated: self.isolated,
+            allow_branch: self.allow_branch.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+fn fold_changelog(pkg: &plan::PackageRelease, dry_run: bool) -> Result<(), CliError> {
+    let version = pkg
+        .planned_version
+        .as_ref()
+        .expect("only promoted packages are selected");
+    let path = pkg.package_root.join(pkg.config.changelog_path());
+    if !path.exists() {
+        log::debug!("no changelog for {} at {}", pkg.meta.name, path.display());
+        return Ok(());
+    }
+
+    let changelog = std::fs::read_to_string(&path)?;
+    let folded =
+        crate::ops::changelog::fold_prereleases(&changelog, version.bare_version_string.as_str())
+            .map_err(|e| anyhow::format_err!("{}: {}", path.display(), e))?;
+    if folded == changelog {
+        return Ok(());
+    }
+    if dry_run {
+        let _ = crate::ops::shell::status(
+            "Folding",
+            format!(
+                "pre-release notes in {}\n{}",
+                path.display(),
+                crate::ops::diff::unified_diff(&changelog, &folded, &path, "folded")
+            ),
+        );
+    } else {
+        let _ = crate::ops::shell::status(
+            "Folding",
+            format!("pre-release notes in {}", path.display()),
+        );
+        std::fs::write(&path, folded)?;
+    }
+
+    Ok(())
+}
+
+/// Delete the tags of the pre-releases leading up to `pkg`'s stable release, per
+/// `prerelease-tags`
+///
+/// Only done after the stable release is pushed, so the pre-releases stay tagged until it exists.
+pub fn delete_prerelease_tags(
+    ws_meta: &cargo_metadata::Metadata,
+    pkg: &plan::PackageRelease,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    if pkg.config.prerelease_tags() != crate::config::PrereleaseTags::Delete {
+        return Ok(());
+    }
+    let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+    if version.full_version.is_prerelease() {
+        return Ok(());
+    }
+    let tag_name = if let Some(tag_name) = pkg.planned_tag.as_deref() {
+        tag_name
+    } else {
+        return Ok(());
+    };
+    let root = ws_meta.workspace_root.as_std_path();
+    let prerelease_tags = git::tags_matching(root, &format!("{}-*", tag_name))?;
+    let prerelease_tags = prerelease_tags
+        .iter()
+        .map(|t| t.as_str())
+        .collect::<Vec<_>>();
+    for prerelease_tag in &prerelease_tags {
+        let _ = crate::ops::shell::status("Deleting", format!("tag {}", prerelease_tag));
+    }
+    if !git::delete_tags(root, &prerelease_tags, dry_run)? {
+        return Err(101.into());
+    }
+    if pkg.config.push() {
+        for remote in pkg.config.push_remotes() {
+            let remote_tags = git::remote_tags(root, remote, &prerelease_tags)?;
+            let remote_tags = remote_tags.iter().map(|t| t.as_str()).collect::<Vec<_>>();
+            if !git::delete_remote_tags(root, remote, &remote_tags, dry_run)? {
+                return Err(101.into());
+            }
+        }
+    }
+
+    Ok(())
+}
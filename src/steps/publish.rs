@@ -1,5 +1,6 @@
 use crate::error::CliError;
 use crate::ops::git;
+use crate::ops::replace::Template;
 use crate::steps::plan;
 
 /// Publish the specified packages
@@ -42,19 +43,12 @@ pub struct PublishStep {
 
 impl PublishStep {
     pub fn run(&self) -> Result<(), CliError> {
-        git::git_version()?;
-
         if self.dry_run {
             let _ =
                 crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
         }
 
-        let ws_meta = self
-            .manifest
-            .metadata()
-            // When evaluating dependency ordering, we need to consider optional dependencies
-            .features(cargo_metadata::CargoOpt::AllFeatures)
-            .exec()?;
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
         let config = self.to_config();
         let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
         let mut pkgs = plan::load(&config, &ws_meta)?;
@@ -72,7 +66,7 @@ impl PublishStep {
             }
 
             pkg.config.publish = Some(false);
-            pkg.config.release = Some(false);
+            pkg.config.release = Some(false.into());
 
             let crate_name = pkg.meta.name.as_str();
             log::debug!("disabled by user, skipping {}", crate_name,);
@@ -81,6 +75,7 @@ impl PublishStep {
         let mut pkgs = plan::plan(pkgs)?;
 
         let mut index = crate::ops::index::CratesIoIndex::new();
+        super::configure_index(&mut index, &ws_config, pkgs.values());
         for pkg in pkgs.values_mut() {
             if pkg.config.release() {
                 let crate_name = pkg.meta.name.as_str();
@@ -96,12 +91,12 @@ impl PublishStep {
                         version.full_version_string, crate_name
                     ));
                     pkg.config.publish = Some(false);
-                    pkg.config.release = Some(false);
+                    pkg.config.release = Some(false.into());
                 }
             }
         }
 
-        let (selected_pkgs, _excluded_pkgs): (Vec<_>, Vec<_>) = pkgs
+        let (selected_pkgs, excluded_pkgs): (Vec<_>, Vec<_>) = pkgs
             .into_iter()
             .map(|(_, pkg)| pkg)
             .partition(|p| p.config.release());
@@ -134,15 +129,38 @@ impl PublishStep {
             log::Level::Warn,
         )?;
 
+        failed |= !super::verify_push_remote_allowed(
+            ws_meta.workspace_root.as_std_path(),
+            &ws_config,
+            dry_run,
+        )?;
+
         failed |= !super::verify_metadata(&selected_pkgs, dry_run, log::Level::Error)?;
-        failed |=
-            !super::verify_rate_limit(&selected_pkgs, &mut index, dry_run, log::Level::Error)?;
+        failed |= !super::verify_lock_current(
+            &ws_meta,
+            &ws_config,
+            &selected_pkgs,
+            dry_run,
+            log::Level::Error,
+        )?;
+        failed |= !super::verify_dependency_reqs(
+            &selected_pkgs,
+            &excluded_pkgs,
+            &mut index,
+            dry_run,
+            log::Level::Error,
+        )?;
+        failed |= !verify_commands(&selected_pkgs, dry_run)?;
+        failed |= !super::verify_registry_health(&ws_config, &selected_pkgs, dry_run)?;
+        super::report_rate_limit(&selected_pkgs, &mut index)?;
 
         // STEP 1: Release Confirmation
         super::confirm("Publish", &selected_pkgs, self.no_confirm, dry_run)?;
 
         // STEP 3: cargo publish
+        failed |= !super::approval::approve(&ws_meta, &ws_config, &selected_pkgs, dry_run)?;
         publish(&ws_meta, &selected_pkgs, &mut index, dry_run)?;
+        await_publish(&selected_pkgs, dry_run)?;
 
         super::finish(failed, dry_run)
     }
@@ -158,18 +176,68 @@ impl PublishStep {
     }
 }
 
+/// Run every package's `verify-command`s, so a failing one stops the release before anything is
+/// published
+pub fn verify_commands(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<bool, CliError> {
+    let mut success = true;
+
+    for pkg in pkgs {
+        if !pkg.config.publish() {
+            continue;
+        }
+        let crate_name = pkg.meta.name.as_str();
+        let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+        for command in pkg.config.verify_command() {
+            let (package_path, manifest_dir) = pkg.location();
+            let template = Template {
+                crate_name: Some(crate_name),
+                version: Some(version.bare_version_string.as_str()),
+                metadata: Some(version.full_version.build.as_str()),
+                package_path: Some(package_path),
+                manifest_dir,
+                ..Default::default()
+            };
+            let command = command
+                .args()
+                .into_iter()
+                .map(|arg| template.render(arg))
+                .collect::<Vec<_>>();
+            let command_line = command.join(" ");
+            let _ = crate::ops::shell::status(
+                "Verifying",
+                format!("{} with `{}`", crate_name, command_line),
+            );
+            if !crate::ops::cmd::call_on_path(command, &pkg.package_root, false)? {
+                let _ = crate::ops::shell::error(format!(
+                    "`{}` failed for {}",
+                    command_line, crate_name
+                ));
+                success = false;
+                if !dry_run {
+                    return Err(101.into());
+                }
+            }
+        }
+    }
+
+    Ok(success)
+}
+
 pub fn publish(
     ws_meta: &cargo_metadata::Metadata,
     pkgs: &[plan::PackageRelease],
     index: &mut crate::ops::index::CratesIoIndex,
     dry_run: bool,
 ) -> Result<(), CliError> {
+    let mut publish_log = crate::ops::rate_limit::PublishLog::load();
+    let mut published_any = false;
     for pkg in pkgs {
         if !pkg.config.publish() {
             continue;
         }
 
         let crate_name = pkg.meta.name.as_str();
+        let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
         let _ = crate::ops::shell::status("Publishing", crate_name);
 
         let verify = if !pkg.config.verify() {
@@ -181,7 +249,8 @@ pub fn publish(
             true
         };
         // feature list to release
-        let features = &pkg.features;
+        let verify_features = pkg.config.verify_features();
+        let features = verify_features.as_ref().unwrap_or(&pkg.features);
         let pkgid = if 1 < ws_meta.workspace_members.len() {
             // Override `workspace.default-members`
             Some(crate_name)
@@ -189,20 +258,103 @@ pub fn publish(
             // `-p` is not recommended outside of a workspace
             None
         };
+        let target_dir = pkg
+            .config
+            .release_target_dir(ws_meta.workspace_root.as_std_path());
+        let verify_options = crate::ops::cargo::VerifyOptions {
+            target_dir: target_dir.as_deref(),
+            ..pkg.config.verify_options()
+        };
+        let verify_targets = pkg.config.verify_targets();
+        if verify {
+            if let Some(profile) = pkg.config.verify_profile() {
+                let _ = crate::ops::shell::status(
+                    "Verifying",
+                    format!("{} with profile {}", crate_name, profile),
+                );
+                if !crate::ops::cargo::verify_profile(
+                    &verify_options,
+                    profile,
+                    &pkg.manifest_path,
+                    pkgid,
+                    features,
+                    pkg.config.target.as_deref(),
+                )? {
+                    return Err(101.into());
+                }
+            }
+            for target in verify_targets {
+                let _ = crate::ops::shell::status(
+                    "Verifying",
+                    format!("{} for {}", crate_name, target),
+                );
+                if !crate::ops::cargo::verify_package(
+                    dry_run,
+                    &verify_options,
+                    &pkg.manifest_path,
+                    pkgid,
+                    features,
+                    target,
+                )? {
+                    return Err(101.into());
+                }
+            }
+        }
+        // Already verified above
+        let verify = verify && verify_targets.is_empty();
+
+        if let (Some(interval), true) = (pkg.config.publish_interval(), published_any) {
+            let _ = crate::ops::shell::status(
+                "Waiting",
+                format!(
+                    "{} before publishing {}",
+                    crate::config::Interval(interval),
+                    crate_name
+                ),
+            );
+            if !dry_run {
+                std::thread::sleep(interval);
+            }
+        }
+
+        let rate_limit = pkg.config.rate_limit();
+        let is_new = rate_limit.is_some() && !index.has_krate(pkg.config.registry(), crate_name)?;
+        if let (Some(rate_limit), false) = (rate_limit.as_ref(), dry_run) {
+            let wait = publish_log.wait_for(pkg.config.registry(), rate_limit, is_new, 1);
+            if !wait.is_zero() {
+                crate::ops::rate_limit::pause(
+                    wait,
+                    &format!(
+                        "the {} rate limit",
+                        pkg.config.registry().unwrap_or("crates.io")
+                    ),
+                );
+            }
+        }
+
         if !crate::ops::cargo::publish(
             dry_run,
             verify,
+            &verify_options,
             &pkg.manifest_path,
             pkgid,
             features,
             pkg.config.registry(),
+            &pkg.config.registry_env(),
             pkg.config.target.as_ref().map(AsRef::as_ref),
         )? {
             return Err(101.into());
         }
+        published_any = true;
+        if rate_limit.is_some() && !dry_run {
+            if let Err(err) = publish_log.record(pkg.config.registry(), crate_name, is_new) {
+                let _ = crate::ops::shell::warn(format!(
+                    "could not record publishing {crate_name} for later rate limits: {err}"
+                ));
+            }
+        }
 
         let timeout = std::time::Duration::from_secs(300);
-        let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
         crate::ops::cargo::wait_for_publish(
             index,
             pkg.config.registry(),
@@ -231,3 +383,235 @@ pub fn publish(
 
     Ok(())
 }
+
+pub fn await_publish(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<(), CliError> {
+    use crate::config::AwaitTarget;
+    use crate::ops::docs_rs::BuildStatus;
+
+    let mut pending = Vec::new();
+    for pkg in pkgs {
+        if !pkg.config.publish() {
+            continue;
+        }
+        let crate_name = pkg.meta.name.as_str();
+        if pkg.config.registry().is_some() {
+            if !pkg.config.await_targets().is_empty() {
+                log::debug!("not awaiting {crate_name} as that is only supported for crates.io");
+            }
+            continue;
+        }
+        let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+        for target in pkg.config.await_targets() {
+            pending.push((*target, crate_name, version.full_version_string.as_str()));
+        }
+    }
+    if pending.is_empty() {
+        return Ok(());
+    }
+    if dry_run {
+        for (target, crate_name, version) in pending {
+            let _ = crate::ops::shell::status(
+                "Awaiting",
+                format!("{target} for {crate_name} {version}"),
+            );
+        }
+        return Ok(());
+    }
+
+    let api = crate::ops::crates_io::CratesIoApi::open()?;
+    let docs = crate::ops::docs_rs::DocsRs::open()?;
+    // docs.rs builds are queued behind everyone else's
+    let timeout = if pending.iter().any(|(t, _, _)| *t == AwaitTarget::Docs) {
+        std::time::Duration::from_secs(60 * 60)
+    } else {
+        std::time::Duration::from_secs(300)
+    };
+    let sleep_time = std::time::Duration::from_secs(10);
+    let now = std::time::Instant::now();
+    let mut failed = false;
+    loop {
+        // Check everything pending at once rather than one request after another
+        let statuses =
+            crate::ops::http::run_all(pending.iter().map(|(target, crate_name, version)| {
+                let (api, docs) = (&api, &docs);
+                async move {
+                    match target {
+                        AwaitTarget::Index => {
+                            api.has_krate_version(crate_name, version)
+                                .await
+                                .map(|ready| {
+                                    if ready {
+                                        BuildStatus::Succeeded
+                                    } else {
+                                        BuildStatus::Pending
+                                    }
+                                })
+                        }
+                        AwaitTarget::Docs => docs.build_status(crate_name, version).await,
+                    }
+                }
+            }));
+        let mut statuses = statuses.into_iter();
+        pending.retain(|(target, crate_name, version)| {
+            let status = statuses.next().expect("one status per pending target");
+            match status {
+                Ok(BuildStatus::Pending) => true,
+                Ok(BuildStatus::Succeeded) => {
+                    let _ = crate::ops::shell::status(
+                        "Ready",
+                        format!("{target} for {crate_name} {version}"),
+                    );
+                    false
+                }
+                Ok(BuildStatus::Failed) => {
+                    let _ = crate::ops::shell::error(format!(
+                        "{target} failed for {crate_name} {version}"
+                    ));
+                    failed = true;
+                    false
+                }
+                Err(err) => {
+                    // Could be transient, keep trying until the timeout
+                    log::debug!("failed to check {target} for {crate_name}: {err:#}");
+                    true
+                }
+            }
+        });
+        if pending.is_empty() {
+            break;
+        } else if timeout < now.elapsed() {
+            for (target, crate_name, version) in &pending {
+                let _ = crate::ops::shell::error(format!(
+                    "timeout waiting on {target} for {crate_name} {version}"
+                ));
+            }
+            failed = true;
+            break;
+        }
+
+        std::thread::sleep(sleep_time);
+    }
+
+    if failed {
+        return Err(101.into());
+    }
+
+    Ok(())
+}
+
+/// Write `release-manifest`, if configured, describing what was just released
+///
+/// It goes in the target directory, keeping the just-pushed tree clean, and is attached to the
+/// forge releases for the new tags.
+pub fn release_manifest(
+    ws_config: &crate::config::Config,
+    ws_meta: &cargo_metadata::Metadata,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<(), CliError> {
+    use crate::ops::release_manifest::{ReleaseManifest, ReleasedCrate};
+    use itertools::Itertools;
+
+    let root = ws_meta.workspace_root.as_std_path();
+    let path = if let Some(path) = ws_config.release_manifest() {
+        ws_meta.target_directory.as_std_path().join(path)
+    } else {
+        return Ok(());
+    };
+
+    // `cargo publish` leaves the packaged crates behind
+    let crates = pkgs
+        .iter()
+        .map(|pkg| {
+            let package_dir = pkg
+                .config
+                .release_target_dir(root)
+                .unwrap_or_else(|| ws_meta.target_directory.clone().into_std_path_buf())
+                .join("package");
+            let name = pkg.meta.name.clone();
+            let version = pkg
+                .planned_version
+                .as_ref()
+                .unwrap_or(&pkg.initial_version)
+                .full_version_string
+                .clone();
+            let crate_path = package_dir.join(format!("{name}-{version}.crate"));
+            let sha256 = if !pkg.config.publish() || dry_run {
+                None
+            } else {
+                match crate::ops::release_manifest::hash_file(&crate_path) {
+                    Ok(sha256) => Some(sha256),
+                    Err(err) => {
+                        let _ = crate::ops::shell::warn(format!(
+                            "could not checksum {}: {err}",
+                            crate_path.display()
+                        ));
+                        None
+                    }
+                }
+            };
+            ReleasedCrate {
+                name,
+                version,
+                tag: pkg.planned_tag.clone(),
+                sha256,
+            }
+        })
+        .collect();
+    let manifest = ReleaseManifest {
+        commit: git::head_id(ws_meta.workspace_root.as_std_path())?,
+        umbrella_tag: super::tag::planned_umbrella_tag(ws_config),
+        crates,
+    };
+
+    let _ = crate::ops::shell::status("Writing", format!("release manifest to {}", path.display()));
+    let written = crate::ops::release_manifest::write(
+        &path,
+        &manifest,
+        ws_config.release_manifest_signature(),
+        dry_run,
+    )?;
+    for signature in &written[1..] {
+        let _ = crate::ops::shell::status("Signed", signature.display());
+    }
+
+    let tags = if let Some(umbrella_tag) = manifest.umbrella_tag.as_ref() {
+        vec![umbrella_tag.as_str()]
+    } else {
+        manifest
+            .crates
+            .iter()
+            .filter_map(|c| c.tag.as_deref())
+            .unique()
+            .collect()
+    };
+    let repository_url = if let Some(url) = git::remote_url(root, ws_config.push_remote())?
+        .as_deref()
+        .and_then(crate::ops::forge::repository_url)
+    {
+        url
+    } else {
+        return Ok(());
+    };
+    for tag in tags {
+        match crate::ops::forge::upload_release_assets(&repository_url, tag, &written, dry_run) {
+            Ok(Some(true)) => {
+                let _ = crate::ops::shell::status(
+                    "Attaching",
+                    format!("release manifest to release {tag} on {repository_url}"),
+                );
+            }
+            Ok(Some(false)) => {
+                log::debug!("no release for {tag} on {repository_url} to attach the manifest to");
+            }
+            Ok(None) => {}
+            Err(err) => {
+                let _ = crate::ops::shell::warn(format!(
+                    "could not attach release manifest to {tag}: {err}"
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use std::collections::HashSet;
 
+use crate::config::PushStrategy;
 use crate::error::CliError;
 use crate::ops::git;
+use crate::ops::replace::{Template, NOW};
 use crate::steps::plan;
 
 /// Push tags/commits to remote
@@ -45,19 +47,12 @@ pub struct PushStep {
 
 impl PushStep {
     pub fn run(&self) -> Result<(), CliError> {
-        git::git_version()?;
-
         if self.dry_run {
             let _ =
                 crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
         }
 
-        let ws_meta = self
-            .manifest
-            .metadata()
-            // When evaluating dependency ordering, we need to consider optional dependencies
-            .features(cargo_metadata::CargoOpt::AllFeatures)
-            .exec()?;
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
         let config = self.to_config();
         let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
         let mut pkgs = plan::load(&config, &ws_meta)?;
@@ -75,7 +70,7 @@ impl PushStep {
             }
 
             pkg.config.push = Some(false);
-            pkg.config.release = Some(false);
+            pkg.config.release = Some(false.into());
 
             let crate_name = pkg.meta.name.as_str();
             log::debug!("disabled by user, skipping {}", crate_name,);
@@ -118,6 +113,18 @@ impl PushStep {
             log::Level::Warn,
         )?;
 
+        failed |= !super::verify_push_remote_allowed(
+            ws_meta.workspace_root.as_std_path(),
+            &ws_config,
+            dry_run,
+        )?;
+
+        failed |= !super::verify_branch_protection(
+            ws_meta.workspace_root.as_std_path(),
+            &ws_config,
+            dry_run,
+        )?;
+
         // STEP 1: Release Confirmation
         super::confirm("Push", &selected_pkgs, self.no_confirm, dry_run)?;
 
@@ -139,52 +146,164 @@ impl PushStep {
     }
 }
 
+/// Push the release to each of the push remotes
+///
+/// Returns whether every remote was pushed to, so `false` when pushing is disabled.
 pub fn push(
     ws_config: &crate::config::Config,
     ws_meta: &cargo_metadata::Metadata,
     pkgs: &[plan::PackageRelease],
     dry_run: bool,
-) -> Result<(), CliError> {
-    if ws_config.push() {
-        let git_remote = ws_config.push_remote();
-        let branch = git::current_branch(ws_meta.workspace_root.as_std_path())?;
-
-        let mut shared_refs = HashSet::new();
-        for pkg in pkgs {
-            if !pkg.config.push() {
-                continue;
-            }
+) -> Result<bool, CliError> {
+    if !ws_config.push() {
+        return Ok(false);
+    }
 
-            if !git::is_local_unchanged(
-                ws_meta.workspace_root.as_std_path(),
-                git_remote,
-                branch.as_str(),
-            )? || dry_run
-            {
-                shared_refs.insert(branch.as_str());
-            }
-            if let Some(tag_name) = pkg.planned_tag.as_deref() {
-                shared_refs.insert(tag_name);
+    let mut pushed = true;
+    for git_remote in ws_config.push_remotes() {
+        if !push_remote(ws_config, ws_meta, pkgs, git_remote, dry_run)? {
+            match ws_config.remote_on_failure(git_remote) {
+                crate::config::PushFailure::Abort => return Err(101.into()),
+                crate::config::PushFailure::Warn => {
+                    let _ = crate::ops::shell::warn(format!(
+                        "failed to push to {git_remote}, continuing"
+                    ));
+                    pushed = false;
+                }
             }
         }
-        if !shared_refs.is_empty() {
-            let mut shared_refs = shared_refs.into_iter().collect::<Vec<_>>();
-            shared_refs.sort_unstable();
-            let _ = crate::ops::shell::status(
-                "Pushing",
-                format!("Pushing {} to {}", shared_refs.join(", "), git_remote),
-            );
-            if !git::push(
-                ws_meta.workspace_root.as_std_path(),
-                git_remote,
-                shared_refs,
-                ws_config.push_options(),
-                dry_run,
-            )? {
-                return Err(101.into());
+    }
+
+    Ok(pushed)
+}
+
+fn push_remote(
+    ws_config: &crate::config::Config,
+    ws_meta: &cargo_metadata::Metadata,
+    pkgs: &[plan::PackageRelease],
+    git_remote: &str,
+    dry_run: bool,
+) -> Result<bool, CliError> {
+    let branch = git::current_branch(ws_meta.workspace_root.as_std_path())?;
+    let strategy = ws_config.push_strategy();
+
+    let umbrella_tag = super::tag::planned_umbrella_tag(ws_config);
+
+    let root = ws_meta.workspace_root.as_std_path();
+    let mut shared_refs = HashSet::new();
+    let mut tags = Vec::new();
+    for pkg in pkgs {
+        if !pkg.config.push() {
+            continue;
+        }
+
+        let push_branch = match strategy {
+            PushStrategy::Refspec => {
+                !git::is_local_unchanged(
+                    ws_meta.workspace_root.as_std_path(),
+                    git_remote,
+                    branch.as_str(),
+                )? || dry_run
             }
+            // Even an up-to-date branch is needed for git to follow its tags
+            PushStrategy::FollowTags => true,
+            PushStrategy::TagsOnly => false,
+        };
+        if push_branch {
+            shared_refs.insert(branch.as_str());
+        }
+        if let Some(tag_name) = pkg.planned_tag.as_deref() {
+            tags.push(tag_name);
         }
     }
+    if let Some(tag_name) = umbrella_tag.as_deref() {
+        // In dry-run, the tag would have been created by now
+        if dry_run || git::tag_exists(root, tag_name)? {
+            tags.push(tag_name);
+        }
+    }
+    let branch_pushed = shared_refs.contains(branch.as_str());
+    for tag_name in tags {
+        // git only brings along annotated tags reachable from a pushed branch
+        let followed = strategy == PushStrategy::FollowTags
+            && branch_pushed
+            && (dry_run || git::is_annotated_tag(root, tag_name)?);
+        if !followed {
+            shared_refs.insert(tag_name);
+        }
+    }
+    if shared_refs.is_empty() {
+        return Ok(true);
+    }
 
-    Ok(())
+    let mut shared_refs = shared_refs.into_iter().collect::<Vec<_>>();
+    shared_refs.sort_unstable();
+    let _ = crate::ops::shell::status(
+        "Pushing",
+        format!("Pushing {} to {}", shared_refs.join(", "), git_remote),
+    );
+    let options = push_options(ws_config, git_remote, pkgs, umbrella_tag.as_deref());
+    Ok(git::push(
+        ws_meta.workspace_root.as_std_path(),
+        git_remote,
+        shared_refs,
+        options.iter().map(|o| o.as_str()),
+        strategy == PushStrategy::FollowTags,
+        dry_run,
+    )?)
+}
+
+/// Render `push-options` for `git_remote`
+///
+/// `{{version}}`, `{{next_version}}`, `{{crate_name}}`, and `{{tag_name}}` are only known when
+/// every pushed package agrees on them, like with a single package or `shared-version`.
+fn push_options(
+    ws_config: &crate::config::Config,
+    git_remote: &str,
+    pkgs: &[plan::PackageRelease],
+    umbrella_tag: Option<&str>,
+) -> Vec<String> {
+    let pushed = pkgs.iter().filter(|p| p.config.push()).collect::<Vec<_>>();
+    let versions = pushed
+        .iter()
+        .map(|p| p.planned_version.as_ref().unwrap_or(&p.initial_version))
+        .collect::<Vec<_>>();
+    let version = versions
+        .first()
+        .filter(|first| {
+            versions
+                .iter()
+                .all(|v| v.full_version == first.full_version)
+        })
+        .copied();
+    let crate_name = match pushed.as_slice() {
+        [pkg] => Some(pkg.meta.name.as_str()),
+        _ => None,
+    };
+    let tag_name = match pushed.as_slice() {
+        [pkg] => pkg.planned_tag.as_deref(),
+        _ => umbrella_tag,
+    };
+    let prev_tag_name = match pushed.as_slice() {
+        [pkg] => pkg.prior_tag.as_deref(),
+        _ => None,
+    };
+    let next_version = match pushed.as_slice() {
+        [pkg] => Some(pkg.next_version()),
+        _ => version.map(|v| v.next_version(ws_config.dev_version_ext())),
+    };
+    let template = Template {
+        version: version.map(|v| v.bare_version_string.as_str()),
+        metadata: version.map(|v| v.full_version.build.as_str()),
+        next_version: next_version.as_deref(),
+        crate_name,
+        tag_name,
+        prev_tag_name,
+        date: Some(NOW.as_str()),
+        ..Default::default()
+    };
+    ws_config
+        .remote_push_options(git_remote)
+        .map(|o| template.render(o))
+        .collect()
 }
@@ -24,6 +24,17 @@ pub struct ReleaseStep {
     #[arg(short, long, requires = "level_or_version")]
     metadata: Option<String>,
 
+    /// Also release dependents whose requirements get updated, bumping them by LEVEL
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "patch",
+        value_enum
+    )]
+    bump_dependents: Option<super::BumpLevel>,
+
     /// Actually perform a release. Dry-run mode is the default
     #[arg(short = 'x', long)]
     execute: bool,
@@ -35,32 +46,378 @@ pub struct ReleaseStep {
     #[arg(long)]
     no_confirm: bool,
 
+    /// Allow releasing packages that were never published
+    #[arg(long)]
+    allow_first_release: bool,
+
+    /// Allow publishing new crates whose names resemble existing ones
+    #[arg(long)]
+    allow_new_name: bool,
+
+    /// Release a patch of an older version line
+    #[arg(long, conflicts_with_all = ["from_tag", "amend"])]
+    backport: bool,
+
+    /// Skip confirmation for these actions
+    #[arg(long, value_delimiter = ',', value_name = "ACTION[,...]", value_enum)]
+    yes: Vec<config::ConfirmAction>,
+
     /// The name of tag for the previous release.
     #[arg(long, value_name = "NAME")]
     prev_tag_name: Option<String>,
 
+    /// Publish the release at TAG without changing the repo
+    #[arg(long, value_name = "TAG", conflicts_with = "level_or_version")]
+    from_tag: Option<String>,
+
+    /// Amend the unpushed release commit and move its tags
+    #[arg(long, conflicts_with_all = ["level_or_version", "from_tag"])]
+    amend: bool,
+
+    /// Release these workspaces, in dependency order
+    #[arg(long, value_name = "PATH", conflicts_with = "manifest_path")]
+    workspace_root: Vec<std::path::PathBuf>,
+
+    /// Run the full release in a scratch clone against staging targets
+    #[arg(long, conflicts_with = "dry_run")]
+    rehearse: bool,
+
+    /// Release in a scratch clone, only updating this repo on success
+    #[arg(long, conflicts_with = "rehearse")]
+    isolated_worktree: bool,
+
+    /// Set on the release `--rehearse` runs, which must not ask for approval or announce
+    #[arg(skip)]
+    rehearsal: bool,
+
+    /// Fail right before STEP, to drill recovery procedures
+    #[arg(long, value_name = "STEP", value_enum)]
+    fail_at: Option<FailPoint>,
+
     #[command(flatten)]
     config: config::ConfigArgs,
 }
 
+/// A stage of the release that `--fail-at` can interrupt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum FailPoint {
+    Commit,
+    Publish,
+    Tag,
+    Push,
+}
+
+impl std::fmt::Display for FailPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use clap::ValueEnum;
+        self.to_possible_value()
+            .expect("no values are skipped")
+            .get_name()
+            .fmt(f)
+    }
+}
+
+/// How far [`ReleaseStep::release_workspace`] goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WorkspacePass {
+    /// Stop after the pre-flight checks
+    Check,
+    /// Release, with `prechecked` when a [`WorkspacePass::Check`] pass already ran
+    Release { prechecked: bool },
+}
+
+/// A copy of the release pointed at a scratch clone of the repo
+struct ScratchRelease {
+    /// The repo that was cloned
+    top_level: std::path::PathBuf,
+    path: std::path::PathBuf,
+    step: ReleaseStep,
+    ws_config: config::Config,
+}
+
 impl ReleaseStep {
     pub fn run(&self) -> Result<(), CliError> {
-        git::git_version()?;
+        if self.rehearse {
+            return self.rehearse();
+        }
+        if self.isolated_worktree {
+            return self.release_isolated();
+        }
+        if let Some(tag_name) = self.from_tag.as_deref() {
+            let cwd = std::env::current_dir()?;
+            let top_level = git::top_level(&self.start_dir(&cwd))?;
+            if git::tag_exists(&top_level, tag_name)? && !git::is_head_at_tag(&top_level, tag_name)?
+            {
+                return self.release_tag_checkout(tag_name);
+            }
+        }
         let mut index = crate::ops::index::CratesIoIndex::new();
 
         if self.dry_run {
             let _ =
                 crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
         }
+        if !self.config.skip.is_empty() {
+            let skipped = self
+                .config
+                .skip
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>();
+            let _ = crate::ops::shell::note(format!("skipping {}", skipped.join(", ")));
+        }
+
+        let ws_metas = if self.workspace_root.is_empty() {
+            let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
+            vec![ws_meta]
+        } else {
+            let ws_metas = self
+                .workspace_root
+                .iter()
+                .map(|root| crate::ops::metadata::workspace(Some(&root.join("Cargo.toml"))))
+                .collect::<Result<Vec<_>, _>>()?;
+            cargo::sort_workspaces(ws_metas)?
+        };
+
+        let dry_run = !self.execute;
+        // Check every workspace before releasing any, rather than stopping halfway through
+        let prechecked = !dry_run && 1 < ws_metas.len();
+        if prechecked {
+            let mut failed = false;
+            for ws_meta in &ws_metas {
+                failed |= self
+                    .release_workspace(ws_meta, &mut index, WorkspacePass::Check, dry_run)?
+                    .unwrap_or(false);
+            }
+            if failed {
+                return Err(101.into());
+            }
+        }
+        let pass = WorkspacePass::Release { prechecked };
+        let mut failed = false;
+        let mut released = false;
+        for ws_meta in &ws_metas {
+            if let Some(ws_failed) = self.release_workspace(ws_meta, &mut index, pass, dry_run)? {
+                failed |= ws_failed;
+                released = true;
+            }
+        }
+        if !released {
+            let _ = crate::ops::shell::error("no packages selected");
+            return Err(2.into());
+        }
+
+        super::finish(failed, dry_run)
+    }
+
+    /// Re-run this release with `--execute` inside a scratch clone of the repo
+    ///
+    /// Publishing goes to `rehearsal-registry` and pushing to `rehearsal-remote`; either is
+    /// skipped when not configured so a rehearsal can never touch the real targets.
+    fn rehearse(&self) -> Result<(), CliError> {
+        let mut scratch = self.scratch_release("rehearsal")?;
+        let _ = crate::ops::shell::status(
+            "Rehearsing",
+            format!("release in {}", scratch.path.display()),
+        );
+        let remote = if let Some(url) = scratch.ws_config.rehearsal_remote() {
+            if !git::add_remote(&scratch.path, "rehearsal", url)? {
+                return Err(101.into());
+            }
+            Some("rehearsal")
+        } else {
+            let _ = crate::ops::shell::note("no `rehearsal-remote` configured, skipping push");
+            None
+        };
+        let registry = scratch.ws_config.rehearsal_registry();
+        if registry.is_none() {
+            let _ = crate::ops::shell::note("no `rehearsal-registry` configured, skipping publish");
+        }
+        if scratch.ws_config.approval().is_some() {
+            let _ = crate::ops::shell::note("skipping `approval` in a rehearsal");
+        }
+        if scratch.ws_config.notify().is_some() {
+            let _ = crate::ops::shell::note("skipping `notify` in a rehearsal");
+        }
+
+        scratch.step.execute = true;
+        scratch.step.rehearsal = true;
+        scratch.step.no_confirm = true;
+        scratch.step.config = self.config.rehearsal(registry, remote);
+
+        let result = scratch.step.run();
+        let _ = crate::ops::shell::note(format!(
+            "rehearsal clone left at {} for inspection",
+            scratch.path.display()
+        ));
+        result
+    }
+
+    /// Run this release inside a scratch clone, only bringing the result back on success
+    ///
+    /// The clone pushes to the same remotes as the original repo.  Afterwards, the release commit
+    /// and tags are fast-forwarded into the original repo so a failure never leaves edited
+    /// manifests or stray tags in the user's working copy.  A failed clone is removed unless the
+    /// release got as far as committing, as the commit may be needed to recover.
+    fn release_isolated(&self) -> Result<(), CliError> {
+        let scratch = self.scratch_release("worktree")?;
+        let _ = crate::ops::shell::status(
+            "Isolating",
+            format!("release in {}", scratch.path.display()),
+        );
+        git::mirror_remotes(&scratch.top_level, &scratch.path)?;
+        let start = git::head_id(&scratch.path)?;
+
+        if let Err(err) = scratch.step.run() {
+            if git::head_id(&scratch.path)? == start {
+                let _ = std::fs::remove_dir_all(&scratch.path);
+                let _ = crate::ops::shell::note("working copy left untouched");
+            } else {
+                let _ = crate::ops::shell::note(format!(
+                    "working copy left untouched, failed release is at {}",
+                    scratch.path.display()
+                ));
+            }
+            return Err(err);
+        }
+
+        if self.execute {
+            let branch = git::current_branch(&scratch.path)?;
+            let _ = crate::ops::shell::status(
+                "Updating",
+                format!("{} with the release", scratch.top_level.display()),
+            );
+            if !git::pull_release(&scratch.top_level, &scratch.path, &branch)? {
+                let _ = crate::ops::shell::error(format!(
+                    "could not fast-forward to the release, it is still at {}",
+                    scratch.path.display()
+                ));
+                return Err(101.into());
+            }
+        }
+        let _ = std::fs::remove_dir_all(&scratch.path);
+        Ok(())
+    }
+
+    /// Publish `tag_name` from a scratch clone checked out at it, leaving the working copy alone
+    fn release_tag_checkout(&self, tag_name: &str) -> Result<(), CliError> {
+        let scratch = self.scratch_release("tag")?;
+        let _ = crate::ops::shell::status(
+            "Checking out",
+            format!("{} in {}", tag_name, scratch.path.display()),
+        );
+        git::mirror_remotes(&scratch.top_level, &scratch.path)?;
+        if !git::checkout_detached(&scratch.path, tag_name)? {
+            return Err(101.into());
+        }
 
-        let ws_meta = self
-            .manifest
-            .metadata()
-            // When evaluating dependency ordering, we need to consider optional dependencies
-            .features(cargo_metadata::CargoOpt::AllFeatures)
+        let result = scratch.step.run();
+        if result.is_ok() {
+            let _ = std::fs::remove_dir_all(&scratch.path);
+        } else {
+            let _ = crate::ops::shell::note(format!(
+                "checkout of {} left at {} for inspection",
+                tag_name,
+                scratch.path.display()
+            ));
+        }
+        result
+    }
+
+    /// Where the release starts from, relative to `cwd`
+    fn start_dir(&self, cwd: &std::path::Path) -> std::path::PathBuf {
+        let start = self
+            .workspace_root
+            .first()
+            .cloned()
+            .or_else(|| {
+                self.manifest
+                    .manifest_path
+                    .as_ref()
+                    .and_then(|p| p.parent().map(|p| p.to_owned()))
+            })
+            .unwrap_or_else(|| cwd.to_owned());
+        cwd.join(start)
+    }
+
+    /// Clone the repo being released into a temp dir and point a copy of this step at it
+    fn scratch_release(&self, purpose: &str) -> Result<ScratchRelease, CliError> {
+        if git::is_env_selected() {
+            // Every `git` call in the scratch clone would act on `GIT_DIR` instead
+            let _ = crate::ops::shell::error(
+                "`--rehearse`, `--isolated-worktree`, and `--from-tag` off of `HEAD` are unsupported with `GIT_DIR` set",
+            );
+            return Err(2.into());
+        }
+        let cwd = std::env::current_dir()?;
+        let start = self.start_dir(&cwd);
+        let top_level = git::top_level(&start)?;
+        let ws_meta = cargo_metadata::MetadataCommand::new()
+            .current_dir(&start)
+            .no_deps()
             .exec()?;
         let ws_config = config::load_workspace_config(&self.config, &ws_meta)?;
-        let mut pkgs = plan::load(&self.config, &ws_meta)?;
+
+        let path =
+            std::env::temp_dir().join(format!("cargo-release-{}-{}", purpose, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        if !git::scratch_clone(&top_level, &path)? {
+            return Err(101.into());
+        }
+
+        let relocate = |p: &std::path::Path| -> std::path::PathBuf {
+            let p = cwd.join(p);
+            match p.strip_prefix(&top_level) {
+                Ok(rel) => path.join(rel),
+                Err(_) => p,
+            }
+        };
+        let mut step = self.clone();
+        step.rehearse = false;
+        step.isolated_worktree = false;
+        step.manifest.manifest_path = Some(relocate(
+            &self
+                .manifest
+                .manifest_path
+                .clone()
+                .unwrap_or_else(|| start.join("Cargo.toml")),
+        ));
+        if !self.workspace_root.is_empty() {
+            step.manifest.manifest_path = None;
+            step.workspace_root = self.workspace_root.iter().map(|p| relocate(p)).collect();
+        }
+
+        Ok(ScratchRelease {
+            top_level,
+            path,
+            step,
+            ws_config,
+        })
+    }
+
+    /// Stop the release before `point` when `--fail-at` asks for it
+    fn inject_failure(&self, point: FailPoint) -> Result<(), CliError> {
+        if self.fail_at == Some(point) {
+            let _ = crate::ops::shell::error(format!("injected failure before {point}"));
+            return Err(101.into());
+        }
+        Ok(())
+    }
+
+    /// Release the selected packages within one workspace
+    ///
+    /// Returns whether any checks failed or `None` if no packages were selected
+    fn release_workspace(
+        &self,
+        ws_meta: &cargo_metadata::Metadata,
+        index: &mut crate::ops::index::CratesIoIndex,
+        pass: WorkspacePass,
+        dry_run: bool,
+    ) -> Result<Option<bool>, CliError> {
+        let ws_config = config::load_workspace_config(&self.config, ws_meta)?;
+        let mut pkgs = plan::load(&self.config, ws_meta)?;
+        super::configure_index(index, &ws_config, pkgs.values());
 
         for pkg in pkgs.values_mut() {
             if let Some(prev_tag) = self.prev_tag_name.as_ref() {
@@ -76,10 +433,22 @@ impl ReleaseStep {
             if index.has_krate(pkg.config.registry(), &pkg.meta.name)? {
                 // Already published, skip it.  Use `cargo release owner` for one-time updates
                 pkg.ensure_owners = false;
+            } else if pkg.config.publish() && pkg.prior_tag.is_none() {
+                pkg.first_release = true;
+                if let Some(commit_msg) = pkg.config.first_release_commit_message() {
+                    pkg.config.pre_release_commit_message = Some(commit_msg.to_owned());
+                }
             }
         }
 
-        let (_selected_pkgs, excluded_pkgs) = self.workspace.partition_packages(&ws_meta);
+        let (_selected_pkgs, excluded_pkgs) = self.workspace.partition_packages(ws_meta);
+        // Before being narrowed down to the selection, for `--bump-dependents`
+        let releasable = pkgs
+            .values()
+            .filter(|pkg| pkg.config.release())
+            .filter(|pkg| !self.workspace.exclude.contains(&pkg.meta.name))
+            .map(|pkg| (pkg.meta.id.clone(), pkg.config.release))
+            .collect::<std::collections::BTreeMap<_, _>>();
         for excluded_pkg in &excluded_pkgs {
             let pkg = if let Some(pkg) = pkgs.get_mut(&excluded_pkg.id) {
                 pkg
@@ -102,7 +471,7 @@ impl ReleaseStep {
             {
                 let version = &pkg.initial_version;
                 if !cargo::is_published(
-                    &mut index,
+                    index,
                     pkg.config.registry(),
                     crate_name,
                     &version.full_version_string,
@@ -117,37 +486,44 @@ impl ReleaseStep {
             }
 
             pkg.planned_version = None;
-            pkg.config.release = Some(false);
-
-            if let Some(prior_tag_name) = &pkg.prior_tag {
-                if let Some(changed) =
-                    crate::steps::version::changed_since(&ws_meta, pkg, prior_tag_name)
-                {
-                    if !changed.is_empty() {
-                        let _ = crate::ops::shell::warn(format!(
-                            "disabled by user, skipping {} which has files changed since {}: {:#?}",
-                            crate_name, prior_tag_name, changed
-                        ));
-                    } else {
-                        log::trace!(
-                            "disabled by user, skipping {} (no changes since {})",
-                            crate_name,
-                            prior_tag_name
-                        );
-                    }
+            pkg.config.release = Some(false.into());
+
+            let since = pkg
+                .prior_tag
+                .clone()
+                .unwrap_or_else(|| "its last release".to_owned());
+            if let Some(changed) = crate::steps::version::changed_since_release(ws_meta, index, pkg)
+            {
+                if !changed.is_empty() {
+                    let _ = crate::ops::shell::warn(format!(
+                        "disabled by user, skipping {} which has files changed since {}: {:#?}",
+                        crate_name, since, changed
+                    ));
                 } else {
-                    log::debug!(
-                        "disabled by user, skipping {} (no {} tag)",
+                    log::trace!(
+                        "disabled by user, skipping {} (no changes since {})",
                         crate_name,
-                        prior_tag_name
+                        since
                     );
                 }
+            } else if let Some(prior_tag_name) = &pkg.prior_tag {
+                log::debug!(
+                    "disabled by user, skipping {} (no {} tag)",
+                    crate_name,
+                    prior_tag_name
+                );
             } else {
                 log::debug!("disabled by user, skipping {} (no tag found)", crate_name,);
             }
         }
 
-        let pkgs = plan::plan(pkgs)?;
+        let mut pkgs = plan::plan(pkgs)?;
+        if let Some(level) = self.bump_dependents {
+            if super::bump_dependents(&mut pkgs, &releasable, level)? {
+                pkgs = plan::plan(pkgs)?;
+            }
+        }
+        plan::skip_unchanged(&mut pkgs)?;
 
         for excluded_pkg in &excluded_pkgs {
             let pkg = if let Some(pkg) = pkgs.get(&excluded_pkg.id) {
@@ -157,12 +533,11 @@ impl ReleaseStep {
                 continue;
             };
 
-            // HACK: `index` only supports default registry
-            if pkg.config.publish() && pkg.config.registry().is_none() {
+            if pkg.config.publish() && index.can_look_up(pkg.config.registry()) {
                 let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
                 let crate_name = pkg.meta.name.as_str();
                 if !cargo::is_published(
-                    &mut index,
+                    index,
                     pkg.config.registry(),
                     crate_name,
                     &version.full_version_string,
@@ -179,27 +554,92 @@ impl ReleaseStep {
             .into_iter()
             .map(|(_, pkg)| pkg)
             .partition(|p| p.config.release());
+        if pass == WorkspacePass::Check && (self.from_tag.is_some() || selected_pkgs.is_empty()) {
+            // Checked as they are released, nothing is published before their checks
+            return Ok(None);
+        }
+        if let Some(from_tag) = self.from_tag.as_deref() {
+            return self
+                .release_from_tag(ws_meta, &ws_config, selected_pkgs, index, from_tag, dry_run)
+                .map(Some);
+        }
         if selected_pkgs.is_empty() {
-            let _ = crate::ops::shell::error("no packages selected");
-            return Err(2.into());
+            if let Some(tag_name) = super::tag::planned_umbrella_tag(&ws_config) {
+                return self
+                    .release_umbrella(ws_meta, &ws_config, &tag_name, dry_run)
+                    .map(Some);
+            }
+            log::debug!("no packages selected in {}", ws_meta.workspace_root);
+            return Ok(None);
         }
 
-        let dry_run = !self.execute;
         let mut failed = false;
 
         let consolidate_commits = super::consolidate_commits(&selected_pkgs, &excluded_pkgs)?;
+        let split_commits = ws_config.commit_strategy() == config::CommitStrategy::Split;
 
         // STEP 0: Help the user make the right decisions.
-        failed |= !super::verify_git_is_clean(
-            ws_meta.workspace_root.as_std_path(),
-            dry_run,
-            log::Level::Error,
-        )?;
+        if self.amend {
+            failed |= !super::verify_amendable(
+                ws_meta,
+                &ws_config,
+                &selected_pkgs,
+                dry_run,
+                log::Level::Error,
+            )?;
+        } else {
+            failed |= !super::verify_git_is_clean_except_submodules(
+                ws_meta.workspace_root.as_std_path(),
+                dry_run,
+                log::Level::Error,
+            )?;
+            failed |= !super::verify_submodules(
+                ws_meta.workspace_root.as_std_path(),
+                &ws_config,
+                dry_run,
+                log::Level::Error,
+            )?;
 
-        failed |= !super::verify_tags_missing(&selected_pkgs, dry_run, log::Level::Error)?;
+            failed |= !super::verify_tags_missing(&selected_pkgs, dry_run, log::Level::Error)?;
+            failed |= !super::verify_umbrella_tag_missing(
+                &ws_config,
+                ws_meta,
+                dry_run,
+                log::Level::Error,
+            )?;
+        }
 
         failed |=
             !super::verify_monotonically_increasing(&selected_pkgs, dry_run, log::Level::Error)?;
+        failed |=
+            !super::verify_registry_version(&selected_pkgs, index, dry_run, self.backport_level())?;
+        if self.backport {
+            failed |= !super::verify_backport(
+                ws_meta.workspace_root.as_std_path(),
+                &selected_pkgs,
+                dry_run,
+                log::Level::Error,
+            )?;
+        }
+
+        failed |= !super::verify_rust_version(&selected_pkgs, dry_run, log::Level::Warn)?;
+        failed |= !super::verify_changelog_updated(&selected_pkgs, dry_run, log::Level::Error)?;
+        failed |= !super::verify_unreleased_notes(&selected_pkgs, dry_run, log::Level::Error)?;
+        failed |= !super::verify_changelog_fragments(&selected_pkgs, dry_run, log::Level::Error)?;
+        failed |= !super::verify_docs(ws_meta, &selected_pkgs, dry_run, log::Level::Error)?;
+        failed |= !super::verify_first_release(
+            &selected_pkgs,
+            self.allow_first_release,
+            dry_run,
+            log::Level::Error,
+        )?;
+        failed |= !super::verify_new_names(
+            &selected_pkgs,
+            index,
+            self.allow_new_name,
+            dry_run,
+            log::Level::Error,
+        )?;
 
         let mut double_publish = false;
         for pkg in &selected_pkgs {
@@ -209,7 +649,7 @@ impl ReleaseStep {
             let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
             let crate_name = pkg.meta.name.as_str();
             if cargo::is_published(
-                &mut index,
+                index,
                 pkg.config.registry(),
                 crate_name,
                 &version.full_version_string,
@@ -228,13 +668,14 @@ impl ReleaseStep {
             }
         }
 
-        super::warn_changed(&ws_meta, &selected_pkgs)?;
+        super::warn_changed(ws_meta, &selected_pkgs)?;
 
+        // Maintenance branches are checked by `verify_backport`
         failed |= !super::verify_git_branch(
             ws_meta.workspace_root.as_std_path(),
             &ws_config,
             dry_run,
-            log::Level::Error,
+            self.backport_level(),
         )?;
 
         failed |= !super::verify_if_behind(
@@ -244,80 +685,411 @@ impl ReleaseStep {
             log::Level::Warn,
         )?;
 
+        failed |= !super::verify_push_remote_allowed(
+            ws_meta.workspace_root.as_std_path(),
+            &ws_config,
+            dry_run,
+        )?;
+
+        failed |= !super::verify_branch_protection(
+            ws_meta.workspace_root.as_std_path(),
+            &ws_config,
+            dry_run,
+        )?;
+
+        failed |= !super::verify_signing(
+            ws_meta.workspace_root.as_std_path(),
+            &ws_config,
+            &selected_pkgs,
+            dry_run,
+        )?;
+
         failed |= !super::verify_metadata(&selected_pkgs, dry_run, log::Level::Error)?;
-        failed |=
-            !super::verify_rate_limit(&selected_pkgs, &mut index, dry_run, log::Level::Error)?;
+        failed |= !super::verify_dependency_reqs(
+            &selected_pkgs,
+            &excluded_pkgs,
+            index,
+            dry_run,
+            log::Level::Error,
+        )?;
+        failed |= !super::verify_unreleased_dependencies(
+            ws_meta,
+            &ws_config,
+            &selected_pkgs,
+            &excluded_pkgs,
+            dry_run,
+        )?;
+        failed |= !super::verify_lock_committed(
+            ws_meta,
+            &ws_config,
+            &selected_pkgs,
+            dry_run,
+            log::Level::Error,
+        )?;
+        failed |= !super::verify_lock_current(
+            ws_meta,
+            &ws_config,
+            &selected_pkgs,
+            dry_run,
+            log::Level::Error,
+        )?;
+        // Too slow, or outward facing, to repeat
+        let prechecked = pass == WorkspacePass::Release { prechecked: true };
+        if !prechecked {
+            failed |= !super::publish::verify_commands(&selected_pkgs, dry_run)?;
+        }
+        failed |= !super::verify_registry_health(&ws_config, &selected_pkgs, dry_run)?;
+        super::report_rate_limit(&selected_pkgs, index)?;
+        if !prechecked && !self.rehearsal {
+            failed |= !super::approval::approve_plan(
+                ws_meta,
+                &ws_config,
+                &selected_pkgs,
+                &excluded_pkgs,
+                dry_run,
+            )?;
+        }
+        if pass == WorkspacePass::Check {
+            return Ok(Some(failed));
+        }
 
         // STEP 1: Release Confirmation
-        super::confirm("Release", &selected_pkgs, self.no_confirm, dry_run)?;
+        super::confirm(
+            "Release",
+            &selected_pkgs,
+            !self.needs_confirm(&ws_config, config::ConfirmAction::Release),
+            dry_run,
+        )?;
+        if self.backport {
+            super::confirm(
+                "Backport",
+                &selected_pkgs,
+                !self.needs_confirm(&ws_config, config::ConfirmAction::Release),
+                dry_run,
+            )?;
+        }
 
         // STEP 2: update current version, save and commit
-        if consolidate_commits {
-            let update_lock =
-                super::version::update_versions(&ws_meta, &selected_pkgs, &excluded_pkgs, dry_run)?;
-            if update_lock {
-                log::debug!("updating lock file");
-                if !dry_run {
-                    let workspace_path = ws_meta.workspace_root.as_std_path().join("Cargo.toml");
-                    cargo::update_lock(&workspace_path)?;
+        self.inject_failure(FailPoint::Commit)?;
+        if self.amend {
+            // Versions, replacements, and hooks were applied by the run being amended
+            let root = ws_meta.workspace_root.as_std_path();
+            let _ = crate::ops::shell::status("Amending", "release commit");
+            if !git::amend_all(
+                root,
+                ws_config.sign_commit(),
+                ws_config.commit_verify(),
+                dry_run,
+            )? {
+                return Err(101.into());
+            }
+            let mut stale_tags = Vec::new();
+            for tag_name in super::amended_tags(&ws_config, &selected_pkgs) {
+                if git::tag_exists(root, &tag_name)? {
+                    log::debug!("deleting git tag {}", tag_name);
+                    stale_tags.push(tag_name);
                 }
             }
-
-            for pkg in &selected_pkgs {
-                super::replace::replace(pkg, dry_run)?;
-
-                // pre-release hook
-                super::hook::hook(&ws_meta, pkg, dry_run)?;
+            let stale_tags = stale_tags.iter().map(|t| t.as_str()).collect::<Vec<_>>();
+            if !git::delete_tags(root, &stale_tags, dry_run)? {
+                return Err(101.into());
             }
-
-            super::commit::workspace_commit(&ws_meta, &ws_config, &selected_pkgs, dry_run)?;
         } else {
-            for pkg in &selected_pkgs {
-                if let Some(version) = pkg.planned_version.as_ref() {
-                    let crate_name = pkg.meta.name.as_str();
-                    let _ = crate::ops::shell::status(
-                        "Upgrading",
-                        format!(
-                            "{} from {} to {}",
-                            crate_name,
-                            pkg.initial_version.full_version_string,
-                            version.full_version_string
-                        ),
-                    );
-                    cargo::set_package_version(
-                        &pkg.manifest_path,
-                        version.full_version_string.as_str(),
+            super::commit::record_releases(ws_meta, &ws_config, &selected_pkgs, dry_run)?;
+            if consolidate_commits {
+                let update_lock = super::version::update_versions(
+                    ws_meta,
+                    &selected_pkgs,
+                    &excluded_pkgs,
+                    dry_run,
+                )?;
+                if update_lock {
+                    super::update_lock(ws_meta, &ws_config, &selected_pkgs, dry_run)?;
+                }
+                if split_commits && has_changes(ws_meta.workspace_root.as_std_path(), dry_run)? {
+                    super::commit::workspace_version_commit(
+                        ws_meta,
+                        &ws_config,
+                        &selected_pkgs,
                         dry_run,
                     )?;
-                    crate::steps::version::update_dependent_versions(
-                        &ws_meta, pkg, version, dry_run,
-                    )?;
-                    if dry_run {
-                        log::debug!("updating lock file");
-                    } else {
-                        cargo::update_lock(&pkg.manifest_path)?;
-                    }
                 }
 
-                super::replace::replace(pkg, dry_run)?;
+                for pkg in &selected_pkgs {
+                    super::update_rust_version(ws_meta, pkg, dry_run)?;
+                    super::replace::replace(pkg, dry_run)?;
 
-                // pre-release hook
-                super::hook::hook(&ws_meta, pkg, dry_run)?;
+                    // pre-release hook
+                    super::hook::hook(ws_meta, pkg, dry_run)?;
+                }
 
-                super::commit::pkg_commit(pkg, dry_run)?;
+                if !split_commits || has_changes(ws_meta.workspace_root.as_std_path(), dry_run)? {
+                    super::commit::workspace_commit(ws_meta, &ws_config, &selected_pkgs, dry_run)?;
+                }
+            } else {
+                for pkg in &selected_pkgs {
+                    if let Some(version) = pkg.planned_version.as_ref() {
+                        let crate_name = pkg.meta.name.as_str();
+                        let _ = crate::ops::shell::status(
+                            "Upgrading",
+                            format!(
+                                "{} from {} to {}",
+                                crate_name,
+                                pkg.initial_version.full_version_string,
+                                version.full_version_string
+                            ),
+                        );
+                        let mut edits = cargo::ManifestEdits::new();
+                        edits.set_package_version(
+                            &pkg.manifest_path,
+                            version.full_version_string.as_str(),
+                        )?;
+                        crate::steps::version::update_dependent_versions(
+                            &mut edits, ws_meta, pkg, version,
+                        )?;
+                        edits.write(dry_run)?;
+                        super::update_lock(
+                            ws_meta,
+                            &ws_config,
+                            std::slice::from_ref(pkg),
+                            dry_run,
+                        )?;
+                        if split_commits {
+                            super::commit::pkg_version_commit(pkg, dry_run)?;
+                        }
+                    }
+
+                    super::update_rust_version(ws_meta, pkg, dry_run)?;
+                    super::replace::replace(pkg, dry_run)?;
+
+                    // pre-release hook
+                    super::hook::hook(ws_meta, pkg, dry_run)?;
+
+                    if !split_commits || has_changes(&pkg.package_root, dry_run)? {
+                        super::commit::pkg_commit(pkg, dry_run)?;
+                    }
+                }
             }
         }
 
         // STEP 3: cargo publish
-        super::publish::publish(&ws_meta, &selected_pkgs, &mut index, dry_run)?;
+        if selected_pkgs.iter().any(|pkg| pkg.config.publish()) {
+            super::confirm(
+                "Publish",
+                &selected_pkgs,
+                !self.needs_confirm(&ws_config, config::ConfirmAction::Publish),
+                dry_run,
+            )?;
+            if !self.rehearsal {
+                failed |= !super::approval::approve(ws_meta, &ws_config, &selected_pkgs, dry_run)?;
+            }
+        }
+        self.inject_failure(FailPoint::Publish)?;
+        super::publish::publish(ws_meta, &selected_pkgs, index, dry_run)?;
         super::owner::ensure_owners(&selected_pkgs, dry_run)?;
 
         // STEP 5: Tag
-        super::tag::tag(&selected_pkgs, dry_run)?;
+        self.inject_failure(FailPoint::Tag)?;
+        super::tag::tag(ws_meta, &selected_pkgs, dry_run)?;
+        super::tag::umbrella_tag(&ws_config, ws_meta, &selected_pkgs, dry_run)?;
 
         // STEP 6: git push
-        super::push::push(&ws_config, &ws_meta, &selected_pkgs, dry_run)?;
+        if ws_config.push() {
+            super::confirm(
+                "Push",
+                &selected_pkgs,
+                !self.needs_confirm(&ws_config, config::ConfirmAction::Push),
+                dry_run,
+            )?;
+        }
+        self.inject_failure(FailPoint::Push)?;
+        let pushed = super::push::push(&ws_config, ws_meta, &selected_pkgs, dry_run)?;
+        for pkg in &selected_pkgs {
+            super::promote::delete_prerelease_tags(ws_meta, pkg, dry_run)?;
+        }
+        super::publish::release_manifest(&ws_config, ws_meta, &selected_pkgs, dry_run)?;
 
-        super::finish(failed, dry_run)
+        // STEP 7: Wait on the release being available
+        super::publish::await_publish(&selected_pkgs, dry_run)?;
+        // Announcements link to the pushed tags and commits
+        if pushed && !self.rehearsal {
+            super::notify::announce(ws_meta, &ws_config, &selected_pkgs, dry_run)?;
+        }
+
+        Ok(Some(failed))
+    }
+
+    /// Tag the workspace when no packages are being released
+    fn release_umbrella(
+        &self,
+        ws_meta: &cargo_metadata::Metadata,
+        ws_config: &config::Config,
+        tag_name: &str,
+        dry_run: bool,
+    ) -> Result<bool, CliError> {
+        let mut failed = false;
+
+        // STEP 0: Help the user make the right decisions.
+        failed |= !super::verify_git_is_clean(
+            ws_meta.workspace_root.as_std_path(),
+            dry_run,
+            log::Level::Error,
+        )?;
+
+        failed |=
+            !super::verify_umbrella_tag_missing(ws_config, ws_meta, dry_run, log::Level::Error)?;
+
+        failed |= !super::verify_git_branch(
+            ws_meta.workspace_root.as_std_path(),
+            ws_config,
+            dry_run,
+            log::Level::Error,
+        )?;
+
+        failed |= !super::verify_if_behind(
+            ws_meta.workspace_root.as_std_path(),
+            ws_config,
+            dry_run,
+            log::Level::Warn,
+        )?;
+
+        failed |= !super::verify_push_remote_allowed(
+            ws_meta.workspace_root.as_std_path(),
+            ws_config,
+            dry_run,
+        )?;
+        // No release commit, so branch protection doesn't stand in the way
+
+        // STEP 1: Release Confirmation
+        if !dry_run && self.needs_confirm(ws_config, config::ConfirmAction::Release) {
+            let confirmed = crate::ops::shell::confirm(&format!("Tag {}?", tag_name));
+            if !confirmed {
+                return Err(0.into());
+            }
+        }
+
+        // STEP 5: Tag
+        self.inject_failure(FailPoint::Tag)?;
+        super::tag::umbrella_tag(ws_config, ws_meta, &[], dry_run)?;
+
+        // STEP 6: git push
+        if !dry_run
+            && ws_config.push()
+            && self.needs_confirm(ws_config, config::ConfirmAction::Push)
+        {
+            let confirmed = crate::ops::shell::confirm(&format!("Push {}?", tag_name));
+            if !confirmed {
+                return Err(0.into());
+            }
+        }
+        self.inject_failure(FailPoint::Push)?;
+        let pushed = super::push::push(ws_config, ws_meta, &[], dry_run)?;
+        if pushed && !self.rehearsal {
+            super::notify::announce(ws_meta, ws_config, &[], dry_run)?;
+        }
+
+        Ok(failed)
     }
+
+    /// Publish the packages released as `tag_name`, e.g. after a failed publish
+    fn release_from_tag(
+        &self,
+        ws_meta: &cargo_metadata::Metadata,
+        ws_config: &config::Config,
+        selected_pkgs: Vec<plan::PackageRelease>,
+        index: &mut crate::ops::index::CratesIoIndex,
+        tag_name: &str,
+        dry_run: bool,
+    ) -> Result<bool, CliError> {
+        let root = ws_meta.workspace_root.as_std_path();
+        if !git::tag_exists(root, tag_name)? {
+            let _ = crate::ops::shell::error(format!("tag `{}` doesn't exist", tag_name));
+            return Err(101.into());
+        }
+
+        let mut selected_pkgs: Vec<_> = selected_pkgs
+            .into_iter()
+            .filter(|pkg| pkg.planned_tag.as_deref() == Some(tag_name))
+            .collect();
+        if selected_pkgs.is_empty() {
+            let _ = crate::ops::shell::error(format!(
+                "no selected packages are released as `{}`",
+                tag_name
+            ));
+            return Err(2.into());
+        }
+
+        let mut failed = false;
+
+        // STEP 0: Help the user make the right decisions.
+        failed |= !super::verify_git_is_clean(root, dry_run, log::Level::Error)?;
+
+        for pkg in &mut selected_pkgs {
+            if !pkg.config.publish() {
+                continue;
+            }
+            let crate_name = pkg.meta.name.as_str();
+            let version = &pkg.initial_version;
+            if cargo::is_published(
+                index,
+                pkg.config.registry(),
+                crate_name,
+                &version.full_version_string,
+            ) {
+                let _ = crate::ops::shell::note(format!(
+                    "{} {} is already published, skipping",
+                    crate_name, version.full_version_string
+                ));
+                pkg.config.publish = Some(false);
+            }
+        }
+
+        failed |= !super::verify_metadata(&selected_pkgs, dry_run, log::Level::Error)?;
+        super::report_rate_limit(&selected_pkgs, index)?;
+
+        // STEP 1: Release Confirmation
+        super::confirm(
+            "Publish",
+            &selected_pkgs,
+            !self.needs_confirm(ws_config, config::ConfirmAction::Release),
+            dry_run,
+        )?;
+
+        // STEP 3: cargo publish
+        if !self.rehearsal {
+            failed |= !super::approval::approve(ws_meta, ws_config, &selected_pkgs, dry_run)?;
+        }
+        self.inject_failure(FailPoint::Publish)?;
+        super::publish::publish(ws_meta, &selected_pkgs, index, dry_run)?;
+        super::owner::ensure_owners(&selected_pkgs, dry_run)?;
+
+        // STEP 7: Wait on the release being available
+        super::publish::await_publish(&selected_pkgs, dry_run)?;
+
+        // The tag was pushed by the release being resumed
+        if ws_config.push() && !self.rehearsal {
+            super::notify::announce(ws_meta, ws_config, &selected_pkgs, dry_run)?;
+        }
+
+        Ok(failed)
+    }
+
+    fn needs_confirm(&self, ws_config: &config::Config, action: config::ConfirmAction) -> bool {
+        !self.no_confirm && !self.yes.contains(&action) && ws_config.confirm().contains(&action)
+    }
+
+    /// Backports are expected to be older than the latest release and off the release branches
+    fn backport_level(&self) -> log::Level {
+        if self.backport {
+            log::Level::Warn
+        } else {
+            log::Level::Error
+        }
+    }
+}
+
+/// Whether anything is left to commit after the version bump was committed on its own
+fn has_changes(dir: &std::path::Path, dry_run: bool) -> Result<bool, CliError> {
+    // Nothing is written in a dry-run
+    Ok(dry_run || git::is_dirty(dir)?.is_some())
 }
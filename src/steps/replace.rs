@@ -1,5 +1,4 @@
 use crate::error::CliError;
-use crate::ops::git;
 use crate::ops::replace::{do_file_replacements, Template, NOW};
 use crate::steps::plan;
 
@@ -42,7 +41,6 @@ pub struct ReplaceStep {
 
 impl ReplaceStep {
     pub fn run(&self) -> Result<(), CliError> {
-        git::git_version()?;
         let mut index = crate::ops::index::CratesIoIndex::new();
 
         if self.dry_run {
@@ -50,15 +48,11 @@ impl ReplaceStep {
                 crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
         }
 
-        let ws_meta = self
-            .manifest
-            .metadata()
-            // When evaluating dependency ordering, we need to consider optional dependencies
-            .features(cargo_metadata::CargoOpt::AllFeatures)
-            .exec()?;
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
         let config = self.to_config();
         let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
         let mut pkgs = plan::load(&config, &ws_meta)?;
+        super::configure_index(&mut index, &ws_config, pkgs.values());
 
         let (_selected_pkgs, excluded_pkgs) = self.workspace.partition_packages(&ws_meta);
         for excluded_pkg in excluded_pkgs {
@@ -98,7 +92,7 @@ impl ReplaceStep {
             }
 
             pkg.config.pre_release_replacements = Some(vec![]);
-            pkg.config.release = Some(false);
+            pkg.config.release = Some(false.into());
         }
 
         let pkgs = plan::plan(pkgs)?;
@@ -168,15 +162,27 @@ pub fn replace(pkg: &plan::PackageRelease, dry_run: bool) -> Result<(), CliError
         let prev_metadata_var = pkg.initial_version.full_version.build.as_str();
         let version_var = version.bare_version_string.as_str();
         let metadata_var = version.full_version.build.as_str();
+        let contributors = pkg
+            .config
+            .pre_release_replacements()
+            .iter()
+            .find_map(|r| super::contributors(pkg, &r.replace).transpose())
+            .transpose()?;
         // try replacing text in configured files
+        let next_version = pkg.next_version();
         let template = Template {
             prev_version: Some(prev_version_var),
             prev_metadata: Some(prev_metadata_var),
             version: Some(version_var),
             metadata: Some(metadata_var),
             crate_name: Some(crate_name),
+            package_path: Some(pkg.package_path.as_str()),
+            manifest_dir: pkg.package_root.to_str(),
+            prev_tag_name: pkg.prior_tag.as_deref(),
+            next_version: Some(next_version.as_str()),
             date: Some(NOW.as_str()),
             tag_name: pkg.planned_tag.as_deref(),
+            contributors: contributors.as_deref(),
             ..Default::default()
         };
         let prerelease = version.is_prerelease();
@@ -191,5 +197,126 @@ pub fn replace(pkg: &plan::PackageRelease, dry_run: bool) -> Result<(), CliError
         )?;
     }
 
+    if let (Some(fragments), false) = (pkg.config.changelog_fragments(), version.is_prerelease()) {
+        assemble_fragments(pkg, fragments, dry_run)?;
+    }
+
+    if pkg.config.keep_a_changelog() && !version.is_prerelease() {
+        let path =
+            crate::ops::replace::long_path(&pkg.package_root.join(pkg.config.changelog_path()));
+        let changelog = if pkg.first_release {
+            std::fs::read_to_string(&path)
+                .ok()
+                .filter(|c| crate::ops::changelog::unreleased_notes(c).is_some())
+        } else {
+            Some(std::fs::read_to_string(&path)?)
+        };
+        let changelog = if let Some(changelog) = changelog {
+            changelog
+        } else {
+            log::debug!(
+                "no changelog to rotate for first release of {}",
+                pkg.meta.name
+            );
+            return Ok(());
+        };
+        let line_ending = crate::ops::replace::LineEnding::detect(&changelog);
+        let changelog = line_ending.normalize(&changelog);
+        let rotated = crate::ops::changelog::rotate(
+            &changelog,
+            version.bare_version_string.as_str(),
+            NOW.as_str(),
+            pkg.planned_tag.as_deref(),
+        )
+        .map_err(|e| anyhow::format_err!("{}: {}", path.display(), e))?;
+        let rotated = if let (true, Some(tag_name)) =
+            (pkg.config.changelog_links(), pkg.planned_tag.as_deref())
+        {
+            let remote = pkg.config.push_remote();
+            let repository_url = crate::ops::git::remote_url(&pkg.package_root, remote)?
+                .and_then(|url| crate::ops::forge::repository_url(&url));
+            if let Some(repository_url) = repository_url {
+                crate::ops::changelog::add_missing_links(
+                    &rotated,
+                    &repository_url,
+                    version.bare_version_string.as_str(),
+                    pkg.prior_tag.as_deref(),
+                    tag_name,
+                )
+            } else {
+                let _ = crate::ops::shell::warn(format!(
+                    "not adding changelog links for {}, `{}` isn't a recognized remote",
+                    pkg.meta.name, remote
+                ));
+                rotated
+            }
+        } else {
+            rotated
+        };
+        if dry_run {
+            let _ = crate::ops::shell::status(
+                "Rotating",
+                format!(
+                    "changelog in {}\n{}",
+                    path.display(),
+                    crate::ops::diff::unified_diff(&changelog, &rotated, &path, "rotated")
+                ),
+            );
+        } else {
+            std::fs::write(&path, line_ending.restore(&rotated))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Move the changelog fragments into the `Unreleased` section of the changelog
+fn assemble_fragments(
+    pkg: &plan::PackageRelease,
+    fragments: &str,
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let dir = pkg.package_root.join(fragments);
+    let fragments = crate::ops::fragment::load(&dir)?;
+    if fragments.is_empty() {
+        return Ok(());
+    }
+
+    let path = crate::ops::replace::long_path(&pkg.package_root.join(pkg.config.changelog_path()));
+    let changelog = std::fs::read_to_string(&path)?;
+    let line_ending = crate::ops::replace::LineEnding::detect(&changelog);
+    let changelog = line_ending.normalize(&changelog);
+    let mut notes = crate::ops::fragment::render(&fragments);
+    if crate::ops::changelog::unreleased_notes(&changelog).map_or(false, |n| !n.is_empty()) {
+        notes.insert(0, '\n');
+    }
+    let assembled = crate::ops::changelog::add_unreleased_note(&changelog, &notes)
+        .map_err(|e| anyhow::format_err!("{}: {}", path.display(), e))?;
+
+    if dry_run {
+        let _ = crate::ops::shell::status(
+            "Assembling",
+            format!(
+                "{} changelog fragments into {}\n{}",
+                fragments.len(),
+                path.display(),
+                crate::ops::diff::unified_diff(&changelog, &assembled, &path, "assembled")
+            ),
+        );
+    } else {
+        let _ = crate::ops::shell::status(
+            "Assembling",
+            format!(
+                "{} changelog fragments into {}",
+                fragments.len(),
+                path.display()
+            ),
+        );
+        std::fs::write(&path, line_ending.restore(&assembled))?;
+        for fragment in &fragments {
+            std::fs::remove_file(&fragment.path)?;
+        }
+    }
+
     Ok(())
 }
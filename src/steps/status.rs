@@ -0,0 +1,217 @@
+use std::io::Write;
+
+use crate::error::CliError;
+use crate::ops::git;
+use crate::steps::plan;
+
+/// Summarize what needs releasing
+#[derive(Debug, Clone, clap::Args)]
+pub struct StatusStep {
+    #[command(flatten)]
+    manifest: clap_cargo::Manifest,
+
+    #[command(flatten)]
+    workspace: clap_cargo::Workspace,
+
+    /// Custom config file
+    #[arg(short, long = "config", value_name = "PATH")]
+    custom_config: Option<std::path::PathBuf>,
+
+    /// Ignore implicit configuration files.
+    #[arg(long)]
+    isolated: bool,
+
+    /// The name of tag for the previous release.
+    #[arg(long, value_name = "NAME")]
+    prev_tag_name: Option<String>,
+}
+
+impl StatusStep {
+    pub fn run(&self) -> Result<(), CliError> {
+        let mut index = crate::ops::index::CratesIoIndex::new();
+
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
+        let config = self.to_config();
+        let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
+        let mut pkgs = plan::load(&config, &ws_meta)?;
+        super::configure_index(&mut index, &ws_config, pkgs.values());
+
+        for pkg in pkgs.values_mut() {
+            if let Some(prev_tag) = self.prev_tag_name.as_ref() {
+                // Trust the user that the tag passed in is the latest tag for the workspace and that
+                // they don't care about any changes from before this tag.
+                pkg.set_prior_tag(prev_tag.to_owned());
+            }
+        }
+
+        let (_selected_pkgs, excluded_pkgs) = self.workspace.partition_packages(&ws_meta);
+        for excluded_pkg in excluded_pkgs {
+            let pkg = if let Some(pkg) = pkgs.get_mut(&excluded_pkg.id) {
+                pkg
+            } else {
+                // Either not in workspace or marked as `release = false`.
+                continue;
+            };
+            pkg.config.release = Some(false.into());
+        }
+
+        let pkgs = plan::plan(pkgs)?;
+
+        let (selected_pkgs, _excluded_pkgs): (Vec<_>, Vec<_>) = pkgs
+            .into_iter()
+            .map(|(_, pkg)| pkg)
+            .partition(|p| p.config.release());
+        if selected_pkgs.is_empty() {
+            let _ = crate::ops::shell::error("no packages selected");
+            return Err(2.into());
+        }
+
+        let statuses = selected_pkgs
+            .iter()
+            .map(|pkg| PackageStatus::load(pkg, &mut index))
+            .collect::<Result<Vec<_>, CliError>>()?;
+        let output = render(&statuses);
+        std::io::stdout().write_all(output.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn to_config(&self) -> crate::config::ConfigArgs {
+        crate::config::ConfigArgs {
+            custom_config: self.custom_config.clone(),
+            isolated: self.isolated,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PackageStatus {
+    pub name: String,
+    pub version: String,
+    pub prior_tag: Option<String>,
+    pub commits_since_tag: Option<usize>,
+    /// `None` when the registry can't be queried
+    pub published: Option<bool>,
+    /// `None` when the registry can't be queried or nothing has been published
+    pub latest_published: Option<semver::Version>,
+}
+
+impl PackageStatus {
+    pub fn load(
+        pkg: &plan::PackageRelease,
+        index: &mut crate::ops::index::CratesIoIndex,
+    ) -> Result<Self, CliError> {
+        let name = pkg.meta.name.clone();
+        let version = pkg.initial_version.full_version_string.clone();
+        let prior_tag = pkg.prior_tag.clone();
+        let commits_since_tag = if let Some(prior_tag) = prior_tag.as_deref() {
+            git::commits_since(&pkg.package_root, prior_tag)?
+        } else {
+            None
+        };
+
+        let (published, latest_published) = if pkg.config.publish() {
+            let registry = pkg.config.registry();
+            match index.has_krate_version(registry, &name, &version) {
+                Ok(published) => {
+                    let latest_published = index.latest_krate_version(registry, &name)?;
+                    // Only the default registry reports unknown crates
+                    let published = published.or(registry.is_none().then_some(false));
+                    (published, latest_published)
+                }
+                Err(err) => {
+                    log::warn!("failed to read metadata for {name}: {err:#}");
+                    (None, None)
+                }
+            }
+        } else {
+            (None, None)
+        };
+
+        Ok(Self {
+            name,
+            version,
+            prior_tag,
+            commits_since_tag,
+            published,
+            latest_published,
+        })
+    }
+
+    /// Whether the local version differs from the latest version on the registry
+    pub fn is_out_of_date(&self) -> Option<bool> {
+        let latest_published = self.latest_published.as_ref()?;
+        Some(latest_published.to_string() != self.version)
+    }
+}
+
+fn render(statuses: &[PackageStatus]) -> String {
+    let header = [
+        "PACKAGE",
+        "VERSION",
+        "LAST TAG",
+        "COMMITS",
+        "PUBLISHED",
+        "REGISTRY",
+    ];
+    let rows = statuses
+        .iter()
+        .map(|s| {
+            [
+                s.name.clone(),
+                s.version.clone(),
+                s.prior_tag.clone().unwrap_or_else(|| "-".to_owned()),
+                s.commits_since_tag
+                    .map(|c| c.to_string())
+                    .unwrap_or_else(|| "-".to_owned()),
+                match s.published {
+                    Some(true) => "yes".to_owned(),
+                    Some(false) => "no".to_owned(),
+                    None => "-".to_owned(),
+                },
+                match (s.latest_published.as_ref(), s.is_out_of_date()) {
+                    (Some(latest), Some(true)) => format!("{latest} (differs)"),
+                    (Some(latest), _) => latest.to_string(),
+                    (None, _) => "-".to_owned(),
+                },
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    super::render_table(&header, &rows)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_aligns_columns() {
+        let statuses = vec![
+            PackageStatus {
+                name: "foo".to_owned(),
+                version: "0.2.0".to_owned(),
+                prior_tag: Some("foo-v0.1.0".to_owned()),
+                commits_since_tag: Some(3),
+                published: Some(false),
+                latest_published: Some(semver::Version::parse("0.1.0").unwrap()),
+            },
+            PackageStatus {
+                name: "foo-internal".to_owned(),
+                version: "0.1.0".to_owned(),
+                prior_tag: None,
+                commits_since_tag: None,
+                published: None,
+                latest_published: None,
+            },
+        ];
+        let actual = render(&statuses);
+        let expected = "\
+PACKAGE       VERSION  LAST TAG    COMMITS  PUBLISHED  REGISTRY
+foo           0.2.0    foo-v0.1.0  3        no         0.1.0 (differs)
+foo-internal  0.1.0    -           -        -          -
+";
+        assert_eq!(actual, expected);
+    }
+}
@@ -46,19 +46,12 @@ pub struct TagStep {
 
 impl TagStep {
     pub fn run(&self) -> Result<(), CliError> {
-        git::git_version()?;
-
         if self.dry_run {
             let _ =
                 crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
         }
 
-        let ws_meta = self
-            .manifest
-            .metadata()
-            // When evaluating dependency ordering, we need to consider optional dependencies
-            .features(cargo_metadata::CargoOpt::AllFeatures)
-            .exec()?;
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
         let config = self.to_config();
         let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
         let mut pkgs = plan::load(&config, &ws_meta)?;
@@ -77,7 +70,7 @@ impl TagStep {
 
             pkg.planned_tag = None;
             pkg.config.tag = Some(false);
-            pkg.config.release = Some(false);
+            pkg.config.release = Some(false.into());
 
             let crate_name = pkg.meta.name.as_str();
             log::debug!("disabled by user, skipping {}", crate_name,);
@@ -95,7 +88,7 @@ impl TagStep {
                     ));
                     pkg.planned_tag = None;
                     pkg.config.tag = Some(false);
-                    pkg.config.release = Some(false);
+                    pkg.config.release = Some(false.into());
                 }
             }
         }
@@ -119,6 +112,13 @@ impl TagStep {
             log::Level::Error,
         )?;
 
+        failed |= !super::verify_signing(
+            ws_meta.workspace_root.as_std_path(),
+            &ws_config,
+            &selected_pkgs,
+            dry_run,
+        )?;
+
         failed |= !super::verify_git_branch(
             ws_meta.workspace_root.as_std_path(),
             &ws_config,
@@ -137,7 +137,8 @@ impl TagStep {
         super::confirm("Tag", &selected_pkgs, self.no_confirm, dry_run)?;
 
         // STEP 5: Tag
-        tag(&selected_pkgs, dry_run)?;
+        tag(&ws_meta, &selected_pkgs, dry_run)?;
+        umbrella_tag(&ws_config, &ws_meta, &selected_pkgs, dry_run)?;
 
         super::finish(failed, dry_run)
     }
@@ -153,8 +154,13 @@ impl TagStep {
     }
 }
 
-pub fn tag(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<(), CliError> {
+pub fn tag(
+    ws_meta: &cargo_metadata::Metadata,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<(), CliError> {
     let mut seen_tags = HashSet::new();
+    let mut tags = Vec::new();
     for pkg in pkgs {
         if let Some(tag_name) = pkg.planned_tag.as_ref() {
             if seen_tags.insert(tag_name) {
@@ -166,26 +172,163 @@ pub fn tag(pkgs: &[plan::PackageRelease], dry_run: bool) -> Result<(), CliError>
                 let prev_metadata_var = pkg.initial_version.full_version.build.as_str();
                 let version_var = version.bare_version_string.as_str();
                 let metadata_var = version.full_version.build.as_str();
+                let shortlog = if Template::uses(pkg.config.tag_message(), "shortlog") {
+                    Some(git::shortlog(cwd, pkg.prior_tag.as_deref())?)
+                } else {
+                    None
+                };
+                let contributors = super::contributors(pkg, pkg.config.tag_message())?;
+                // Forges show the tag message as the release body, so offer the release notes
+                let commits = if Template::uses(pkg.config.tag_message(), "commits") {
+                    super::notify::commits(ws_meta, pkg)
+                } else {
+                    None
+                };
+                let changelog = if Template::uses(pkg.config.tag_message(), "changelog") {
+                    Some(super::notify::changelog(ws_meta, std::slice::from_ref(pkg)))
+                } else {
+                    None
+                };
+                let next_version = pkg.next_version();
                 let template = Template {
                     prev_version: Some(prev_version_var),
                     prev_metadata: Some(prev_metadata_var),
                     version: Some(version_var),
                     metadata: Some(metadata_var),
                     crate_name: Some(crate_name),
+                    package_path: Some(pkg.package_path.as_str()),
+                    manifest_dir: pkg.package_root.to_str(),
+                    prev_tag_name: pkg.prior_tag.as_deref(),
+                    next_version: Some(next_version.as_str()),
                     tag_name: Some(tag_name),
                     date: Some(NOW.as_str()),
+                    shortlog: shortlog.as_deref(),
+                    contributors: contributors.as_deref(),
+                    commits: Some(commits.as_deref().unwrap_or_default()),
+                    changelog: changelog.as_deref(),
                     ..Default::default()
                 };
                 let tag_message = template.render(pkg.config.tag_message());
 
                 log::debug!("creating git tag {}", tag_name);
-                if !git::tag(cwd, tag_name, &tag_message, pkg.config.sign_tag(), dry_run)? {
-                    // tag failed, abort release
-                    return Err(101.into());
-                }
+                tags.push((tag_name.as_str(), tag_message, pkg.config.sign_tag(), cwd));
             }
         }
     }
 
+    // All packages share one repo, so create every tag in one go
+    if let Some((_, _, _, cwd)) = tags.first() {
+        let new_tags = tags
+            .iter()
+            .map(|(name, message, sign, _)| git::NewTag {
+                name,
+                message,
+                sign: *sign,
+            })
+            .collect::<Vec<_>>();
+        if !git::tag_all(cwd, &new_tags, dry_run)? {
+            // tag failed, abort release
+            return Err(101.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Name of the workspace-wide tag, if one is configured
+pub fn planned_umbrella_tag(ws_config: &crate::config::Config) -> Option<String> {
+    if !ws_config.tag() {
+        return None;
+    }
+    let tag_name = ws_config.umbrella_tag_name()?;
+    let template = Template {
+        date: Some(NOW.as_str()),
+        ..Default::default()
+    };
+    Some(template.render(tag_name))
+}
+
+/// The last workspace-wide tag before this release, for `{{prev_tag_name}}` and the notes since
+pub fn prior_umbrella_tag(
+    ws_config: &crate::config::Config,
+    ws_meta: &cargo_metadata::Metadata,
+) -> Option<String> {
+    let tag_name = ws_config.umbrella_tag_name()?;
+    let template = Template {
+        date: Some("*"),
+        ..Default::default()
+    };
+    let tag_glob = globset::Glob::new(&template.render(tag_name))
+        .ok()?
+        .compile_matcher();
+    git::find_last_tag(ws_meta.workspace_root.as_std_path(), &tag_glob)
+}
+
+/// Create the workspace-wide tag, describing the release of `pkgs`
+///
+/// Will automatically skip an existing tag
+pub fn umbrella_tag(
+    ws_config: &crate::config::Config,
+    ws_meta: &cargo_metadata::Metadata,
+    pkgs: &[plan::PackageRelease],
+    dry_run: bool,
+) -> Result<(), CliError> {
+    let tag_name = if let Some(tag_name) = planned_umbrella_tag(ws_config) {
+        tag_name
+    } else {
+        return Ok(());
+    };
+    let cwd = ws_meta.workspace_root.as_std_path();
+    if git::tag_exists(cwd, &tag_name)? {
+        let _ = crate::ops::shell::warn(format!(
+            "disabled due to existing tag ({}), skipping workspace tag",
+            tag_name
+        ));
+        return Ok(());
+    }
+
+    let message = ws_config.umbrella_tag_message();
+    let prev_tag_name = prior_umbrella_tag(ws_config, ws_meta);
+    let version = super::find_shared_versions(pkgs)?;
+    let releases = pkgs
+        .iter()
+        .map(|pkg| {
+            let version = pkg.planned_version.as_ref().unwrap_or(&pkg.initial_version);
+            format!("{} {}", pkg.meta.name, version.bare_version_string)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let changelog = super::notify::changelog(ws_meta, pkgs);
+    let shortlog = if Template::uses(message, "shortlog") {
+        Some(git::shortlog(cwd, prev_tag_name.as_deref())?)
+    } else {
+        None
+    };
+    let contributors =
+        super::workspace_contributors(ws_meta, ws_config, prev_tag_name.as_deref(), message)?;
+    let next_version = version
+        .as_ref()
+        .map(|v| v.next_version(ws_config.dev_version_ext()));
+    let template = Template {
+        version: version.as_ref().map(|v| v.bare_version_string.as_str()),
+        metadata: version.as_ref().map(|v| v.full_version.build.as_str()),
+        next_version: next_version.as_deref(),
+        tag_name: Some(&tag_name),
+        prev_tag_name: prev_tag_name.as_deref(),
+        date: Some(NOW.as_str()),
+        releases: Some(releases.as_str()),
+        changelog: Some(changelog.as_str()),
+        shortlog: shortlog.as_deref(),
+        contributors: contributors.as_deref(),
+        ..Default::default()
+    };
+    let tag_message = template.render(message);
+
+    log::debug!("creating git tag {}", tag_name);
+    if !git::tag(cwd, &tag_name, &tag_message, ws_config.sign_tag(), dry_run)? {
+        // tag failed, abort release
+        return Err(101.into());
+    }
+
     Ok(())
 }
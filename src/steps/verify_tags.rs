@@ -0,0 +1,210 @@
+use std::io::Write;
+
+use crate::error::CliError;
+use crate::ops::git;
+use crate::steps::plan;
+
+/// Audit tags against published versions
+#[derive(Debug, Clone, clap::Args)]
+pub struct VerifyTagsStep {
+    #[command(flatten)]
+    manifest: clap_cargo::Manifest,
+
+    #[command(flatten)]
+    workspace: clap_cargo::Workspace,
+
+    /// Custom config file
+    #[arg(short, long = "config", value_name = "PATH")]
+    custom_config: Option<std::path::PathBuf>,
+
+    /// Ignore implicit configuration files.
+    #[arg(long)]
+    isolated: bool,
+}
+
+impl VerifyTagsStep {
+    pub fn run(&self) -> Result<(), CliError> {
+        let mut index = crate::ops::index::CratesIoIndex::new();
+
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
+        let config = self.to_config();
+        let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
+        let mut pkgs = plan::load(&config, &ws_meta)?;
+        super::configure_index(&mut index, &ws_config, pkgs.values());
+
+        let (_selected_pkgs, excluded_pkgs) = self.workspace.partition_packages(&ws_meta);
+        for excluded_pkg in excluded_pkgs {
+            let pkg = if let Some(pkg) = pkgs.get_mut(&excluded_pkg.id) {
+                pkg
+            } else {
+                // Either not in workspace or marked as `release = false`.
+                continue;
+            };
+            pkg.config.release = Some(false.into());
+        }
+
+        let selected_pkgs = pkgs
+            .into_values()
+            .filter(|p| p.config.release())
+            .collect::<Vec<_>>();
+        if selected_pkgs.is_empty() {
+            let _ = crate::ops::shell::error("no packages selected");
+            return Err(2.into());
+        }
+
+        let mut findings = Vec::new();
+        for pkg in &selected_pkgs {
+            findings.extend(audit(pkg, &mut index)?);
+        }
+
+        if findings.is_empty() {
+            let _ = crate::ops::shell::status("Verified", "tags match published versions");
+            return Ok(());
+        }
+        let output = render(&findings);
+        std::io::stdout().write_all(output.as_bytes())?;
+
+        Err(101.into())
+    }
+
+    fn to_config(&self) -> crate::config::ConfigArgs {
+        crate::config::ConfigArgs {
+            custom_config: self.custom_config.clone(),
+            isolated: self.isolated,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Issue {
+    /// Published to the registry but never tagged
+    MissingTag,
+    /// Tagged but not published (or yanked)
+    Unpublished,
+    /// The tag is signed but the signature doesn't verify
+    BadSignature,
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingTag => "published without a tag".fmt(f),
+            Self::Unpublished => "tag without a published version".fmt(f),
+            Self::BadSignature => "invalid signature".fmt(f),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Finding {
+    pub name: String,
+    pub version: Option<String>,
+    pub tag: Option<String>,
+    pub issue: Issue,
+}
+
+fn audit(
+    pkg: &plan::PackageRelease,
+    index: &mut crate::ops::index::CratesIoIndex,
+) -> Result<Vec<Finding>, CliError> {
+    let name = pkg.meta.name.as_str();
+    let mut findings = Vec::new();
+    if !pkg.config.tag() {
+        log::debug!("skipping {name}, tagging is disabled");
+        return Ok(findings);
+    }
+
+    let glob = globset::Glob::new(&pkg.tag_glob())?.compile_matcher();
+    let tags = git::tags_matching(&pkg.package_root, "*")?
+        .into_iter()
+        .filter(|tag| glob.is_match(tag))
+        .collect::<std::collections::BTreeSet<_>>();
+
+    if pkg.config.publish() {
+        let registry = pkg.config.registry();
+        let mut published = index.krate_versions(registry, name)?.unwrap_or_default();
+        published.sort();
+        let mut expected = std::collections::BTreeSet::new();
+        for version in published {
+            let tag = pkg.tag_for(&plan::Version::from(version.clone()));
+            if !tags.contains(&tag) {
+                findings.push(Finding {
+                    name: name.to_owned(),
+                    version: Some(version.to_string()),
+                    tag: Some(tag.clone()),
+                    issue: Issue::MissingTag,
+                });
+            }
+            expected.insert(tag);
+        }
+        for tag in tags.difference(&expected) {
+            findings.push(Finding {
+                name: name.to_owned(),
+                version: None,
+                tag: Some(tag.clone()),
+                issue: Issue::Unpublished,
+            });
+        }
+    }
+
+    for tag in &tags {
+        if git::verify_tag(&pkg.package_root, tag)? == Some(false) {
+            findings.push(Finding {
+                name: name.to_owned(),
+                version: None,
+                tag: Some(tag.clone()),
+                issue: Issue::BadSignature,
+            });
+        }
+    }
+
+    Ok(findings)
+}
+
+fn render(findings: &[Finding]) -> String {
+    let header = ["PACKAGE", "VERSION", "TAG", "ISSUE"];
+    let rows = findings
+        .iter()
+        .map(|f| {
+            [
+                f.name.clone(),
+                f.version.clone().unwrap_or_else(|| "-".to_owned()),
+                f.tag.clone().unwrap_or_else(|| "-".to_owned()),
+                f.issue.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    super::render_table(&header, &rows)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_lists_issues() {
+        let findings = vec![
+            Finding {
+                name: "foo".to_owned(),
+                version: Some("0.1.0".to_owned()),
+                tag: Some("foo-v0.1.0".to_owned()),
+                issue: Issue::MissingTag,
+            },
+            Finding {
+                name: "foo".to_owned(),
+                version: None,
+                tag: Some("foo-v0.2.0".to_owned()),
+                issue: Issue::BadSignature,
+            },
+        ];
+        let actual = render(&findings);
+        let expected = "\
+PACKAGE  VERSION  TAG         ISSUE
+foo      0.1.0    foo-v0.1.0  published without a tag
+foo      -        foo-v0.2.0  invalid signature
+";
+        assert_eq!(actual, expected);
+    }
+}
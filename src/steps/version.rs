@@ -1,3 +1,5 @@
+use std::io::Write;
+
 use crate::error::CargoResult;
 use crate::error::CliError;
 use crate::ops::git;
@@ -46,23 +48,27 @@ pub struct VersionStep {
     /// The name of tag for the previous release.
     #[arg(long, value_name = "NAME", help_heading = "Version")]
     prev_tag_name: Option<String>,
+
+    /// Print the planned changes to stdout in this format
+    #[arg(long, value_enum)]
+    format: Option<PreviewFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum PreviewFormat {
+    Text,
+    Json,
 }
 
 impl VersionStep {
     pub fn run(&self) -> Result<(), CliError> {
-        git::git_version()?;
-
         if self.dry_run {
             let _ =
                 crate::ops::shell::warn("`--dry-run` is superfluous, dry-run is done by default");
         }
 
-        let ws_meta = self
-            .manifest
-            .metadata()
-            // When evaluating dependency ordering, we need to consider optional dependencies
-            .features(cargo_metadata::CargoOpt::AllFeatures)
-            .exec()?;
+        let ws_meta = crate::ops::metadata::workspace(self.manifest.manifest_path.as_deref())?;
         let config = self.to_config();
         let ws_config = crate::config::load_workspace_config(&config, &ws_meta)?;
         let mut pkgs = plan::load(&config, &ws_meta)?;
@@ -91,7 +97,7 @@ impl VersionStep {
             }
 
             pkg.planned_version = None;
-            pkg.config.release = Some(false);
+            pkg.config.release = Some(false.into());
         }
 
         let pkgs = plan::plan(pkgs)?;
@@ -134,17 +140,26 @@ impl VersionStep {
             log::Level::Warn,
         )?;
 
+        if let Some(format) = self.format {
+            let changes = planned_changes(&ws_meta, &selected_pkgs, &excluded_pkgs);
+            let output = match format {
+                PreviewFormat::Text => render(&changes),
+                PreviewFormat::Json => {
+                    let mut output = serde_json::to_string_pretty(&changes)?;
+                    output.push('\n');
+                    output
+                }
+            };
+            std::io::stdout().write_all(output.as_bytes())?;
+        }
+
         // STEP 1: Release Confirmation
         super::confirm("Bump", &selected_pkgs, self.no_confirm, dry_run)?;
 
         // STEP 2: update current version, save and commit
         let update_lock = update_versions(&ws_meta, &selected_pkgs, &excluded_pkgs, dry_run)?;
         if update_lock {
-            log::debug!("Updating lock file");
-            if !dry_run {
-                let workspace_path = ws_meta.workspace_root.as_std_path().join("Cargo.toml");
-                crate::ops::cargo::update_lock(&workspace_path)?;
-            }
+            super::update_lock(&ws_meta, &ws_config, &selected_pkgs, dry_run)?;
         }
 
         super::finish(failed, dry_run)
@@ -160,13 +175,128 @@ impl VersionStep {
     }
 }
 
+/// A manifest change `update_versions` will make
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct PlannedChange {
+    pub package: String,
+    /// The dependency whose requirement changes, `None` for a version change
+    pub dependency: Option<String>,
+    pub from: String,
+    pub to: String,
+}
+
+pub fn planned_changes(
+    ws_meta: &cargo_metadata::Metadata,
+    selected_pkgs: &[plan::PackageRelease],
+    excluded_pkgs: &[plan::PackageRelease],
+) -> Vec<PlannedChange> {
+    let workspace_version = selected_pkgs
+        .iter()
+        .filter(|p| p.config.shared_version() == Some(crate::config::SharedVersion::WORKSPACE))
+        .find_map(|p| p.planned_version.as_ref());
+
+    let mut changes = Vec::new();
+    let mut dependent_changes = Vec::new();
+    for pkg in selected_pkgs.iter().chain(excluded_pkgs.iter()) {
+        let is_inherited =
+            pkg.config.shared_version() == Some(crate::config::SharedVersion::WORKSPACE);
+        let version = if is_inherited {
+            workspace_version
+        } else {
+            pkg.planned_version.as_ref()
+        };
+        let version = if let Some(version) = version {
+            version
+        } else {
+            continue;
+        };
+        changes.push(PlannedChange {
+            package: pkg.meta.name.clone(),
+            dependency: None,
+            from: pkg.initial_version.full_version_string.clone(),
+            to: version.full_version_string.clone(),
+        });
+
+        let upgrade = pkg.config.dependent_version_for(&pkg.meta.name);
+        let prerelease = pkg.config.dependent_prerelease();
+        for dependent in find_ws_members(ws_meta) {
+            for dep in &dependent.dependencies {
+                let is_dependency =
+                    dep.path.as_ref().map(|p| p.as_std_path()) == Some(pkg.package_root.as_path());
+                if !is_dependency || dep.req == semver::VersionReq::STAR {
+                    continue;
+                }
+                let from = dep.req.to_string();
+                let to = if let Some(to) = crate::ops::cargo::upgraded_req(
+                    &from,
+                    &version.full_version,
+                    upgrade,
+                    prerelease,
+                ) {
+                    to
+                } else {
+                    continue;
+                };
+                let change = PlannedChange {
+                    package: dependent.name.clone(),
+                    dependency: Some(pkg.meta.name.clone()),
+                    from,
+                    to,
+                };
+                // Normal, dev, and build dependencies are reported once
+                if !dependent_changes.contains(&change) {
+                    dependent_changes.push(change);
+                }
+            }
+        }
+    }
+    changes.extend(dependent_changes);
+    changes
+}
+
+fn render(changes: &[PlannedChange]) -> String {
+    let header = ["PACKAGE", "CHANGE", "FROM", "TO"];
+    let rows = changes
+        .iter()
+        .map(|c| {
+            [
+                c.package.clone(),
+                c.dependency
+                    .as_ref()
+                    .map(|d| format!("dependency on {d}"))
+                    .unwrap_or_else(|| "version".to_owned()),
+                c.from.clone(),
+                c.to.clone(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    super::render_table(&header, &rows)
+}
+
 pub fn changed_since(
     ws_meta: &cargo_metadata::Metadata,
     pkg: &plan::PackageRelease,
     since_ref: &str,
 ) -> Option<Vec<std::path::PathBuf>> {
-    let changed_root = if pkg.bin {
-        ws_meta.workspace_root.as_std_path()
+    let workspace_root = ws_meta.workspace_root.as_std_path();
+    let mut watched = globset::GlobSetBuilder::new();
+    for pattern in pkg.config.watch_paths() {
+        match globset::Glob::new(pattern.trim_end_matches('/')) {
+            Ok(glob) => {
+                watched.add(glob);
+            }
+            Err(err) => {
+                let _ = crate::ops::shell::warn(format!(
+                    "ignoring `watch-paths` entry `{pattern}`: {err}"
+                ));
+            }
+        }
+    }
+    let watched = watched.build().ok()?;
+
+    let changed_root = if pkg.bin || !watched.is_empty() {
+        workspace_root
     } else {
         // Limit our lookup since we don't need to check for `Cargo.lock`
         &pkg.package_root
@@ -174,12 +304,95 @@ pub fn changed_since(
     let changed = git::changed_files(changed_root, since_ref).ok().flatten()?;
     let changed: Vec<_> = changed
         .into_iter()
-        .filter(|p| pkg.package_content.contains(p))
+        .filter(|p| {
+            pkg.package_content.contains(p)
+                || p.strip_prefix(workspace_root)
+                    .map(|p| p.ancestors().any(|p| watched.is_match(p)))
+                    .unwrap_or(false)
+        })
         .collect();
 
     Some(changed)
 }
 
+/// Files in the `.crate` that would be packaged that differ from the one published for the
+/// current version
+///
+/// Returns `None` when the current version isn't in the registry's index.
+pub fn content_changed(
+    ws_meta: &cargo_metadata::Metadata,
+    index: &mut crate::ops::index::CratesIoIndex,
+    pkg: &plan::PackageRelease,
+) -> Result<Option<Vec<std::path::PathBuf>>, CliError> {
+    let name = pkg.meta.name.as_str();
+    let version = &pkg.initial_version.full_version_string;
+    let published =
+        if let Some(published) = index.download_krate(pkg.config.registry(), name, version)? {
+            published
+        } else {
+            return Ok(None);
+        };
+
+    let target_dir = pkg
+        .config
+        .release_target_dir(ws_meta.workspace_root.as_std_path())
+        .unwrap_or_else(|| ws_meta.target_directory.clone().into_std_path_buf());
+    crate::ops::cargo::package(&pkg.manifest_path, &target_dir)?;
+    let packaged = target_dir
+        .join("package")
+        .join(format!("{name}-{version}.crate"));
+
+    let scratch = std::env::temp_dir().join(format!(
+        "cargo-release-{}-{name}-{version}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&scratch);
+    let result = (|| {
+        let published_crate = scratch.join("published.crate");
+        std::fs::create_dir_all(&scratch)?;
+        std::fs::write(&published_crate, published)?;
+        crate::ops::cargo::unpack_crate(&published_crate, &scratch.join("published"))?;
+        crate::ops::cargo::unpack_crate(&packaged, &scratch.join("packaged"))?;
+        let root = format!("{name}-{version}");
+        crate::ops::cargo::crate_differences(
+            &scratch.join("published").join(&root),
+            &scratch.join("packaged").join(&root),
+        )
+    })();
+    let _ = std::fs::remove_dir_all(&scratch);
+    Ok(Some(result?))
+}
+
+/// Files changed since the last release, according to `change-detection`
+///
+/// Returns `None` when there is nothing to compare against.
+pub fn changed_since_release(
+    ws_meta: &cargo_metadata::Metadata,
+    index: &mut crate::ops::index::CratesIoIndex,
+    pkg: &plan::PackageRelease,
+) -> Option<Vec<std::path::PathBuf>> {
+    if pkg.config.change_detection() == crate::config::ChangeDetection::Content {
+        match content_changed(ws_meta, index, pkg) {
+            Ok(Some(changed)) => return Some(changed),
+            Ok(None) => {
+                log::debug!(
+                    "cannot compare {} to v{} which is unpublished, falling back to git",
+                    pkg.meta.name,
+                    pkg.initial_version.full_version_string
+                );
+            }
+            Err(err) => {
+                log::debug!(
+                    "cannot compare {} to the registry, falling back to git: {}",
+                    pkg.meta.name,
+                    err
+                );
+            }
+        }
+    }
+    changed_since(ws_meta, pkg, pkg.prior_tag.as_deref()?)
+}
+
 pub fn update_versions(
     ws_meta: &cargo_metadata::Metadata,
     selected_pkgs: &[plan::PackageRelease],
@@ -187,6 +400,7 @@ pub fn update_versions(
     dry_run: bool,
 ) -> CargoResult<bool> {
     let mut changed = false;
+    let mut edits = crate::ops::cargo::ManifestEdits::new();
 
     let workspace_version = selected_pkgs
         .iter()
@@ -202,10 +416,9 @@ pub fn update_versions(
             ),
         );
         let workspace_path = ws_meta.workspace_root.as_std_path().join("Cargo.toml");
-        crate::ops::cargo::set_workspace_version(
+        edits.set_workspace_version(
             &workspace_path,
             workspace_version.full_version_string.as_str(),
-            dry_run,
         )?;
         // Deferring `update_dependent_versions` to the per-package logic
         changed = true;
@@ -250,25 +463,25 @@ pub fn update_versions(
                         version.full_version_string
                     ),
                 );
-                crate::ops::cargo::set_package_version(
+                edits.set_package_version(
                     &pkg.manifest_path,
                     version.full_version_string.as_str(),
-                    dry_run,
                 )?;
             }
-            update_dependent_versions(ws_meta, pkg, version, dry_run)?;
+            update_dependent_versions(&mut edits, ws_meta, pkg, version)?;
             changed = true;
         }
     }
+    edits.write(dry_run)?;
 
     Ok(changed)
 }
 
 pub fn update_dependent_versions(
+    edits: &mut crate::ops::cargo::ManifestEdits,
     ws_meta: &cargo_metadata::Metadata,
     pkg: &plan::PackageRelease,
     version: &plan::Version,
-    dry_run: bool,
 ) -> CargoResult<()> {
     // This is redundant with iterating over `workspace_members`
     // - As `find_dependency_tables` returns workspace dependencies
@@ -279,26 +492,26 @@ pub fn update_dependent_versions(
     // - Nicer message to the user
     {
         let workspace_path = ws_meta.workspace_root.as_std_path().join("Cargo.toml");
-        crate::ops::cargo::upgrade_dependency_req(
+        edits.upgrade_dependency_req(
             "workspace",
             &workspace_path,
             &pkg.package_root,
             &pkg.meta.name,
             &version.full_version,
-            pkg.config.dependent_version(),
-            dry_run,
+            pkg.config.dependent_version_for(&pkg.meta.name),
+            pkg.config.dependent_prerelease(),
         )?;
     }
 
     for dep in find_ws_members(ws_meta) {
-        crate::ops::cargo::upgrade_dependency_req(
+        edits.upgrade_dependency_req(
             &dep.name,
             dep.manifest_path.as_std_path(),
             &pkg.package_root,
             &pkg.meta.name,
             &version.full_version,
-            pkg.config.dependent_version(),
-            dry_run,
+            pkg.config.dependent_version_for(&pkg.meta.name),
+            pkg.config.dependent_prerelease(),
         )?;
     }
 
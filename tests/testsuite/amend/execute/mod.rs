@@ -0,0 +1,38 @@
+use cargo_test_support::compare::assert_ui;
+
+use crate::add_origin;
+use crate::git;
+use crate::git_from;
+use crate::init_registry;
+use crate::CargoCommand;
+use cargo_test_support::curr_dir;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(curr_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    add_origin(&project);
+    project.process("git").arg("tag").arg("v0.1.0").run();
+    std::fs::write(project_root.join("src/lib.rs"), "pub fn fixed() {}\n").unwrap();
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["--amend", "--no-publish", "--no-push", "-x", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_matches_path(curr_dir!().join("stdout.log"))
+        .stderr_matches_path(curr_dir!().join("stderr.log"));
+
+    assert_ui().subset_matches(curr_dir!().join("out"), &project_root);
+
+    assert_eq!(git(cwd, &["status", "--porcelain"]), "");
+    assert_eq!(git(cwd, &["rev-list", "--count", "HEAD"]), "1\n");
+    assert_eq!(
+        git(cwd, &["rev-parse", "v0.1.0^{commit}"]),
+        git(cwd, &["rev-parse", "HEAD"])
+    );
+}
@@ -0,0 +1,3 @@
+mod execute;
+mod not_release_commit;
+mod pushed;
@@ -0,0 +1,24 @@
+use crate::git;
+use crate::git_from;
+use crate::init_registry;
+use crate::CargoCommand;
+use cargo_test_support::curr_dir;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(curr_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["--amend", "--no-publish", "--no-push", "-x", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .code(101)
+        .stdout_matches_path(curr_dir!().join("stdout.log"))
+        .stderr_matches_path(curr_dir!().join("stderr.log"));
+
+    assert_eq!(git(cwd, &["rev-list", "--count", "HEAD"]), "1\n");
+}
@@ -0,0 +1,43 @@
+use cargo_test_support::compare::assert_ui;
+
+use crate::git;
+use crate::git_from;
+use crate::init_registry;
+use crate::CargoCommand;
+use crate::HttpStub;
+use cargo_test_support::curr_dir;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(curr_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    let stub = HttpStub::new(200, r#"{"allow": true}"#);
+    let config = cargo_test_support::paths::root().join("approval.toml");
+    std::fs::write(
+        &config,
+        format!("[approval]\nwebhook = \"{}/approve\"\n", stub.url()),
+    )
+    .unwrap();
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["patch", "-x", "--no-confirm", "--no-publish", "--no-push"])
+        .arg("--config")
+        .arg(&config)
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_matches_path(curr_dir!().join("stdout.log"))
+        .stderr_matches_path(curr_dir!().join("stderr.log"));
+
+    assert_ui().subset_matches(curr_dir!().join("out"), &project_root);
+
+    assert_eq!(git(cwd, &["rev-list", "--count", "HEAD"]), "2\n");
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("POST /approve HTTP/1.1\n"));
+    assert!(requests[0].contains(r#""to":"0.1.1""#));
+}
@@ -0,0 +1,3 @@
+mod approved;
+mod denied;
+mod timeout;
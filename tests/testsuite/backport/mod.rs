@@ -0,0 +1,3 @@
+mod minor_bump;
+mod not_version_line;
+mod patch;
@@ -0,0 +1 @@
+mod extends_package;
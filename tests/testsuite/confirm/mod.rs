@@ -0,0 +1,4 @@
+mod push_declined;
+mod release_declined;
+mod yes_push;
+mod yes_release;
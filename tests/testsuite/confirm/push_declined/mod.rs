@@ -0,0 +1,36 @@
+use cargo_test_support::compare::assert_ui;
+
+use crate::add_origin;
+use crate::git;
+use crate::git_from;
+use crate::init_registry;
+use crate::CargoCommand;
+use cargo_test_support::curr_dir;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(curr_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    let remote = add_origin(&project);
+    git(cwd, &["push", "origin", "master"]);
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["patch", "-x", "--no-publish"])
+        .stdin("n\n")
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_matches_path(curr_dir!().join("stdout.log"))
+        .stderr_matches_path(curr_dir!().join("stderr.log"));
+
+    assert_ui().subset_matches(curr_dir!().join("out"), &project_root);
+
+    // Released locally, without asking, but never pushed
+    assert_eq!(git(cwd, &["rev-list", "--count", "HEAD"]), "2\n");
+    assert_eq!(git(&remote, &["rev-list", "--count", "master"]), "1\n");
+    assert_eq!(git(&remote, &["tag", "--list"]), "");
+}
@@ -0,0 +1,33 @@
+use crate::git;
+use crate::git_from;
+use crate::init_registry;
+use crate::CargoCommand;
+use cargo_test_support::curr_dir;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(curr_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+    project.process("git").arg("tag").arg("v0.1.0").run();
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args([
+            "delete-tag",
+            "v0.1.0",
+            "--local",
+            "--reason",
+            "yanked",
+            "--execute",
+            "--no-confirm",
+        ])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_matches_path(curr_dir!().join("stdout.log"))
+        .stderr_matches_path(curr_dir!().join("stderr.log"));
+
+    assert_eq!(git(cwd, &["tag"]), "");
+}
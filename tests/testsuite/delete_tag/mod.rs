@@ -0,0 +1,3 @@
+mod dry_run;
+mod execute;
+mod missing_tag;
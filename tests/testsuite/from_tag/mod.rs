@@ -0,0 +1,3 @@
+mod missing_tag;
+mod off_head;
+mod version_mismatch;
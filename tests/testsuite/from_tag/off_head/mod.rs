@@ -0,0 +1,38 @@
+use cargo_test_support::compare::assert_ui;
+
+use crate::git;
+use crate::git_from;
+use crate::init_registry;
+use crate::CargoCommand;
+use cargo_test_support::curr_dir;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(curr_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    git(cwd, &["tag", "v0.1.0"]);
+    let manifest = std::fs::read_to_string(project_root.join("Cargo.toml")).unwrap();
+    project.change_file(
+        "Cargo.toml",
+        &manifest.replace(r#"version = "0.1.0""#, r#"version = "0.2.0""#),
+    );
+    project.process("cargo").arg("generate-lockfile").run();
+    git(cwd, &["commit", "-am", "Start 0.2.0"]);
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["--from-tag", "v0.1.0", "--no-confirm"])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_matches_path(curr_dir!().join("stdout.log"))
+        .stderr_matches_path(curr_dir!().join("stderr.log"));
+
+    assert_ui().subset_matches(curr_dir!().join("out"), &project_root);
+
+    assert_eq!(git(cwd, &["status", "--porcelain"]), "");
+    assert!(git(cwd, &["describe", "--tags"]).starts_with("v0.1.0-1-"));
+}
@@ -0,0 +1,4 @@
+mod abort;
+mod dry_run;
+mod on_failure_warn;
+mod ordered;
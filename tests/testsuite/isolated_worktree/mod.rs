@@ -0,0 +1,3 @@
+mod fail_after_commit;
+mod fail_before_commit;
+mod success;
@@ -0,0 +1,43 @@
+use cargo_test_support::compare::assert_ui;
+
+use crate::git;
+use crate::git_from;
+use crate::init_registry;
+use crate::CargoCommand;
+use cargo_test_support::curr_dir;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(curr_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    let assert = snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args([
+            "patch",
+            "--isolated-worktree",
+            "-x",
+            "--no-confirm",
+            "--no-publish",
+            "--no-push",
+        ])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_matches_path(curr_dir!().join("stdout.log"))
+        .stderr_matches_path(curr_dir!().join("stderr.log"));
+
+    assert_ui().subset_matches(curr_dir!().join("out"), &project_root);
+
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).into_owned();
+    let scratch = stderr
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("Isolating release in "))
+        .unwrap();
+    assert!(!std::path::Path::new(scratch).exists());
+
+    assert_eq!(git(cwd, &["status", "--porcelain"]), "");
+    assert_eq!(git(cwd, &["tag", "--list"]), "v0.1.1\n");
+}
@@ -5,12 +5,49 @@
 #[macro_use]
 extern crate cargo_test_macro;
 
+use stub::HttpStub;
+
+mod amend;
+mod approval;
+mod backport;
+mod config;
+mod confirm;
+mod delete_tag;
+mod from_tag;
+mod hook;
+mod isolated_worktree;
+mod new_fragment;
+mod notify;
+mod promote_stable;
+mod registry;
+mod rehearse;
+mod skip;
+mod split_commits;
+mod stub;
+mod verify_command;
 mod version;
+mod workspaces;
 
 fn init_registry() {
     cargo_test_support::registry::init();
 }
 
+/// A `--config` file looking crates up in [`init_registry`]'s index instead of crates.io's
+///
+/// `extra` is added at the top level and `registry` to the `[registries.crates-io]` table.
+pub fn local_index_config(extra: &str, registry: &str) -> std::path::PathBuf {
+    let config = cargo_test_support::paths::root().join("local-index.toml");
+    std::fs::write(
+        &config,
+        format!(
+            "{extra}\n[registries.crates-io]\nindex = \"{}\"\n{registry}",
+            cargo_test_support::registry::registry_url()
+        ),
+    )
+    .unwrap();
+    config
+}
+
 pub fn git_from(template: impl AsRef<std::path::Path>) -> cargo_test_support::Project {
     create_default_gitconfig();
     let project = cargo_test_support::Project::from_template(template.as_ref());
@@ -21,6 +58,26 @@ pub fn git_from(template: impl AsRef<std::path::Path>) -> cargo_test_support::Pr
     project
 }
 
+/// Like [`git_from`] for a repo holding separate workspaces at `roots`
+pub fn git_from_workspaces(
+    template: impl AsRef<std::path::Path>,
+    roots: &[&str],
+) -> cargo_test_support::Project {
+    create_default_gitconfig();
+    let project = cargo_test_support::Project::from_template(template.as_ref());
+    for root in roots {
+        project
+            .process("cargo")
+            .arg("generate-lockfile")
+            .cwd(project.root().join(root))
+            .run();
+    }
+    let repo = cargo_test_support::git::init(&project.root());
+    cargo_test_support::git::add(&repo);
+    cargo_test_support::git::commit(&repo);
+    project
+}
+
 fn create_default_gitconfig() {
     // If we're running this under a user account that has a different default branch set up
     // then tests that assume the default branch is master will fail. We set the default branch
@@ -36,6 +93,41 @@ fn create_default_gitconfig() {
     .unwrap();
 }
 
+/// Give `project` an `origin` remote backed by a bare repo, returning the remote's path
+pub fn add_origin(project: &cargo_test_support::Project) -> std::path::PathBuf {
+    let remote = cargo_test_support::paths::root().join("origin.git");
+    project
+        .process("git")
+        .arg("init")
+        .arg("--bare")
+        .arg(&remote)
+        .run();
+    project
+        .process("git")
+        .arg("remote")
+        .arg("add")
+        .arg("origin")
+        .arg(&remote)
+        .run();
+    remote
+}
+
+/// Run `git` in `dir`, returning its stdout
+pub fn git(dir: &std::path::Path, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
 pub fn cargo_exe() -> std::path::PathBuf {
     snapbox::cmd::cargo_bin("cargo-release")
 }
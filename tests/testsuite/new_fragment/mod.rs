@@ -0,0 +1,3 @@
+mod issue;
+mod not_configured;
+mod numeric_slug;
@@ -0,0 +1 @@
+mod no_push;
@@ -0,0 +1,3 @@
+mod dry_run;
+mod fold_changelog;
+mod no_prerelease;
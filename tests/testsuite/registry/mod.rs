@@ -0,0 +1,4 @@
+mod new_name_resembles;
+mod newer_version_published;
+mod rate_limited;
+mod unhealthy;
@@ -0,0 +1,46 @@
+use cargo_test_support::compare::assert_ui;
+
+use crate::git_from;
+use crate::init_registry;
+use crate::local_index_config;
+use crate::CargoCommand;
+use crate::HttpStub;
+use cargo_test_support::curr_dir;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(curr_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    let stub = HttpStub::new(
+        200,
+        r#"{"status":{"indicator":"major","description":"Publishing is paused"}}"#,
+    );
+    let config = local_index_config(
+        r#"registry-health = "error""#,
+        &format!("status-url = \"{}/status.json\"\n", stub.url()),
+    );
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args([
+            "patch",
+            "-x",
+            "--no-confirm",
+            "--no-push",
+            "--allow-first-release",
+            "--fail-at",
+            "commit",
+        ])
+        .arg("--config")
+        .arg(&config)
+        .current_dir(cwd)
+        .assert()
+        .code(101)
+        .stdout_matches_path(curr_dir!().join("stdout.log"))
+        .stderr_matches_path(curr_dir!().join("stderr.log"));
+
+    assert_ui().subset_matches(curr_dir!().join("out"), &project_root);
+}
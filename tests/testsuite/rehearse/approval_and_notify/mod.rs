@@ -0,0 +1,44 @@
+use cargo_test_support::compare::assert_ui;
+
+use crate::git;
+use crate::git_from;
+use crate::init_registry;
+use crate::CargoCommand;
+use crate::HttpStub;
+use cargo_test_support::curr_dir;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from(curr_dir!().join("in"));
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    let stub = HttpStub::new(200, r#"{"allow": true}"#);
+    let config = cargo_test_support::paths::root().join("rehearsal.toml");
+    std::fs::write(
+        &config,
+        format!(
+            "[approval]\nwebhook = \"{url}/approve\"\n\n[notify.matrix]\nhomeserver = \"{url}\"\nroom-id = \"!room:example.org\"\n",
+            url = stub.url()
+        ),
+    )
+    .unwrap();
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args(["patch", "--rehearse", "--no-confirm"])
+        .arg("--config")
+        .arg(&config)
+        .env("MATRIX_ACCESS_TOKEN", "secret")
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_matches_path(curr_dir!().join("stdout.log"))
+        .stderr_matches_path(curr_dir!().join("stderr.log"));
+
+    assert_ui().subset_matches(curr_dir!().join("out"), &project_root);
+
+    assert_eq!(git(cwd, &["status", "--porcelain"]), "");
+    assert_eq!(stub.requests(), Vec::<String>::new());
+}
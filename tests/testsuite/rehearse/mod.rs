@@ -0,0 +1 @@
+mod approval_and_notify;
@@ -0,0 +1,2 @@
+mod steps;
+mod unknown_step;
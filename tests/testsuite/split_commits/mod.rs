@@ -0,0 +1,2 @@
+mod replacements;
+mod version_only;
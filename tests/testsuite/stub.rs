@@ -0,0 +1,79 @@
+use std::io::BufRead as _;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// A local HTTP server answering every request the same way, recording what it was sent
+pub struct HttpStub {
+    url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl HttpStub {
+    pub fn new(status: u16, body: &str) -> Self {
+        Self::with_delay(status, body, std::time::Duration::ZERO)
+    }
+
+    /// Answer only after `delay`, e.g. to outlast a timeout
+    pub fn with_delay(status: u16, body: &str, delay: std::time::Duration) -> Self {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        let body = body.to_owned();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                let request = read_request(&stream);
+                recorded.lock().unwrap().push(request);
+                std::thread::sleep(delay);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Each request so far, as its request line followed by its body
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn read_request(stream: &std::net::TcpStream) -> String {
+    let mut reader = std::io::BufReader::new(stream);
+    let mut request_line = String::new();
+    let _ = reader.read_line(&mut request_line);
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    let _ = reader.read_exact(&mut body);
+    format!(
+        "{}\n{}",
+        request_line.trim_end(),
+        String::from_utf8_lossy(&body)
+    )
+}
@@ -0,0 +1,2 @@
+mod fails;
+mod passes;
@@ -0,0 +1,43 @@
+use cargo_test_support::compare::assert_ui;
+
+use crate::git;
+use crate::git_from_workspaces;
+use crate::init_registry;
+use crate::CargoCommand;
+use cargo_test_support::curr_dir;
+
+#[cargo_test]
+fn case() {
+    init_registry();
+    let project = git_from_workspaces(curr_dir!().join("in"), &["a", "b"]);
+    let project_root = project.root();
+    let cwd = &project_root;
+
+    snapbox::cmd::Command::cargo_ui()
+        .arg("release")
+        .args([
+            "patch",
+            "--workspace-root",
+            "b",
+            "--workspace-root",
+            "a",
+            "-x",
+            "--no-confirm",
+            "--no-publish",
+            "--no-push",
+        ])
+        .current_dir(cwd)
+        .assert()
+        .success()
+        .stdout_matches_path(curr_dir!().join("stdout.log"))
+        .stderr_matches_path(curr_dir!().join("stderr.log"));
+
+    assert_ui().subset_matches(curr_dir!().join("out"), &project_root);
+
+    // `beta` depends on `alpha`, so its workspace goes second despite being listed first
+    assert_eq!(
+        git(cwd, &["log", "--format=%s", "-2"]),
+        "chore: Release 0.2.1\nchore: Release 0.1.1\n"
+    );
+    assert_eq!(git(cwd, &["tag", "--list"]), "v0.1.1\nv0.2.1\n");
+}
@@ -0,0 +1,2 @@
+mod check_failure;
+mod dependency_order;